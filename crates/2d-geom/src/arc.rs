@@ -0,0 +1,309 @@
+use crate::ellipse::{arc_length, transform_axes};
+use crate::{Ellipse, Scalar};
+use euclid::{point2, vec2, Angle, Point2D, Transform2D, Vector2D};
+use fart_aabb::{Aabb, ToAabb};
+use std::f64::consts::PI;
+
+/// An elliptical arc: part of an ellipse, between two parametric angles. See
+/// `Ellipse` for how angles are parameterized.
+///
+/// Circular arcs are elliptical arcs with equal radii.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Arc<T, U> {
+    /// The center of the arc's ellipse.
+    pub center: Point2D<T, U>,
+    /// The radii along the arc's ellipse's own x and y axes.
+    pub radii: Vector2D<T, U>,
+    /// The rotation of the arc's ellipse's x axis from the x axis.
+    pub rotation: Angle<T>,
+    /// The parametric angle that the arc starts at.
+    pub start_angle: Angle<T>,
+    /// How far the arc sweeps around its ellipse from its start angle.
+    /// Positive sweeps go from the ellipse's x axis towards its y axis, and
+    /// negative sweeps go the other way.
+    pub sweep_angle: Angle<T>,
+}
+
+impl<T, U> Arc<T, U> {
+    /// Create a new arc around the ellipse with the given center, radii, and
+    /// rotation, from the start angle and sweeping through the sweep angle.
+    #[inline]
+    pub fn new(
+        center: Point2D<T, U>,
+        radii: Vector2D<T, U>,
+        rotation: Angle<T>,
+        start_angle: Angle<T>,
+        sweep_angle: Angle<T>,
+    ) -> Arc<T, U> {
+        Arc {
+            center,
+            radii,
+            rotation,
+            start_angle,
+            sweep_angle,
+        }
+    }
+}
+
+impl<T, U> Arc<T, U>
+where
+    T: Scalar,
+{
+    /// Cast from number representation `T` to number representation `V`.
+    #[inline]
+    pub fn cast<V>(&self) -> Arc<V, U>
+    where
+        V: Scalar,
+    {
+        let angle = |a: Angle<T>| Angle::radians(V::from(a.radians).unwrap());
+        Arc::new(
+            self.center.cast(),
+            self.radii.cast(),
+            angle(self.rotation),
+            angle(self.start_angle),
+            angle(self.sweep_angle),
+        )
+    }
+}
+
+impl<U> Arc<f64, U> {
+    /// Create an arc from SVG's endpoint parameterization: the arc from `from`
+    /// to `to` around an ellipse with the given radii and rotation. Of the
+    /// four such arcs, the large arc flag picks one that sweeps more than 180
+    /// degrees, and the sweep flag picks one that sweeps in the positive angle
+    /// direction.
+    ///
+    /// Radii that are too small to reach from `from` to `to` are scaled up
+    /// until they do.
+    ///
+    /// Returns `None` if the arc is degenerate: if `from` and `to` are the
+    /// same it is empty, and if either radius is zero it is a straight line.
+    ///
+    /// ```
+    /// use euclid::{point2, vec2, Angle, UnknownUnit};
+    /// use fart_2d_geom::Arc;
+    ///
+    /// // The top half of a circle of radius 10 around the origin.
+    /// let arc = Arc::<f64, UnknownUnit>::from_endpoints(
+    ///     point2(10.0, 0.0),
+    ///     point2(-10.0, 0.0),
+    ///     vec2(10.0, 10.0),
+    ///     Angle::zero(),
+    ///     false,
+    ///     true,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert!((arc.center - point2(0.0, 0.0)).length() < 1e-12);
+    /// assert!((arc.sweep_angle.radians - std::f64::consts::PI).abs() < 1e-12);
+    /// assert!((arc.point_at(0.5) - point2(0.0, 10.0)).length() < 1e-12);
+    /// ```
+    pub fn from_endpoints(
+        from: Point2D<f64, U>,
+        to: Point2D<f64, U>,
+        radii: Vector2D<f64, U>,
+        rotation: Angle<f64>,
+        large_arc_flag: bool,
+        sweep_flag: bool,
+    ) -> Option<Arc<f64, U>> {
+        if from == to || radii.x == 0.0 || radii.y == 0.0 {
+            return None;
+        }
+
+        // See https://www.w3.org/TR/SVG11/implnote.html#ArcConversionEndpointToCenter
+        let (sin, cos) = rotation.radians.sin_cos();
+        let half = (from - to) / 2.0;
+        let x1 = cos * half.x + sin * half.y;
+        let y1 = -sin * half.x + cos * half.y;
+
+        // Scale up radii that are too small to reach from `from` to `to`.
+        let (mut rx, mut ry) = (radii.x.abs(), radii.y.abs());
+        let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+        if lambda > 1.0 {
+            rx *= lambda.sqrt();
+            ry *= lambda.sqrt();
+        }
+
+        let numerator = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+        let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+        let mut coefficient = (numerator / denominator).max(0.0).sqrt();
+        if large_arc_flag == sweep_flag {
+            coefficient = -coefficient;
+        }
+        let cx1 = coefficient * rx * y1 / ry;
+        let cy1 = -coefficient * ry * x1 / rx;
+
+        let middle = from.lerp(to, 0.5);
+        let center = point2(
+            cos * cx1 - sin * cy1 + middle.x,
+            sin * cx1 + cos * cy1 + middle.y,
+        );
+
+        let angle =
+            |ux: f64, uy: f64, vx: f64, vy: f64| (ux * vy - uy * vx).atan2(ux * vx + uy * vy);
+        let (ux, uy) = ((x1 - cx1) / rx, (y1 - cy1) / ry);
+        let (vx, vy) = ((-x1 - cx1) / rx, (-y1 - cy1) / ry);
+        let start_angle = angle(1.0, 0.0, ux, uy);
+        let mut sweep_angle = angle(ux, uy, vx, vy);
+        if !sweep_flag && sweep_angle > 0.0 {
+            sweep_angle -= 2.0 * PI;
+        } else if sweep_flag && sweep_angle < 0.0 {
+            sweep_angle += 2.0 * PI;
+        }
+
+        Some(Arc::new(
+            center,
+            vec2(rx, ry),
+            rotation,
+            Angle::radians(start_angle),
+            Angle::radians(sweep_angle),
+        ))
+    }
+
+    /// Get the ellipse that this arc is a part of.
+    #[inline]
+    pub fn ellipse(&self) -> Ellipse<f64, U> {
+        Ellipse::new(self.center, self.radii, self.rotation)
+    }
+
+    /// Get the point this arc starts at.
+    #[inline]
+    pub fn start(&self) -> Point2D<f64, U> {
+        self.point_at(0.0)
+    }
+
+    /// Get the point this arc ends at.
+    #[inline]
+    pub fn end(&self) -> Point2D<f64, U> {
+        self.point_at(1.0)
+    }
+
+    /// Get the point on this arc at `t`, where `0` is the start of the arc,
+    /// `1` is its end, and values in between sweep evenly through the angles
+    /// between them.
+    pub fn point_at(&self, t: f64) -> Point2D<f64, U> {
+        self.ellipse().point_at(self.angle_at(t))
+    }
+
+    /// Get the unit tangent vector of this arc at `t`, pointing in the
+    /// direction that the arc travels from its start to its end.
+    ///
+    /// ```
+    /// use euclid::{point2, vec2, Angle, UnknownUnit};
+    /// use fart_2d_geom::Arc;
+    ///
+    /// // A clockwise quarter circle, from the top of the circle to the right.
+    /// let arc = Arc::<f64, UnknownUnit>::new(
+    ///     point2(0.0, 0.0),
+    ///     vec2(1.0, 1.0),
+    ///     Angle::zero(),
+    ///     Angle::frac_pi_2(),
+    ///     -Angle::frac_pi_2(),
+    /// );
+    ///
+    /// assert!((arc.tangent_at(0.0) - vec2(1.0, 0.0)).length() < 1e-12);
+    /// assert!((arc.tangent_at(1.0) - vec2(0.0, -1.0)).length() < 1e-12);
+    /// ```
+    pub fn tangent_at(&self, t: f64) -> Vector2D<f64, U> {
+        let tangent = self.ellipse().tangent_at(self.angle_at(t));
+        if self.sweep_angle.radians < 0.0 {
+            -tangent
+        } else {
+            tangent
+        }
+    }
+
+    /// Get the length of this arc.
+    ///
+    /// ```
+    /// use euclid::{point2, vec2, Angle, UnknownUnit};
+    /// use fart_2d_geom::Arc;
+    ///
+    /// let arc = Arc::<f64, UnknownUnit>::new(
+    ///     point2(0.0, 0.0),
+    ///     vec2(2.0, 2.0),
+    ///     Angle::zero(),
+    ///     Angle::zero(),
+    ///     Angle::frac_pi_2(),
+    /// );
+    /// assert_eq!(arc.length(), std::f64::consts::PI);
+    /// ```
+    pub fn length(&self) -> f64 {
+        arc_length(
+            self.radii,
+            self.start_angle.radians,
+            self.start_angle.radians + self.sweep_angle.radians,
+        )
+    }
+
+    /// Transform this arc with the given linear transformation and return the
+    /// new, transformed arc.
+    ///
+    /// ```
+    /// use euclid::{point2, vec2, Angle, Transform2D, UnknownUnit};
+    /// use fart_2d_geom::Arc;
+    ///
+    /// let arc = Arc::<f64, UnknownUnit>::new(
+    ///     point2(0.0, 0.0),
+    ///     vec2(1.0, 1.0),
+    ///     Angle::zero(),
+    ///     Angle::zero(),
+    ///     Angle::frac_pi_2(),
+    /// );
+    ///
+    /// // Flipping the arc upside down flips the direction it sweeps in.
+    /// let flipped = arc.transform(&Transform2D::<_, _, UnknownUnit>::create_scale(2.0, -1.0));
+    /// assert!((flipped.start() - point2(2.0, 0.0)).length() < 1e-12);
+    /// assert!((flipped.end() - point2(0.0, -1.0)).length() < 1e-12);
+    /// ```
+    pub fn transform<V>(&self, transformation: &Transform2D<f64, U, V>) -> Arc<f64, V> {
+        let axes = transform_axes(self.radii, self.rotation, transformation);
+        Arc::new(
+            transformation.transform_point(self.center),
+            axes.radii,
+            axes.rotation,
+            axes.angle(self.start_angle),
+            axes.sweep(self.sweep_angle),
+        )
+    }
+
+    /// Transform this arc in place with the given linear transformation.
+    pub fn transform_in_place(&mut self, transformation: &Transform2D<f64, U, U>) {
+        *self = self.transform(transformation);
+    }
+
+    /// The parametric angle at `t` along this arc.
+    fn angle_at(&self, t: f64) -> Angle<f64> {
+        self.start_angle + self.sweep_angle * t
+    }
+
+    /// Does this arc sweep over the given parametric angle?
+    fn sweeps_over(&self, theta: f64) -> bool {
+        let turn = 2.0 * PI;
+        let (start, sweep) = (self.start_angle.radians, self.sweep_angle.radians);
+        if sweep.abs() >= turn {
+            true
+        } else if sweep >= 0.0 {
+            (theta - start).rem_euclid(turn) <= sweep
+        } else {
+            (start - theta).rem_euclid(turn) <= -sweep
+        }
+    }
+}
+
+impl<U> ToAabb<f64, U> for Arc<f64, U> {
+    fn to_aabb(&self) -> Aabb<f64, U> {
+        // Each axis has two extrema on the full ellipse, half a turn apart.
+        let (sin, cos) = self.rotation.radians.sin_cos();
+        let x = (-self.radii.y * sin).atan2(self.radii.x * cos);
+        let y = (self.radii.y * cos).atan2(self.radii.x * sin);
+        let ellipse = self.ellipse();
+        Aabb::for_vertices(
+            vec![x, x + PI, y, y + PI]
+                .into_iter()
+                .filter(|&theta| self.sweeps_over(theta))
+                .map(|theta| ellipse.point_at(Angle::radians(theta)))
+                .chain(vec![self.start(), self.end()]),
+        )
+    }
+}
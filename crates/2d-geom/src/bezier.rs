@@ -0,0 +1,390 @@
+use crate::{integrate, Scalar};
+use euclid::{Point2D, Transform2D, Vector2D};
+use fart_aabb::{Aabb, ToAabb};
+
+/// A quadratic Bézier curve.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QuadraticBezier<T, U> {
+    /// The point the curve starts at.
+    pub start: Point2D<T, U>,
+    /// The control point describing the curve.
+    pub control: Point2D<T, U>,
+    /// The point the curve ends at.
+    pub end: Point2D<T, U>,
+}
+
+/// A cubic Bézier curve.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CubicBezier<T, U> {
+    /// The point the curve starts at.
+    pub start: Point2D<T, U>,
+    /// The first control point, describing the curve out from the start.
+    pub control_1: Point2D<T, U>,
+    /// The second control point, describing the curve into the end.
+    pub control_2: Point2D<T, U>,
+    /// The point the curve ends at.
+    pub end: Point2D<T, U>,
+}
+
+impl<T, U> QuadraticBezier<T, U> {
+    /// Create a new quadratic Bézier curve.
+    #[inline]
+    pub fn new(
+        start: Point2D<T, U>,
+        control: Point2D<T, U>,
+        end: Point2D<T, U>,
+    ) -> QuadraticBezier<T, U> {
+        QuadraticBezier {
+            start,
+            control,
+            end,
+        }
+    }
+}
+
+impl<T, U> CubicBezier<T, U> {
+    /// Create a new cubic Bézier curve.
+    #[inline]
+    pub fn new(
+        start: Point2D<T, U>,
+        control_1: Point2D<T, U>,
+        control_2: Point2D<T, U>,
+        end: Point2D<T, U>,
+    ) -> CubicBezier<T, U> {
+        CubicBezier {
+            start,
+            control_1,
+            control_2,
+            end,
+        }
+    }
+}
+
+impl<T, U> QuadraticBezier<T, U>
+where
+    T: Scalar,
+{
+    /// Cast from number representation `T` to number representation `V`.
+    #[inline]
+    pub fn cast<V>(&self) -> QuadraticBezier<V, U>
+    where
+        V: Scalar,
+    {
+        QuadraticBezier::new(self.start.cast(), self.control.cast(), self.end.cast())
+    }
+}
+
+impl<T, U> CubicBezier<T, U>
+where
+    T: Scalar,
+{
+    /// Cast from number representation `T` to number representation `V`.
+    #[inline]
+    pub fn cast<V>(&self) -> CubicBezier<V, U>
+    where
+        V: Scalar,
+    {
+        CubicBezier::new(
+            self.start.cast(),
+            self.control_1.cast(),
+            self.control_2.cast(),
+            self.end.cast(),
+        )
+    }
+}
+
+impl<T, U> QuadraticBezier<T, U>
+where
+    T: Scalar,
+{
+    /// Transform this curve with the given linear transformation and return
+    /// the new, transformed curve.
+    ///
+    /// ```
+    /// use euclid::{point2, Transform2D, UnknownUnit};
+    /// use fart_2d_geom::QuadraticBezier;
+    ///
+    /// let curve = QuadraticBezier::<i32, UnknownUnit>::new(
+    ///     point2(0, 0),
+    ///     point2(1, 2),
+    ///     point2(2, 0),
+    /// );
+    /// let curve = curve.transform(&Transform2D::<_, _, UnknownUnit>::create_translation(1, 1));
+    /// assert_eq!(curve.control, point2(2, 3));
+    /// ```
+    #[inline]
+    pub fn transform<V>(&self, transformation: &Transform2D<T, U, V>) -> QuadraticBezier<T, V> {
+        QuadraticBezier::new(
+            transformation.transform_point(self.start),
+            transformation.transform_point(self.control),
+            transformation.transform_point(self.end),
+        )
+    }
+
+    /// Transform this curve in place with the given linear transformation.
+    #[inline]
+    pub fn transform_in_place(&mut self, transformation: &Transform2D<T, U, U>) {
+        self.start = transformation.transform_point(self.start);
+        self.control = transformation.transform_point(self.control);
+        self.end = transformation.transform_point(self.end);
+    }
+}
+
+impl<T, U> CubicBezier<T, U>
+where
+    T: Scalar,
+{
+    /// Transform this curve with the given linear transformation and return
+    /// the new, transformed curve.
+    ///
+    /// ```
+    /// use euclid::{point2, Transform2D, UnknownUnit};
+    /// use fart_2d_geom::CubicBezier;
+    ///
+    /// let curve = CubicBezier::<i32, UnknownUnit>::new(
+    ///     point2(0, 0),
+    ///     point2(0, 2),
+    ///     point2(2, 2),
+    ///     point2(2, 0),
+    /// );
+    /// let curve = curve.transform(&Transform2D::<_, _, UnknownUnit>::create_scale(2, 3));
+    /// assert_eq!(curve.control_2, point2(4, 6));
+    /// ```
+    #[inline]
+    pub fn transform<V>(&self, transformation: &Transform2D<T, U, V>) -> CubicBezier<T, V> {
+        CubicBezier::new(
+            transformation.transform_point(self.start),
+            transformation.transform_point(self.control_1),
+            transformation.transform_point(self.control_2),
+            transformation.transform_point(self.end),
+        )
+    }
+
+    /// Transform this curve in place with the given linear transformation.
+    #[inline]
+    pub fn transform_in_place(&mut self, transformation: &Transform2D<T, U, U>) {
+        self.start = transformation.transform_point(self.start);
+        self.control_1 = transformation.transform_point(self.control_1);
+        self.control_2 = transformation.transform_point(self.control_2);
+        self.end = transformation.transform_point(self.end);
+    }
+}
+
+impl<U> QuadraticBezier<f64, U> {
+    /// Get the point on this curve at `t`, where `0` is its start and `1` is
+    /// its end.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::QuadraticBezier;
+    ///
+    /// let curve = QuadraticBezier::<f64, UnknownUnit>::new(
+    ///     point2(0.0, 0.0),
+    ///     point2(1.0, 2.0),
+    ///     point2(2.0, 0.0),
+    /// );
+    /// assert_eq!(curve.point_at(0.5), point2(1.0, 1.0));
+    /// ```
+    pub fn point_at(&self, t: f64) -> Point2D<f64, U> {
+        let u = 1.0 - t;
+        (self.start.to_vector() * (u * u)
+            + self.control.to_vector() * (2.0 * u * t)
+            + self.end.to_vector() * (t * t))
+            .to_point()
+    }
+
+    /// Get the unit tangent vector of this curve at `t`, pointing in the
+    /// direction the curve travels from its start to its end.
+    ///
+    /// Where the curve's derivative vanishes, such as at an end whose control
+    /// point coincides with it, this is the direction the curve leaves that
+    /// point in. If the curve is a single point, this is the zero vector.
+    ///
+    /// ```
+    /// use euclid::{point2, vec2, UnknownUnit};
+    /// use fart_2d_geom::QuadraticBezier;
+    ///
+    /// let curve = QuadraticBezier::<f64, UnknownUnit>::new(
+    ///     point2(0.0, 0.0),
+    ///     point2(0.0, 0.0),
+    ///     point2(3.0, 4.0),
+    /// );
+    /// assert_eq!(curve.tangent_at(0.0), vec2(0.6, 0.8));
+    /// ```
+    pub fn tangent_at(&self, t: f64) -> Vector2D<f64, U> {
+        let (p0, p1, p2) = (self.start, self.control, self.end);
+        let first = ((p1 - p0) * (1.0 - t) + (p2 - p1) * t) * 2.0;
+        let second = (p2 - p1 - (p1 - p0)) * 2.0;
+        direction(&[first, second])
+    }
+
+    /// Get the length of this curve.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::QuadraticBezier;
+    ///
+    /// // A straight line, with its control point in the middle.
+    /// let curve = QuadraticBezier::<f64, UnknownUnit>::new(
+    ///     point2(0.0, 0.0),
+    ///     point2(1.5, 2.0),
+    ///     point2(3.0, 4.0),
+    /// );
+    /// assert!((curve.length() - 5.0).abs() < 1e-12);
+    /// ```
+    pub fn length(&self) -> f64 {
+        let (p0, p1, p2) = (self.start, self.control, self.end);
+        let speed = |t: f64| (((p1 - p0) * (1.0 - t) + (p2 - p1) * t) * 2.0).length();
+        integrate(&speed, 0.0, 1.0)
+    }
+}
+
+impl<U> CubicBezier<f64, U> {
+    /// Get the point on this curve at `t`, where `0` is its start and `1` is
+    /// its end.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::CubicBezier;
+    ///
+    /// let curve = CubicBezier::<f64, UnknownUnit>::new(
+    ///     point2(0.0, 0.0),
+    ///     point2(0.0, 4.0),
+    ///     point2(4.0, 4.0),
+    ///     point2(4.0, 0.0),
+    /// );
+    /// assert_eq!(curve.point_at(0.5), point2(2.0, 3.0));
+    /// ```
+    pub fn point_at(&self, t: f64) -> Point2D<f64, U> {
+        let u = 1.0 - t;
+        (self.start.to_vector() * (u * u * u)
+            + self.control_1.to_vector() * (3.0 * u * u * t)
+            + self.control_2.to_vector() * (3.0 * u * t * t)
+            + self.end.to_vector() * (t * t * t))
+            .to_point()
+    }
+
+    /// Get the unit tangent vector of this curve at `t`, pointing in the
+    /// direction the curve travels from its start to its end.
+    ///
+    /// Where the curve's derivative vanishes, such as at an end whose control
+    /// point coincides with it, this is the direction the curve leaves that
+    /// point in. If the curve is a single point, this is the zero vector.
+    ///
+    /// ```
+    /// use euclid::{point2, vec2, UnknownUnit};
+    /// use fart_2d_geom::CubicBezier;
+    ///
+    /// let curve = CubicBezier::<f64, UnknownUnit>::new(
+    ///     point2(0.0, 0.0),
+    ///     point2(0.0, 4.0),
+    ///     point2(4.0, 4.0),
+    ///     point2(4.0, 0.0),
+    /// );
+    /// assert_eq!(curve.tangent_at(0.0), vec2(0.0, 1.0));
+    /// assert_eq!(curve.tangent_at(0.5), vec2(1.0, 0.0));
+    /// ```
+    pub fn tangent_at(&self, t: f64) -> Vector2D<f64, U> {
+        let (p0, p1, p2, p3) = (self.start, self.control_1, self.control_2, self.end);
+        let u = 1.0 - t;
+        let first = ((p1 - p0) * (u * u) + (p2 - p1) * (2.0 * u * t) + (p3 - p2) * (t * t)) * 3.0;
+        let second = ((p2 - p1 - (p1 - p0)) * u + (p3 - p2 - (p2 - p1)) * t) * 6.0;
+        let third = (p3 - p2 - (p2 - p1) * 2.0 + (p1 - p0)) * 6.0;
+        direction(&[first, second, third])
+    }
+
+    /// Get the length of this curve.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::CubicBezier;
+    ///
+    /// // A straight line, with its control points a third of the way along it
+    /// // from each end.
+    /// let curve = CubicBezier::<f64, UnknownUnit>::new(
+    ///     point2(0.0, 0.0),
+    ///     point2(1.0, 0.0),
+    ///     point2(2.0, 0.0),
+    ///     point2(3.0, 0.0),
+    /// );
+    /// assert!((curve.length() - 3.0).abs() < 1e-12);
+    /// ```
+    pub fn length(&self) -> f64 {
+        let (p0, p1, p2, p3) = (self.start, self.control_1, self.control_2, self.end);
+        let speed = |t: f64| {
+            let u = 1.0 - t;
+            (((p1 - p0) * (u * u) + (p2 - p1) * (2.0 * u * t) + (p3 - p2) * (t * t)) * 3.0).length()
+        };
+        integrate(&speed, 0.0, 1.0)
+    }
+}
+
+impl<U> ToAabb<f64, U> for QuadraticBezier<f64, U> {
+    fn to_aabb(&self) -> Aabb<f64, U> {
+        // The derivative is linear, with a root at `t` on each axis.
+        let root = |p0: f64, p1: f64, p2: f64| {
+            let denominator = p0 - 2.0 * p1 + p2;
+            if denominator == 0.0 {
+                None
+            } else {
+                Some((p0 - p1) / denominator)
+            }
+        };
+        let (p0, p1, p2) = (self.start, self.control, self.end);
+        let extrema = vec![root(p0.x, p1.x, p2.x), root(p0.y, p1.y, p2.y)];
+        Aabb::for_vertices(
+            extrema
+                .into_iter()
+                .flatten()
+                .filter(|t| 0.0 < *t && *t < 1.0)
+                .map(|t| self.point_at(t))
+                .chain(vec![p0, p2]),
+        )
+    }
+}
+
+impl<U> ToAabb<f64, U> for CubicBezier<f64, U> {
+    fn to_aabb(&self) -> Aabb<f64, U> {
+        // The derivative is quadratic, with up to two roots in `t` on each
+        // axis.
+        let roots = |p0: f64, p1: f64, p2: f64, p3: f64| {
+            let a = -p0 + 3.0 * p1 - 3.0 * p2 + p3;
+            let b = 2.0 * (p0 - 2.0 * p1 + p2);
+            let c = p1 - p0;
+            if a == 0.0 {
+                if b == 0.0 {
+                    vec![]
+                } else {
+                    vec![-c / b]
+                }
+            } else {
+                let discriminant = b * b - 4.0 * a * c;
+                if discriminant < 0.0 {
+                    vec![]
+                } else {
+                    let sqrt = discriminant.sqrt();
+                    vec![(-b + sqrt) / (2.0 * a), (-b - sqrt) / (2.0 * a)]
+                }
+            }
+        };
+        let (p0, p1, p2, p3) = (self.start, self.control_1, self.control_2, self.end);
+        let mut extrema = roots(p0.x, p1.x, p2.x, p3.x);
+        extrema.extend(roots(p0.y, p1.y, p2.y, p3.y));
+        Aabb::for_vertices(
+            extrema
+                .into_iter()
+                .filter(|t| 0.0 < *t && *t < 1.0)
+                .map(|t| self.point_at(t))
+                .chain(vec![p0, p3]),
+        )
+    }
+}
+
+/// The direction of the first of a curve's derivatives that doesn't vanish,
+/// or the zero vector if they all do.
+fn direction<U>(derivatives: &[Vector2D<f64, U>]) -> Vector2D<f64, U> {
+    derivatives
+        .iter()
+        .find(|d| d.square_length() > 0.0)
+        .map_or(Vector2D::zero(), |d| d.normalize())
+}
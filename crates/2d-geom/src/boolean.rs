@@ -0,0 +1,784 @@
+use crate::predicates::crossing_point;
+use crate::{is_counter_clockwise, orientation, Polygon, PolygonWithHoles, RelativeDirection};
+use euclid::{vec2, Point2D, Vector2D};
+use fart_aabb::Aabb;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+/// A boolean operation between two regions: the subject and the clip.
+///
+/// See `boolean` and `Polygon::intersection` et al.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BooleanOp {
+    /// The region covered by both the subject and the clip.
+    Intersection,
+
+    /// The region covered by either the subject or the clip, or both.
+    Union,
+
+    /// The region covered by the subject, but not the clip.
+    Difference,
+
+    /// The region covered by exactly one of the subject or the clip.
+    Xor,
+}
+
+impl BooleanOp {
+    /// Is a point that is (or is not) in the subject and (or is not) in the
+    /// clip, in the result of this operation?
+    #[inline]
+    fn contains(self, in_subject: bool, in_clip: bool) -> bool {
+        match self {
+            BooleanOp::Intersection => in_subject && in_clip,
+            BooleanOp::Union => in_subject || in_clip,
+            BooleanOp::Difference => in_subject && !in_clip,
+            BooleanOp::Xor => in_subject != in_clip,
+        }
+    }
+}
+
+/// Compute a boolean operation between two regions, each described by a set of
+/// polygons with holes.
+///
+/// The polygons may be concave, overlap each other, share edges, touch each
+/// other at vertices, or even be self-intersecting. A point is inside a region
+/// if its winding number with respect to the region's polygons is non-zero.
+///
+/// The result is a set of non-overlapping polygons with holes. Vertices that
+/// are collinear with their neighbors are removed from the result, and where
+/// the result touches itself at a single point, it is split into separate
+/// polygons there.
+///
+/// ```
+/// use euclid::{point2, UnknownUnit};
+/// use fart_2d_geom::{boolean, BooleanOp, Polygon, PolygonWithHoles};
+///
+/// let square = |x, y, size| -> PolygonWithHoles<f64, UnknownUnit> {
+///     Polygon::new(vec![
+///         point2(x, y),
+///         point2(x + size, y),
+///         point2(x + size, y + size),
+///         point2(x, y + size),
+///     ])
+///     .into()
+/// };
+///
+/// // Cut a hole out of the middle of a square, and then fill the hole back in.
+/// let frame = boolean(&[square(0.0, 0.0, 9.0)], &[square(3.0, 3.0, 3.0)], BooleanOp::Difference);
+/// assert_eq!(frame.len(), 1);
+/// assert_eq!(frame[0].holes().len(), 1);
+///
+/// let filled = boolean(&frame, &[square(3.0, 3.0, 3.0)], BooleanOp::Union);
+/// assert_eq!(filled.len(), 1);
+/// assert!(filled[0].holes().is_empty());
+/// assert_eq!(filled[0].exterior().len(), 4);
+/// ```
+pub fn boolean<U>(
+    subject: &[PolygonWithHoles<f64, U>],
+    clip: &[PolygonWithHoles<f64, U>],
+    op: BooleanOp,
+) -> Vec<PolygonWithHoles<f64, U>> {
+    // Holes are reversed, so that their insides have a winding number of zero.
+    let mut rings = vec![];
+    for (operand, polygons) in [subject, clip].iter().enumerate() {
+        for polygon in polygons.iter() {
+            rings.push((operand, polygon.exterior().vertices().to_vec()));
+            for hole in polygon.holes() {
+                let mut ring = hole.vertices().to_vec();
+                ring.reverse();
+                rings.push((operand, ring));
+            }
+        }
+    }
+    boolean_rings(rings, op)
+}
+
+impl<U> Polygon<f64, U> {
+    /// Get the region covered by both this polygon and `other`.
+    ///
+    /// See `boolean` for details.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::Polygon;
+    ///
+    /// let a = Polygon::<f64, UnknownUnit>::new(vec![
+    ///     point2(0.0, 0.0),
+    ///     point2(4.0, 0.0),
+    ///     point2(4.0, 4.0),
+    ///     point2(0.0, 4.0),
+    /// ]);
+    /// let b = Polygon::<f64, UnknownUnit>::new(vec![
+    ///     point2(2.0, 2.0),
+    ///     point2(6.0, 2.0),
+    ///     point2(6.0, 6.0),
+    ///     point2(2.0, 6.0),
+    /// ]);
+    ///
+    /// let intersection = a.intersection(&b);
+    /// assert_eq!(intersection.len(), 1);
+    /// assert_eq!(intersection[0].exterior().area(), 4.0);
+    /// ```
+    pub fn intersection(&self, other: &Polygon<f64, U>) -> Vec<PolygonWithHoles<f64, U>> {
+        self.boolean(other, BooleanOp::Intersection)
+    }
+
+    /// Get the region covered by either this polygon or `other`.
+    ///
+    /// See `boolean` for details.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::Polygon;
+    ///
+    /// let a = Polygon::<f64, UnknownUnit>::new(vec![
+    ///     point2(0.0, 0.0),
+    ///     point2(4.0, 0.0),
+    ///     point2(4.0, 4.0),
+    ///     point2(0.0, 4.0),
+    /// ]);
+    /// let b = Polygon::<f64, UnknownUnit>::new(vec![
+    ///     point2(2.0, 2.0),
+    ///     point2(6.0, 2.0),
+    ///     point2(6.0, 6.0),
+    ///     point2(2.0, 6.0),
+    /// ]);
+    ///
+    /// let union = a.union(&b);
+    /// assert_eq!(union.len(), 1);
+    /// assert_eq!(union[0].exterior().len(), 8);
+    /// assert_eq!(union[0].exterior().area(), 28.0);
+    /// ```
+    pub fn union(&self, other: &Polygon<f64, U>) -> Vec<PolygonWithHoles<f64, U>> {
+        self.boolean(other, BooleanOp::Union)
+    }
+
+    /// Get the region covered by this polygon but not `other`.
+    ///
+    /// See `boolean` for details.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::Polygon;
+    ///
+    /// let a = Polygon::<f64, UnknownUnit>::new(vec![
+    ///     point2(0.0, 0.0),
+    ///     point2(4.0, 0.0),
+    ///     point2(4.0, 4.0),
+    ///     point2(0.0, 4.0),
+    /// ]);
+    /// let b = Polygon::<f64, UnknownUnit>::new(vec![
+    ///     point2(2.0, 2.0),
+    ///     point2(6.0, 2.0),
+    ///     point2(6.0, 6.0),
+    ///     point2(2.0, 6.0),
+    /// ]);
+    ///
+    /// let difference = a.difference(&b);
+    /// assert_eq!(difference.len(), 1);
+    /// assert_eq!(difference[0].exterior().len(), 6);
+    /// assert_eq!(difference[0].exterior().area(), 12.0);
+    /// ```
+    pub fn difference(&self, other: &Polygon<f64, U>) -> Vec<PolygonWithHoles<f64, U>> {
+        self.boolean(other, BooleanOp::Difference)
+    }
+
+    /// Get the region covered by exactly one of this polygon or `other`.
+    ///
+    /// See `boolean` for details.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::Polygon;
+    ///
+    /// let a = Polygon::<f64, UnknownUnit>::new(vec![
+    ///     point2(0.0, 0.0),
+    ///     point2(4.0, 0.0),
+    ///     point2(4.0, 4.0),
+    ///     point2(0.0, 4.0),
+    /// ]);
+    /// let b = Polygon::<f64, UnknownUnit>::new(vec![
+    ///     point2(2.0, 2.0),
+    ///     point2(6.0, 2.0),
+    ///     point2(6.0, 6.0),
+    ///     point2(2.0, 6.0),
+    /// ]);
+    ///
+    /// // The two L-shapes left over touch at two corners, and are returned as
+    /// // separate polygons.
+    /// let xor = a.xor(&b);
+    /// assert_eq!(xor.len(), 2);
+    /// assert_eq!(xor[0].exterior().area() + xor[1].exterior().area(), 24.0);
+    /// ```
+    pub fn xor(&self, other: &Polygon<f64, U>) -> Vec<PolygonWithHoles<f64, U>> {
+        self.boolean(other, BooleanOp::Xor)
+    }
+
+    pub(crate) fn boolean(
+        &self,
+        other: &Polygon<f64, U>,
+        op: BooleanOp,
+    ) -> Vec<PolygonWithHoles<f64, U>> {
+        boolean_rings(
+            vec![
+                (0, self.vertices().to_vec()),
+                (1, other.vertices().to_vec()),
+            ],
+            op,
+        )
+    }
+}
+
+/// An edge of one of the input rings.
+struct Edge<U> {
+    a: Point2D<f64, U>,
+    b: Point2D<f64, U>,
+    operand: usize,
+}
+
+/// A set of coincident pieces of input edges, between two vertices.
+struct Group {
+    /// The smaller of the two vertex ids.
+    u: usize,
+    /// The larger of the two vertex ids.
+    v: usize,
+    /// For each operand, how much greater its winding number is on this
+    /// group's left side than on its right, looking from `u` to `v`.
+    delta: [i32; 2],
+}
+
+/// The core of `boolean`, working on the operands' rings. Each ring is tagged
+/// with its operand: `0` for the subject, and `1` for the clip.
+fn boolean_rings<U>(
+    rings: Vec<(usize, Vec<Point2D<f64, U>>)>,
+    op: BooleanOp,
+) -> Vec<PolygonWithHoles<f64, U>> {
+    let scale = rings
+        .iter()
+        .flat_map(|(_, ring)| ring.iter())
+        .fold(0.0_f64, |s, p| s.max(p.x.abs()).max(p.y.abs()));
+    if scale == 0.0 {
+        return vec![];
+    }
+
+    // Points that are within `eps` of each other are considered the same.
+    let eps = scale * 1e-10;
+
+    let mut edges = vec![];
+    for (operand, ring) in &rings {
+        for (i, &a) in ring.iter().enumerate() {
+            let b = ring[(i + 1) % ring.len()];
+            if a != b {
+                edges.push(Edge {
+                    a,
+                    b,
+                    operand: *operand,
+                });
+            }
+        }
+    }
+
+    // Find everywhere that edges cross or touch each other, and split them
+    // there. Sweep across the edges from left to right, so that only edges
+    // whose extents overlap along the x axis are compared.
+    let mut order = (0..edges.len()).collect::<Vec<_>>();
+    order.sort_by(|&i, &j| {
+        let (e, f) = (&edges[i], &edges[j]);
+        e.a.x.min(e.b.x).partial_cmp(&f.a.x.min(f.b.x)).unwrap()
+    });
+    let mut splits = edges
+        .iter()
+        .map(|e| vec![(0.0, e.a), (1.0, e.b)])
+        .collect::<Vec<_>>();
+    for (k, &i) in order.iter().enumerate() {
+        let e = &edges[i];
+        let (max_x, min_y, max_y) = (e.a.x.max(e.b.x), e.a.y.min(e.b.y), e.a.y.max(e.b.y));
+        for &j in &order[k + 1..] {
+            let f = &edges[j];
+            if f.a.x.min(f.b.x) > max_x + eps {
+                break;
+            }
+            if f.a.y.max(f.b.y) >= min_y - eps && f.a.y.min(f.b.y) <= max_y + eps {
+                split_pair(&edges, i, j, eps, &mut splits);
+            }
+        }
+    }
+
+    // Merge the split points into vertices, and group the pieces of edges
+    // between them.
+    let mut vertices = Vertices::new(eps);
+    let mut groups: Vec<Group> = vec![];
+    let mut group_ids = HashMap::new();
+    for (e, mut splits) in edges.iter().zip(splits) {
+        splits.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        let mut ids = splits.into_iter().map(|(_, p)| vertices.id(p));
+        let mut prev = ids.next().unwrap();
+        for id in ids {
+            if id == prev {
+                continue;
+            }
+            let (u, v) = if prev < id { (prev, id) } else { (id, prev) };
+            let g = *group_ids.entry((u, v)).or_insert_with(|| {
+                groups.push(Group {
+                    u,
+                    v,
+                    delta: [0, 0],
+                });
+                groups.len() - 1
+            });
+            groups[g].delta[e.operand] += if prev < id { 1 } else { -1 };
+            prev = id;
+        }
+    }
+    let vertices = vertices.points;
+
+    // Find each group's winding numbers on either side, and keep the groups
+    // that are on the boundary of the result, oriented so that the result is
+    // on their left.
+    let groups: Vec<Group> = groups.into_iter().filter(|g| g.delta != [0, 0]).collect();
+    let windings = face_windings(&vertices, &groups, eps);
+    let mut kept = vec![];
+    for (g, group) in groups.iter().enumerate() {
+        let (left, right) = (windings[2 * g], windings[2 * g + 1]);
+        let in_left = op.contains(left[0] != 0, left[1] != 0);
+        let in_right = op.contains(right[0] != 0, right[1] != 0);
+        if in_left && !in_right {
+            kept.push((group.u, group.v));
+        } else if in_right && !in_left {
+            kept.push((group.v, group.u));
+        }
+    }
+
+    let rings = trace_rings(&vertices, &kept);
+
+    // Sort the rings into exteriors and holes, and put each hole in the
+    // smallest exterior that contains it.
+    let mut exteriors = vec![];
+    let mut holes = vec![];
+    for ring in rings {
+        let mut ring = remove_collinear(ring.into_iter().map(|i| vertices[i]).collect(), eps);
+        if ring.len() < 3 {
+            continue;
+        }
+        let is_exterior = is_counter_clockwise(&ring);
+        if !is_exterior {
+            ring.reverse();
+        }
+        let ring = Polygon::new(ring);
+        let area = ring.area();
+        if area <= eps * scale {
+            continue;
+        }
+        if is_exterior {
+            let aabb = Aabb::for_vertices(ring.vertices().iter().cloned());
+            exteriors.push((area, aabb, ring, vec![]));
+        } else {
+            holes.push(ring);
+        }
+    }
+    for hole in holes {
+        let p = hole.vertices()[0].lerp(hole.vertices()[1], 0.5);
+        let mut candidates = exteriors
+            .iter_mut()
+            .filter(|(_, aabb, _, _)| aabb.contains_point(p))
+            .collect::<Vec<_>>();
+        // Every hole is inside some exterior, so there's no need to check
+        // when there is only one candidate.
+        if candidates.len() > 1 {
+            candidates.retain(|(_, _, exterior, _)| exterior.winding_number(p) != 0);
+        }
+        let container = candidates
+            .into_iter()
+            .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        if let Some((_, _, _, exterior_holes)) = container {
+            exterior_holes.push(hole);
+        }
+    }
+
+    exteriors
+        .into_iter()
+        .map(|(_, _, exterior, holes)| PolygonWithHoles::new(exterior, holes))
+        .collect()
+}
+
+/// Get the winding numbers on the left of each of the groups' half edges,
+/// where half edge `2 * g` goes from group `g`'s `u` to its `v`, and half edge
+/// `2 * g + 1` goes back again.
+///
+/// The groups form a planar graph, whose faces are traced first. Each
+/// connected component's outer face gets its winding numbers by casting a
+/// single ray across the other components, and from there the winding numbers
+/// are carried across each group into the neighboring face using its `delta`.
+fn face_windings<U>(vertices: &[Point2D<f64, U>], groups: &[Group], eps: f64) -> Vec<[i32; 2]> {
+    let n = groups.len() * 2;
+    let origin = |h: usize| {
+        if h & 1 == 0 {
+            groups[h / 2].u
+        } else {
+            groups[h / 2].v
+        }
+    };
+    let delta = |h: usize| {
+        let d = groups[h / 2].delta;
+        if h & 1 == 0 {
+            d
+        } else {
+            [-d[0], -d[1]]
+        }
+    };
+    let angle = |h: usize| {
+        let d: Vector2D<f64, U> = vertices[origin(h ^ 1)] - vertices[origin(h)];
+        d.y.atan2(d.x)
+    };
+
+    // Each vertex's outgoing half edges, in counter-clockwise order.
+    let mut outgoing = vec![vec![]; vertices.len()];
+    for h in 0..n {
+        outgoing[origin(h)].push(h);
+    }
+    let mut position = vec![0; n];
+    for out in &mut outgoing {
+        out.sort_by(|&a, &b| angle(a).partial_cmp(&angle(b)).unwrap());
+        for (i, &h) in out.iter().enumerate() {
+            position[h] = i;
+        }
+    }
+
+    // Trace the faces, turning as far left as possible at each vertex, so
+    // that each face is on the left of its half edges.
+    let next = |h: usize| {
+        let out = &outgoing[origin(h ^ 1)];
+        out[(position[h ^ 1] + out.len() - 1) % out.len()]
+    };
+    let mut face = vec![usize::MAX; n];
+    let mut faces: Vec<Vec<usize>> = vec![];
+    for h in 0..n {
+        if face[h] != usize::MAX {
+            continue;
+        }
+        let mut cycle = vec![];
+        let mut e = h;
+        while face[e] == usize::MAX {
+            face[e] = faces.len();
+            cycle.push(e);
+            e = next(e);
+        }
+        faces.push(cycle);
+    }
+
+    // Find the connected components, as sets of faces.
+    let mut component = vec![usize::MAX; faces.len()];
+    let mut components: Vec<Vec<usize>> = vec![];
+    for f in 0..faces.len() {
+        if component[f] != usize::MAX {
+            continue;
+        }
+        component[f] = components.len();
+        let mut members = vec![f];
+        let mut i = 0;
+        while i < members.len() {
+            for &h in &faces[members[i]] {
+                let neighbor = face[h ^ 1];
+                if component[neighbor] == usize::MAX {
+                    component[neighbor] = components.len();
+                    members.push(neighbor);
+                }
+            }
+            i += 1;
+        }
+        components.push(members);
+    }
+
+    let min_x = vertices.iter().fold(f64::INFINITY, |x, p| x.min(p.x));
+
+    let mut windings = vec![None; faces.len()];
+    for (c, members) in components.iter().enumerate() {
+        // The outer face is the one with the most negative area, since it is
+        // traced clockwise.
+        let face_area = |f: usize| {
+            faces[f]
+                .iter()
+                .map(|&h| {
+                    vertices[origin(h)]
+                        .to_vector()
+                        .cross(vertices[origin(h ^ 1)].to_vector())
+                })
+                .sum::<f64>()
+        };
+        let outer = members
+            .iter()
+            .map(|&f| (f, face_area(f)))
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+            .unwrap()
+            .0;
+
+        // Cast a ray leftwards from the component's leftmost vertex, and count
+        // the other components' groups that it crosses. The ray is tilted
+        // away from the axis, so that it crosses the many horizontal and
+        // vertical edges that polygons tend to have cleanly.
+        let m = faces[outer]
+            .iter()
+            .map(|&h| vertices[origin(h)])
+            .min_by(|a, b| (a.x, a.y).partial_cmp(&(b.x, b.y)).unwrap())
+            .unwrap();
+        let ray = vec2(-1.0, -0.0137);
+        let far = m + ray * (m.x - min_x + eps);
+        let mut winding = [0, 0];
+        for (g, group) in groups.iter().enumerate() {
+            let (p, q) = (vertices[group.u], vertices[group.v]);
+            let outside = p.x.min(q.x) > m.x || p.y.min(q.y) > m.y || p.y.max(q.y) < far.y;
+            if outside || component[face[2 * g]] == c {
+                continue;
+            }
+            // Vertices exactly on the ray's line are treated as if they were
+            // on its right.
+            let (p_left, q_left) = (ray.cross(p - m) > 0.0, ray.cross(q - m) > 0.0);
+            if p_left == q_left {
+                continue;
+            }
+            let t = (p - m).cross(q - p) / ray.cross(q - p);
+            if t > 0.0 {
+                let sign = if q_left { 1 } else { -1 };
+                winding[0] += sign * group.delta[0];
+                winding[1] += sign * group.delta[1];
+            }
+        }
+
+        windings[outer] = Some(winding);
+        let mut queue = vec![outer];
+        while let Some(f) = queue.pop() {
+            let w = windings[f].unwrap();
+            for &h in &faces[f] {
+                let neighbor = face[h ^ 1];
+                if windings[neighbor].is_none() {
+                    let d = delta(h);
+                    windings[neighbor] = Some([w[0] - d[0], w[1] - d[1]]);
+                    queue.push(neighbor);
+                }
+            }
+        }
+    }
+
+    face.iter().map(|&f| windings[f].unwrap()).collect()
+}
+
+/// Record where edges `i` and `j` cross or touch each other in `splits`, as
+/// the parameter along each edge and the point there.
+fn split_pair<U>(
+    edges: &[Edge<U>],
+    i: usize,
+    j: usize,
+    eps: f64,
+    splits: &mut [Vec<(f64, Point2D<f64, U>)>],
+) {
+    let (e, f) = (&edges[i], &edges[j]);
+
+    // Places where one edge's end point lies on the other edge. This also
+    // covers collinear overlaps.
+    let mut touching = false;
+    for &p in &[f.a, f.b] {
+        if let Some(t) = param_on(e.a, e.b, p, eps) {
+            splits[i].push((t, p));
+            touching = true;
+        }
+    }
+    for &p in &[e.a, e.b] {
+        if let Some(t) = param_on(f.a, f.b, p, eps) {
+            splits[j].push((t, p));
+            touching = true;
+        }
+    }
+    let near = |p: Point2D<f64, U>, q: Point2D<f64, U>| (p - q).length() <= eps;
+    if touching || near(e.a, f.a) || near(e.a, f.b) || near(e.b, f.a) || near(e.b, f.b) {
+        return;
+    }
+
+    // A proper crossing.
+    let straddles = |a: Point2D<f64, U>, b: Point2D<f64, U>, p, q| {
+        let (o1, o2) = (orientation(a, b, p), orientation(a, b, q));
+        o1 != RelativeDirection::Collinear && o2 != RelativeDirection::Collinear && o1 != o2
+    };
+    if straddles(e.a, e.b, f.a, f.b) && straddles(f.a, f.b, e.a, e.b) {
+        let p = crossing_point(e.a, e.b, f.a, f.b);
+        let (de, df) = (e.b - e.a, f.b - f.a);
+        let s = (p - e.a).dot(de) / de.square_length();
+        let t = (p - f.a).dot(df) / df.square_length();
+        splits[i].push((s.clamp(0.0, 1.0), p));
+        splits[j].push((t.clamp(0.0, 1.0), p));
+    }
+}
+
+/// If `p` is within `eps` of the segment from `a` to `b`, but not within
+/// `eps` of either of its end points, get its parameter along the segment.
+fn param_on<U>(
+    a: Point2D<f64, U>,
+    b: Point2D<f64, U>,
+    p: Point2D<f64, U>,
+    eps: f64,
+) -> Option<f64> {
+    let ab = b - a;
+    let t = (p - a).dot(ab) / ab.square_length();
+    if t <= 0.0 || t >= 1.0 {
+        return None;
+    }
+    let closest = a + ab * t;
+    if (p - closest).length() > eps || (p - a).length() <= eps || (p - b).length() <= eps {
+        return None;
+    }
+    Some(t)
+}
+
+/// A set of vertices, where points within `eps` of an existing vertex are
+/// merged into it.
+struct Vertices<U> {
+    eps: f64,
+    points: Vec<Point2D<f64, U>>,
+    grid: HashMap<(i64, i64), Vec<usize>>,
+}
+
+impl<U> Vertices<U> {
+    fn new(eps: f64) -> Vertices<U> {
+        Vertices {
+            eps,
+            points: vec![],
+            grid: HashMap::new(),
+        }
+    }
+
+    /// Get the id of the vertex at `p`, adding a new vertex if there isn't one
+    /// already.
+    fn id(&mut self, p: Point2D<f64, U>) -> usize {
+        let cell_size = self.eps * 2.0;
+        let cell = (
+            (p.x / cell_size).floor() as i64,
+            (p.y / cell_size).floor() as i64,
+        );
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                if let Some(ids) = self.grid.get(&(cell.0 + dx, cell.1 + dy)) {
+                    for &id in ids {
+                        if (self.points[id] - p).length() <= self.eps {
+                            return id;
+                        }
+                    }
+                }
+            }
+        }
+        let id = self.points.len();
+        self.points.push(p);
+        self.grid.entry(cell).or_default().push(id);
+        id
+    }
+}
+
+/// Join the given directed edges into rings of vertex ids.
+///
+/// At each vertex, the ring turns as far left as it can, and rings that visit
+/// the same vertex more than once are split into separate rings there. This
+/// way, no ring touches itself.
+fn trace_rings<U>(vertices: &[Point2D<f64, U>], edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    let angle = |from: usize, to: usize| {
+        let d: Vector2D<f64, U> = vertices[to] - vertices[from];
+        d.y.atan2(d.x)
+    };
+
+    let mut outgoing: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (i, &(from, _)) in edges.iter().enumerate() {
+        outgoing.entry(from).or_default().push(i);
+    }
+
+    let mut used = vec![false; edges.len()];
+    let mut rings = vec![];
+    for start in 0..edges.len() {
+        if used[start] {
+            continue;
+        }
+        let mut ring = vec![];
+        let mut e = start;
+        while !used[e] {
+            used[e] = true;
+            let (from, to) = edges[e];
+            ring.push(from);
+
+            // Sweep clockwise from the direction back along `e` to the first
+            // unused outgoing edge.
+            let back = angle(to, from);
+            let next = outgoing[&to]
+                .iter()
+                .filter(|&&o| !used[o] || o == start)
+                .map(|&o| {
+                    let mut sweep = (back - angle(to, edges[o].1)).rem_euclid(2.0 * PI);
+                    if sweep == 0.0 {
+                        sweep = 2.0 * PI;
+                    }
+                    (o, sweep)
+                })
+                .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+            match next {
+                Some((o, _)) => e = o,
+                None => break,
+            }
+        }
+        rings.extend(split_at_repeats(ring));
+    }
+    rings
+}
+
+/// Split a ring that visits some vertices more than once into rings that each
+/// visit their vertices once.
+fn split_at_repeats(ring: Vec<usize>) -> Vec<Vec<usize>> {
+    let mut rings = vec![];
+    let mut stack: Vec<usize> = vec![];
+    let mut positions = HashMap::new();
+    for id in ring {
+        match positions.get(&id) {
+            Some(&k) => {
+                let mut split = vec![id];
+                for other in stack.drain(k + 1..) {
+                    positions.remove(&other);
+                    split.push(other);
+                }
+                rings.push(split);
+            }
+            None => {
+                positions.insert(id, stack.len());
+                stack.push(id);
+            }
+        }
+    }
+    rings.push(stack);
+    rings
+}
+
+/// Remove vertices that are (within `eps` of being) on the straight line
+/// between their neighbors.
+fn remove_collinear<U>(ring: Vec<Point2D<f64, U>>, eps: f64) -> Vec<Point2D<f64, U>> {
+    let straight = |a: Point2D<f64, U>, b: Point2D<f64, U>, c: Point2D<f64, U>| {
+        let (ab, bc) = (b - a, c - b);
+        let ac = (c - a).length();
+        ac > 0.0 && ab.cross(bc).abs() / ac <= eps && ab.dot(bc) > 0.0
+    };
+
+    let mut out: Vec<Point2D<f64, U>> = vec![];
+    for p in ring {
+        out.push(p);
+        while out.len() >= 3 && straight(out[out.len() - 3], out[out.len() - 2], p) {
+            let n = out.len();
+            out.remove(n - 2);
+        }
+    }
+    loop {
+        let n = out.len();
+        if n < 3 {
+            break;
+        }
+        if straight(out[n - 2], out[n - 1], out[0]) {
+            out.pop();
+        } else if straight(out[n - 1], out[0], out[1]) {
+            out.remove(0);
+        } else {
+            break;
+        }
+    }
+    out
+}
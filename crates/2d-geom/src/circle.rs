@@ -0,0 +1,211 @@
+use crate::{Ellipse, Scalar};
+use euclid::{point2, vec2, Angle, Point2D, Transform2D, Vector2D};
+use fart_aabb::{Aabb, ToAabb};
+use fart_utils::NoMorePartial;
+use rand::rngs::SmallRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::f64::consts::PI;
+
+/// A circle.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Circle<T, U> {
+    /// The center of the circle.
+    pub center: Point2D<T, U>,
+    /// The radius of the circle.
+    pub radius: T,
+}
+
+impl<T, U> Circle<T, U> {
+    /// Create a new circle with the given center and radius.
+    #[inline]
+    pub fn new(center: Point2D<T, U>, radius: T) -> Circle<T, U> {
+        Circle { center, radius }
+    }
+}
+
+impl<T, U> Circle<T, U>
+where
+    T: Scalar,
+{
+    /// Cast from number representation `T` to number representation `V`.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::Circle;
+    ///
+    /// let c = Circle::<f64, UnknownUnit>::new(point2(1.5, 2.5), 3.0);
+    /// let c = c.cast::<i64>();
+    /// assert_eq!(c.center, point2(1, 2));
+    /// assert_eq!(c.radius, 3);
+    /// ```
+    #[inline]
+    pub fn cast<V>(&self) -> Circle<V, U>
+    where
+        V: Scalar,
+    {
+        Circle::new(self.center.cast(), V::from(self.radius).unwrap())
+    }
+}
+
+impl<U> Circle<f64, U> {
+    /// Find the smallest circle that contains all of the given points, or
+    /// `None` if there aren't any.
+    ///
+    /// This is Welzl's algorithm, which takes `O(n)` expected time.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::Circle;
+    ///
+    /// let c = Circle::<f64, UnknownUnit>::enclosing(&[
+    ///     point2(0.0, 0.0),
+    ///     point2(1.0, 1.0),
+    ///     point2(6.0, 0.0),
+    ///     point2(3.0, -3.0),
+    /// ]).unwrap();
+    /// assert_eq!(c.center, point2(3.0, 0.0));
+    /// assert_eq!(c.radius, 3.0);
+    ///
+    /// assert!(Circle::<f64, UnknownUnit>::enclosing(&[]).is_none());
+    /// ```
+    pub fn enclosing(points: &[Point2D<f64, U>]) -> Option<Circle<f64, U>> {
+        // Welzl's algorithm is only fast when the points come in a random
+        // order, but there's no need for the order to differ between calls.
+        let mut points = points.to_vec();
+        points.shuffle(&mut SmallRng::seed_from_u64(0));
+
+        // Each point outside of the circle so far must be on the boundary of
+        // the circle for it and the points before it, and so must any point
+        // outside of that circle, up to three points.
+        let mut circle = Circle::new(*points.first()?, 0.0);
+        for i in 1..points.len() {
+            if circle.encloses(points[i]) {
+                continue;
+            }
+            circle = Circle::new(points[i], 0.0);
+            for j in 0..i {
+                if circle.encloses(points[j]) {
+                    continue;
+                }
+                circle = Circle::through_two(points[i], points[j]);
+                for k in 0..j {
+                    if !circle.encloses(points[k]) {
+                        circle = Circle::through_three(points[i], points[j], points[k]);
+                    }
+                }
+            }
+        }
+        Some(circle)
+    }
+
+    /// Is `p` inside this circle, give or take rounding?
+    fn encloses(&self, p: Point2D<f64, U>) -> bool {
+        (p - self.center).length() <= self.radius * (1.0 + 1e-12)
+    }
+
+    /// The smallest circle through `a` and `b`.
+    fn through_two(a: Point2D<f64, U>, b: Point2D<f64, U>) -> Circle<f64, U> {
+        let center = a.lerp(b, 0.5);
+        Circle::new(center, (a - center).length().max((b - center).length()))
+    }
+
+    /// The circle through `a`, `b`, and `c`, or the smallest circle containing
+    /// them if they are collinear.
+    fn through_three(a: Point2D<f64, U>, b: Point2D<f64, U>, c: Point2D<f64, U>) -> Circle<f64, U> {
+        let (ab, ac) = (b - a, c - a);
+        let d = 2.0 * ab.cross(ac);
+        if d == 0.0 {
+            let (l, m) = [(a, b), (a, c), (b, c)]
+                .iter()
+                .cloned()
+                .max_by_key(|&(l, m)| NoMorePartial((m - l).square_length()))
+                .unwrap();
+            return Circle::through_two(l, m);
+        }
+        let (ab2, ac2) = (ab.square_length(), ac.square_length());
+        let center = a + vec2(ac.y * ab2 - ab.y * ac2, ab.x * ac2 - ac.x * ab2) / d;
+        let radius = [a, b, c]
+            .iter()
+            .map(|&p| (p - center).length())
+            .fold(0.0, f64::max);
+        Circle::new(center, radius)
+    }
+
+    /// Get the point on this circle at the given angle from its center,
+    /// measured from the x axis towards the y axis.
+    ///
+    /// ```
+    /// use euclid::{point2, Angle, UnknownUnit};
+    /// use fart_2d_geom::Circle;
+    ///
+    /// let c = Circle::<f64, UnknownUnit>::new(point2(1.0, 1.0), 2.0);
+    /// assert_eq!(c.point_at(Angle::zero()), point2(3.0, 1.0));
+    ///
+    /// let p = c.point_at(Angle::pi());
+    /// assert!((p - point2(-1.0, 1.0)).length() < 1e-12);
+    /// ```
+    pub fn point_at(&self, angle: Angle<f64>) -> Point2D<f64, U> {
+        let (sin, cos) = angle.radians.sin_cos();
+        self.center + vec2(cos, sin) * self.radius
+    }
+
+    /// Get the unit tangent vector of this circle at the given angle, pointing
+    /// in the direction of increasing angle.
+    ///
+    /// ```
+    /// use euclid::{point2, vec2, Angle, UnknownUnit};
+    /// use fart_2d_geom::Circle;
+    ///
+    /// let c = Circle::<f64, UnknownUnit>::new(point2(1.0, 1.0), 2.0);
+    /// assert_eq!(c.tangent_at(Angle::zero()), vec2(0.0, 1.0));
+    /// ```
+    pub fn tangent_at(&self, angle: Angle<f64>) -> Vector2D<f64, U> {
+        let (sin, cos) = angle.radians.sin_cos();
+        vec2(-sin, cos)
+    }
+
+    /// Get this circle's circumference.
+    #[inline]
+    pub fn length(&self) -> f64 {
+        2.0 * PI * self.radius.abs()
+    }
+
+    /// Transform this circle with the given linear transformation and return
+    /// the new, transformed shape.
+    ///
+    /// Transformations that scale by different amounts in different directions
+    /// don't preserve circles, so this returns an ellipse.
+    ///
+    /// ```
+    /// use euclid::{point2, vec2, Transform2D, UnknownUnit};
+    /// use fart_2d_geom::Circle;
+    ///
+    /// let c = Circle::<f64, UnknownUnit>::new(point2(1.0, 1.0), 2.0);
+    /// let e = c.transform(&Transform2D::<_, _, UnknownUnit>::create_scale(3.0, 1.0));
+    ///
+    /// assert_eq!(e.center, point2(3.0, 1.0));
+    /// assert!((e.radii - vec2(6.0, 2.0)).length() < 1e-12);
+    /// ```
+    pub fn transform<V>(&self, transformation: &Transform2D<f64, U, V>) -> Ellipse<f64, V> {
+        Ellipse::new(self.center, vec2(self.radius, self.radius), Angle::zero())
+            .transform(transformation)
+    }
+}
+
+impl<T, U> ToAabb<T, U> for Circle<T, U>
+where
+    T: Scalar,
+{
+    fn to_aabb(&self) -> Aabb<T, U> {
+        let r = if self.radius < T::zero() {
+            T::zero() - self.radius
+        } else {
+            self.radius
+        };
+        Aabb::new(
+            point2(self.center.x - r, self.center.y - r),
+            point2(self.center.x + r, self.center.y + r),
+        )
+    }
+}
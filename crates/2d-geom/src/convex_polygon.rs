@@ -1,8 +1,11 @@
-use crate::{line, sort_around, Polygon};
-use euclid::{point2, Point2D};
+use crate::predicates::{cross, crossing_point};
+use crate::{line, orientation, Circle, Line, Polygon, RelativeDirection, Scalar};
+use euclid::{point2, vec2, Point2D, Vector2D};
 use fart_aabb::{Aabb, ToAabb};
 use fart_utils::NoMorePartial;
-use num_traits::{Bounded, Num, NumAssign, NumCast, Signed};
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::convert::TryFrom;
 use std::fmt;
 use std::ops::Deref;
 
@@ -50,9 +53,48 @@ impl<T, U> From<ConvexPolygon<T, U>> for Polygon<T, U> {
     }
 }
 
+impl<T, U> TryFrom<Polygon<T, U>> for ConvexPolygon<T, U>
+where
+    T: Scalar,
+{
+    type Error = Polygon<T, U>;
+
+    /// Convert a polygon into a convex polygon, if it is convex. If it isn't,
+    /// it is returned back as the error.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::{ConvexPolygon, Polygon};
+    /// use std::convert::TryFrom;
+    ///
+    /// let square: Polygon<i32, UnknownUnit> = Polygon::new(vec![
+    ///     point2(0, 0),
+    ///     point2(10, 0),
+    ///     point2(10, 10),
+    ///     point2(0, 10),
+    /// ]);
+    /// assert!(ConvexPolygon::try_from(square).is_ok());
+    ///
+    /// let arrow: Polygon<i32, UnknownUnit> = Polygon::new(vec![
+    ///     point2(0, 0),
+    ///     point2(10, 5),
+    ///     point2(0, 10),
+    ///     point2(3, 5),
+    /// ]);
+    /// assert!(ConvexPolygon::try_from(arrow).is_err());
+    /// ```
+    fn try_from(polygon: Polygon<T, U>) -> Result<ConvexPolygon<T, U>, Polygon<T, U>> {
+        if polygon.is_convex() {
+            Ok(ConvexPolygon { inner: polygon })
+        } else {
+            Err(polygon)
+        }
+    }
+}
+
 impl<T, U> ConvexPolygon<T, U>
 where
-    T: Copy + NumAssign + PartialOrd + Signed + Bounded + fmt::Debug,
+    T: Scalar,
 {
     /// Compute the convex hull of the given vertices.
     ///
@@ -96,57 +138,55 @@ where
     /// assert!(ConvexPolygon::<i32, UnknownUnit>::hull(vec![point2(0, 0), point2(1, 1), point2(2, 2)]).is_none());
     /// ```
     pub fn hull(mut vertices: Vec<Point2D<T, U>>) -> Option<ConvexPolygon<T, U>> {
-        let max = vertices
-            .iter()
-            .cloned()
-            .fold(point2(T::min_value(), T::min_value()), |a, b| {
-                if NoMorePartial((a.x, a.y)) > NoMorePartial((b.x, b.y)) {
-                    a
-                } else {
-                    b
-                }
-            });
-
-        sort_around(max, &mut vertices);
+        // Andrew's monotone chain: sort the vertices from left to right, and
+        // then sweep across them to build the lower half of the hull, and
+        // back again to build the upper half. Any vertex that doesn't make a
+        // strict left turn is popped off, which leaves collinear vertices out
+        // of the hull.
+        vertices.sort_by_key(|v| NoMorePartial((v.x, v.y)));
         vertices.dedup();
 
         if vertices.len() < 3 {
             return None;
         }
 
-        debug_assert_eq!(max, vertices.last().cloned().unwrap());
-        let mut stack = vec![max, vertices[0]];
-        let mut i = 1;
-        while i < vertices.len() - 1 {
-            assert!(stack.len() >= 2);
-            let v = vertices[i];
-            let l = line(stack[stack.len() - 2], stack[stack.len() - 1]);
-            if l.is_left(v) {
-                // This vertex is (likely) part of the hull! Add it to our
-                // stack.
-                stack.push(v);
-                i += 1;
-            } else if stack.len() == 2 {
-                // The first two vertices in the stack are always part of the
-                // hull, and therefore should never be reconsidered, so start
-                // considering the next `i`th vertex.
-                i += 1;
-            } else {
-                // The top of our stack is not part of the hull, so pop it from
-                // the stack to uncommit it.
-                stack.pop();
+        let mut hull: Vec<Point2D<T, U>> = Vec::with_capacity(vertices.len() + 1);
+        let sweep = |hull: &mut Vec<Point2D<T, U>>, v: Point2D<T, U>, min_len: usize| {
+            while hull.len() >= min_len
+                && !line(hull[hull.len() - 2], hull[hull.len() - 1]).is_left(v)
+            {
+                hull.pop();
             }
+            hull.push(v);
+        };
+        for &v in &vertices {
+            sweep(&mut hull, v, 2);
+        }
+        let lower_len = hull.len() + 1;
+        for &v in vertices.iter().rev().skip(1) {
+            sweep(&mut hull, v, lower_len);
         }
 
-        if stack.len() < 3 {
+        // The sweep back ends at the first vertex, where the hull started.
+        hull.pop();
+
+        if hull.len() < 3 {
             return None;
         }
 
         Some(ConvexPolygon {
-            inner: Polygon::new(stack),
+            inner: Polygon::new(hull),
         })
     }
 
+    /// Construct a convex polygon from vertices that are already known to be
+    /// convex and in counter-clockwise order, without computing their hull.
+    pub(crate) fn new_unchecked(vertices: Vec<Point2D<T, U>>) -> ConvexPolygon<T, U> {
+        ConvexPolygon {
+            inner: Polygon::new(vertices),
+        }
+    }
+
     /// Does this convex polygon properly contain the given point?
     ///
     /// # Example
@@ -194,11 +234,451 @@ where
     pub fn improperly_contains_point(&self, point: Point2D<T, U>) -> bool {
         self.edges().all(|e| e.is_left_or_collinear(point))
     }
+
+    /// Compute the Minkowski sum of this convex polygon and another: the
+    /// shape swept out by `other` as its origin is moved around every point of
+    /// this polygon.
+    ///
+    /// Takes `O(n + m)` time, by merging the two polygons' edges in order of
+    /// their angles.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::ConvexPolygon;
+    ///
+    /// let square = ConvexPolygon::<i32, UnknownUnit>::hull(vec![
+    ///     point2(0, 0),
+    ///     point2(2, 0),
+    ///     point2(2, 2),
+    ///     point2(0, 2),
+    /// ]).unwrap();
+    /// let triangle = ConvexPolygon::<i32, UnknownUnit>::hull(vec![
+    ///     point2(0, 0),
+    ///     point2(1, 0),
+    ///     point2(0, 1),
+    /// ]).unwrap();
+    ///
+    /// let sum = square.minkowski_sum(&triangle);
+    /// assert_eq!(sum.vertices(), &[
+    ///     point2(0, 0),
+    ///     point2(3, 0),
+    ///     point2(3, 2),
+    ///     point2(2, 3),
+    ///     point2(0, 3),
+    /// ]);
+    /// ```
+    pub fn minkowski_sum(&self, other: &ConvexPolygon<T, U>) -> ConvexPolygon<T, U> {
+        let p = from_lowest(self.vertices());
+        let q = from_lowest(other.vertices());
+        let (n, m) = (p.len(), q.len());
+
+        let mut sum = Vec::with_capacity(n + m);
+        let (mut i, mut j) = (0, 0);
+        while i < n || j < m {
+            sum.push(p[i % n] + q[j % m].to_vector());
+            let order = if i == n {
+                Ordering::Greater
+            } else if j == m {
+                Ordering::Less
+            } else {
+                angle_order(p[i], p[(i + 1) % n], q[j], q[(j + 1) % m])
+            };
+            match order {
+                Ordering::Less => i += 1,
+                Ordering::Greater => j += 1,
+                Ordering::Equal => {
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+
+        convex_ring(sum).expect("the Minkowski sum of two polygons has area")
+    }
+
+    /// Compute the Minkowski difference of this convex polygon and another:
+    /// the Minkowski sum of this polygon and `other` reflected through the
+    /// origin.
+    ///
+    /// The difference contains every translation of `other` that makes it
+    /// overlap this polygon, so it contains the origin exactly when the two
+    /// polygons overlap.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::ConvexPolygon;
+    ///
+    /// let square = |x, y| ConvexPolygon::<i32, UnknownUnit>::hull(vec![
+    ///     point2(x, y),
+    ///     point2(x + 2, y),
+    ///     point2(x + 2, y + 2),
+    ///     point2(x, y + 2),
+    /// ]).unwrap();
+    ///
+    /// let difference = square(0, 0).minkowski_difference(&square(1, 1));
+    /// assert!(difference.contains_point(point2(0, 0)));
+    ///
+    /// let difference = square(0, 0).minkowski_difference(&square(5, 1));
+    /// assert!(!difference.contains_point(point2(0, 0)));
+    /// ```
+    pub fn minkowski_difference(&self, other: &ConvexPolygon<T, U>) -> ConvexPolygon<T, U> {
+        let reflected = other
+            .vertices()
+            .iter()
+            .map(|&v| point2(-v.x, -v.y))
+            .collect();
+        self.minkowski_sum(&ConvexPolygon::new_unchecked(reflected))
+    }
+
+    /// Find the two vertices of this convex polygon that are farthest apart.
+    ///
+    /// Takes `O(n)` time, by rotating a pair of parallel calipers around the
+    /// polygon.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::{line, ConvexPolygon};
+    ///
+    /// let p = ConvexPolygon::<i32, UnknownUnit>::hull(vec![
+    ///     point2(0, 0),
+    ///     point2(10, 1),
+    ///     point2(11, 4),
+    ///     point2(2, 3),
+    /// ]).unwrap();
+    ///
+    /// let d = p.diameter();
+    /// assert!(d == line(point2(0, 0), point2(11, 4)) || d == line(point2(11, 4), point2(0, 0)));
+    /// ```
+    pub fn diameter(&self) -> Line<T, U> {
+        let v = self.vertices();
+        let n = v.len();
+        let next = |k: usize| (k + 1) % n;
+        let distance = |l: &Line<T, U>| (l.b - l.a).square_length();
+
+        // Each edge's calipers touch the vertex farthest from it, which only
+        // ever moves forwards as the edges do.
+        let mut far = (0..n)
+            .max_by(|&j, &k| match cross(v[1], v[0], v[j], v[k]) {
+                RelativeDirection::Left => Ordering::Greater,
+                RelativeDirection::Collinear => Ordering::Equal,
+                RelativeDirection::Right => Ordering::Less,
+            })
+            .unwrap();
+        let mut best = line(v[0], v[1]);
+        for i in 0..n {
+            let (a, b) = (v[i], v[next(i)]);
+            while cross(b, a, v[next(far)], v[far]) == RelativeDirection::Left {
+                far = next(far);
+            }
+
+            // An edge parallel to this one is entirely as far from it, and
+            // either of its ends could be farthest from this edge's ends.
+            let mut candidates = vec![v[far]];
+            if cross(b, a, v[next(far)], v[far]) == RelativeDirection::Collinear {
+                candidates.push(v[next(far)]);
+            }
+            for &c in &candidates {
+                for &end in &[a, b] {
+                    let l = line(end, c);
+                    if distance(&l) > distance(&best) {
+                        best = l;
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    /// Do this convex polygon and another overlap?
+    ///
+    /// Polygons that only touch along their boundaries do not overlap. This is
+    /// a separating axis test, which looks for one of the polygons' edges with
+    /// the other polygon entirely on its outside. It is exact, even for
+    /// floating point coordinates.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::ConvexPolygon;
+    ///
+    /// let square = |x, y| ConvexPolygon::<i32, UnknownUnit>::hull(vec![
+    ///     point2(x, y),
+    ///     point2(x + 2, y),
+    ///     point2(x + 2, y + 2),
+    ///     point2(x, y + 2),
+    /// ]).unwrap();
+    ///
+    /// assert!(square(0, 0).overlaps(&square(1, 1)));
+    /// assert!(!square(0, 0).overlaps(&square(5, 1)));
+    ///
+    /// // Touching is not overlapping.
+    /// assert!(!square(0, 0).overlaps(&square(2, 1)));
+    /// ```
+    pub fn overlaps(&self, other: &ConvexPolygon<T, U>) -> bool {
+        let separates = |p: &ConvexPolygon<T, U>, q: &ConvexPolygon<T, U>| {
+            p.edges()
+                .any(|e| q.vertices().iter().all(|&v| e.is_right_or_collinear(v)))
+        };
+        !separates(self, other) && !separates(other, self)
+    }
+}
+
+impl<U> ConvexPolygon<f64, U> {
+    /// Compute the intersection of this convex polygon and another, or `None`
+    /// if they don't overlap.
+    ///
+    /// Unlike `Polygon::intersection`, this takes `O(n + m)` time, by
+    /// intersecting the half-planes inside the two polygons' edges in order
+    /// of their angles.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::ConvexPolygon;
+    ///
+    /// let square = |x, y| ConvexPolygon::<f64, UnknownUnit>::hull(vec![
+    ///     point2(x, y),
+    ///     point2(x + 2.0, y),
+    ///     point2(x + 2.0, y + 2.0),
+    ///     point2(x, y + 2.0),
+    /// ]).unwrap();
+    ///
+    /// let overlap = square(0.0, 0.0).convex_intersection(&square(1.0, 1.0)).unwrap();
+    /// assert_eq!(overlap.area(), 1.0);
+    ///
+    /// assert!(square(0.0, 0.0).convex_intersection(&square(5.0, 1.0)).is_none());
+    /// ```
+    pub fn convex_intersection(
+        &self,
+        other: &ConvexPolygon<f64, U>,
+    ) -> Option<ConvexPolygon<f64, U>> {
+        let p = from_lowest(self.vertices());
+        let q = from_lowest(other.vertices());
+        let (n, m) = (p.len(), q.len());
+        let mut edges = Vec::with_capacity(n + m);
+        let (mut i, mut j) = (0, 0);
+        while i < n || j < m {
+            if j == m
+                || (i < n
+                    && angle_order(p[i], p[(i + 1) % n], q[j], q[(j + 1) % m]) != Ordering::Greater)
+            {
+                edges.push(line(p[i], p[(i + 1) % n]));
+                i += 1;
+            } else {
+                edges.push(line(q[j], q[(j + 1) % m]));
+                j += 1;
+            }
+        }
+
+        let outside = |e: &Line<f64, U>, v: Point2D<f64, U>| {
+            orientation(e.a, e.b, v) == RelativeDirection::Right
+        };
+        let meet = |e: &Line<f64, U>, f: &Line<f64, U>| crossing_point(e.a, e.b, f.a, f.b);
+
+        // Keep the edges that bound the intersection in a deque, popping
+        // edges off either end when a new edge cuts off their corners.
+        let mut kept: VecDeque<Line<f64, U>> = VecDeque::with_capacity(edges.len());
+        for e in edges {
+            while kept.len() >= 2 && outside(&e, meet(&kept[kept.len() - 2], &kept[kept.len() - 1]))
+            {
+                kept.pop_back();
+            }
+            while kept.len() >= 2 && outside(&e, meet(&kept[0], &kept[1])) {
+                kept.pop_front();
+            }
+            if let Some(last) = kept.back() {
+                if cross(last.b, last.a, e.b, e.a) == RelativeDirection::Collinear {
+                    if angle_order(last.a, last.b, e.a, e.b) != Ordering::Equal {
+                        // Facing edges with nothing between them leave no
+                        // area.
+                        return None;
+                    }
+                    // Of two parallel edges, only the inner one matters.
+                    if orientation(last.a, last.b, e.a) != RelativeDirection::Left {
+                        continue;
+                    }
+                    kept.pop_back();
+                }
+            }
+            kept.push_back(e);
+        }
+        while kept.len() >= 3
+            && outside(&kept[0], meet(&kept[kept.len() - 2], &kept[kept.len() - 1]))
+        {
+            kept.pop_back();
+        }
+        while kept.len() >= 3 && outside(&kept[kept.len() - 1], meet(&kept[0], &kept[1])) {
+            kept.pop_front();
+        }
+        if kept.len() < 3 {
+            return None;
+        }
+
+        let vertices = (0..kept.len())
+            .map(|i| meet(&kept[i], &kept[(i + 1) % kept.len()]))
+            .collect();
+        convex_ring(vertices)
+    }
+
+    /// Find the rectangle with the smallest area that contains this convex
+    /// polygon.
+    ///
+    /// One of the rectangle's sides always lies along one of the polygon's
+    /// edges, so this takes `O(n)` time, by rotating calipers around the
+    /// polygon to find the polygon's extent along and away from each edge.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::ConvexPolygon;
+    /// use fart_aabb::ToAabb;
+    ///
+    /// // A diamond, which fits in a smaller rectangle when it is turned.
+    /// let diamond = ConvexPolygon::<f64, UnknownUnit>::hull(vec![
+    ///     point2(0.0, 0.0),
+    ///     point2(3.0, 3.0),
+    ///     point2(2.0, 4.0),
+    ///     point2(-1.0, 1.0),
+    /// ]).unwrap();
+    /// assert_eq!(diamond.to_aabb().width() * diamond.to_aabb().height(), 16.0);
+    ///
+    /// let rectangle = diamond.minimum_bounding_rectangle();
+    /// assert_eq!(rectangle.len(), 4);
+    /// assert!((rectangle.area() - 6.0).abs() < 1e-9);
+    /// ```
+    pub fn minimum_bounding_rectangle(&self) -> ConvexPolygon<f64, U> {
+        let v = self.vertices();
+        let n = v.len();
+        let next = |k: usize| (k + 1) % n;
+
+        // Move a caliper forwards around the polygon for as long as that
+        // doesn't make `f` any smaller.
+        let advance = |k: &mut usize, f: &dyn Fn(usize) -> f64| {
+            for _ in 0..n {
+                if f(next(*k)) < f(*k) {
+                    break;
+                }
+                *k = next(*k);
+            }
+        };
+
+        let (mut ahead, mut behind, mut across) = (0, 0, 0);
+        let mut best: Option<(f64, [Point2D<f64, U>; 4])> = None;
+        for i in 0..n {
+            let a = v[i];
+            let u = (v[next(i)] - a).normalize();
+            let normal = vec2(-u.y, u.x);
+            let along = |k: usize| (v[k] - a).dot(u);
+            let away = |k: usize| (v[k] - a).dot(normal);
+            if i == 0 {
+                let extreme =
+                    |f: &dyn Fn(usize) -> f64| (0..n).max_by_key(|&k| NoMorePartial(f(k))).unwrap();
+                ahead = extreme(&along);
+                behind = extreme(&|k| -along(k));
+                across = extreme(&away);
+            } else {
+                advance(&mut ahead, &along);
+                advance(&mut behind, &|k| -along(k));
+                advance(&mut across, &away);
+            }
+
+            let (start, end, height) = (along(behind), along(ahead), away(across));
+            let area = (end - start) * height;
+            if best.is_none_or(|(best_area, _)| area < best_area) {
+                let (start, end) = (a + u * start, a + u * end);
+                best = Some((
+                    area,
+                    [start, end, end + normal * height, start + normal * height],
+                ));
+            }
+        }
+
+        let (_, corners) = best.unwrap();
+        ConvexPolygon::new_unchecked(corners.to_vec())
+    }
+
+    /// Find the smallest circle that contains this convex polygon.
+    ///
+    /// See `Circle::enclosing`.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::ConvexPolygon;
+    ///
+    /// let p = ConvexPolygon::<f64, UnknownUnit>::hull(vec![
+    ///     point2(0.0, 0.0),
+    ///     point2(4.0, 0.0),
+    ///     point2(2.0, 1.0),
+    /// ]).unwrap();
+    ///
+    /// let circle = p.enclosing_circle();
+    /// assert_eq!(circle.center, point2(2.0, 0.0));
+    /// assert_eq!(circle.radius, 2.0);
+    /// ```
+    pub fn enclosing_circle(&self) -> Circle<f64, U> {
+        Circle::enclosing(self.vertices()).unwrap()
+    }
+
+    /// Find the shortest translation that would move this convex polygon out
+    /// of another, or `None` if they don't overlap.
+    ///
+    /// Like `overlaps`, this is a separating axis test: the translation is
+    /// perpendicular to whichever of the polygons' edges they overlap the
+    /// least across. Moving this polygon by it leaves the polygons touching.
+    ///
+    /// ```
+    /// use euclid::{point2, vec2, UnknownUnit};
+    /// use fart_2d_geom::ConvexPolygon;
+    ///
+    /// let square = |x, y| ConvexPolygon::<f64, UnknownUnit>::hull(vec![
+    ///     point2(x, y),
+    ///     point2(x + 2.0, y),
+    ///     point2(x + 2.0, y + 2.0),
+    ///     point2(x, y + 2.0),
+    /// ]).unwrap();
+    ///
+    /// let push = square(0.0, 0.0).minimum_translation(&square(1.5, 0.5)).unwrap();
+    /// assert_eq!(push, vec2(-0.5, 0.0));
+    ///
+    /// assert!(square(0.0, 0.0).minimum_translation(&square(2.0, 0.5)).is_none());
+    /// ```
+    pub fn minimum_translation(&self, other: &ConvexPolygon<f64, U>) -> Option<Vector2D<f64, U>> {
+        let project = |p: &ConvexPolygon<f64, U>, axis: Vector2D<f64, U>| {
+            p.vertices()
+                .iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+                    let x = v.to_vector().dot(axis);
+                    (lo.min(x), hi.max(x))
+                })
+        };
+
+        let mut best: Option<Vector2D<f64, U>> = None;
+        for e in self.edges().chain(other.edges()) {
+            // Project onto the edge's normal without normalizing it, so that
+            // polygons that only touch don't overlap by a rounding error.
+            let axis = vec2(e.a.y - e.b.y, e.b.x - e.a.x);
+            let (self_lo, self_hi) = project(self, axis);
+            let (other_lo, other_hi) = project(other, axis);
+
+            // How far this polygon would have to move forwards or backwards
+            // along the axis to stop overlapping, in units of its length.
+            let (forwards, backwards) = (other_hi - self_lo, self_hi - other_lo);
+            if forwards <= 0.0 || backwards <= 0.0 {
+                return None;
+            }
+            let translation = if forwards < backwards {
+                axis * (forwards / axis.square_length())
+            } else {
+                axis * (-backwards / axis.square_length())
+            };
+            if best.is_none_or(|b| translation.square_length() < b.square_length()) {
+                best = Some(translation);
+            }
+        }
+        best
+    }
 }
 
 impl<T, U> ConvexPolygon<T, U>
 where
-    T: Copy + NumCast,
+    T: Scalar,
 {
     /// Cast from number representation `T` to number representation `V`.
     ///
@@ -218,7 +698,7 @@ where
     #[inline]
     pub fn cast<V>(&self) -> ConvexPolygon<V, U>
     where
-        V: NumCast + Copy,
+        V: Scalar,
     {
         ConvexPolygon {
             inner: self.inner.cast(),
@@ -228,7 +708,7 @@ where
 
 impl<T, U> ConvexPolygon<T, U>
 where
-    T: Copy + Num + PartialOrd
+    T: Scalar,
 {
     /// Transform this convex polygon with the given linear transformation and
     /// return the new, transformed convex polygon.
@@ -281,7 +761,7 @@ where
 
 impl<T, U> ToAabb<T, U> for ConvexPolygon<T, U>
 where
-    T: Copy + Num + PartialOrd,
+    T: Scalar,
 {
     fn to_aabb(&self) -> Aabb<T, U> {
         self.inner.to_aabb()
@@ -290,7 +770,7 @@ where
 
 impl<T, U> From<Aabb<T, U>> for ConvexPolygon<T, U>
 where
-    T: Copy + NumAssign + PartialOrd + Signed + fmt::Debug,
+    T: Scalar,
 {
     fn from(aabb: Aabb<T, U>) -> Self {
         ConvexPolygon {
@@ -298,3 +778,80 @@ where
         }
     }
 }
+
+/// Rotate a convex polygon's vertices to start from its lowest vertex (the
+/// leftmost, if there are several), so that its edges are in order of their
+/// angles.
+fn from_lowest<T, U>(vertices: &[Point2D<T, U>]) -> Vec<Point2D<T, U>>
+where
+    T: Copy + PartialOrd,
+{
+    let lowest = (0..vertices.len())
+        .min_by_key(|&i| NoMorePartial((vertices[i].y, vertices[i].x)))
+        .unwrap();
+    vertices[lowest..]
+        .iter()
+        .chain(&vertices[..lowest])
+        .cloned()
+        .collect()
+}
+
+/// Compare the angles of the directions from `a` to `b` and from `c` to `d`,
+/// counter-clockwise from the positive x axis, exactly.
+fn angle_order<T, U>(
+    a: Point2D<T, U>,
+    b: Point2D<T, U>,
+    c: Point2D<T, U>,
+    d: Point2D<T, U>,
+) -> Ordering
+where
+    T: Scalar,
+{
+    // Directions in the upper half of the plane come before those in the
+    // lower half, and within each half, the cross product orders them.
+    let upper =
+        |from: Point2D<T, U>, to: Point2D<T, U>| to.y > from.y || (to.y == from.y && to.x > from.x);
+    match (upper(a, b), upper(c, d)) {
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        _ => match cross(b, a, d, c) {
+            RelativeDirection::Left => Ordering::Less,
+            RelativeDirection::Collinear => Ordering::Equal,
+            RelativeDirection::Right => Ordering::Greater,
+        },
+    }
+}
+
+/// Make a convex polygon from vertices that are already in counter-clockwise
+/// order, and would be convex if not for rounding, by dropping any vertices
+/// that don't make a strict left turn. Returns `None` if that leaves no area.
+fn convex_ring<T, U>(mut vertices: Vec<Point2D<T, U>>) -> Option<ConvexPolygon<T, U>>
+where
+    T: Scalar,
+{
+    vertices.dedup();
+    while vertices.len() > 1 && vertices.first() == vertices.last() {
+        vertices.pop();
+    }
+    if vertices.len() < 3 {
+        return None;
+    }
+
+    // The lowest vertex is always a corner of the polygon, so start from it
+    // and come back around to it at the end.
+    let vertices = from_lowest(&vertices);
+    let mut ring: Vec<Point2D<T, U>> = Vec::with_capacity(vertices.len() + 1);
+    for &v in vertices.iter().chain(Some(&vertices[0])) {
+        while ring.len() >= 2 && !line(ring[ring.len() - 2], ring[ring.len() - 1]).is_left(v) {
+            ring.pop();
+        }
+        ring.push(v);
+    }
+    ring.pop();
+
+    if ring.len() < 3 {
+        None
+    } else {
+        Some(ConvexPolygon::new_unchecked(ring))
+    }
+}
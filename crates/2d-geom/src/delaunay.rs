@@ -0,0 +1,942 @@
+use crate::{orientation, ConvexPolygon};
+use euclid::{point2, Point2D, Vector2D};
+use fart_aabb::Aabb;
+use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+/// A missing half-edge: the other side of an edge on the convex hull.
+const NONE: usize = usize::MAX;
+
+/// The Delaunay triangulation of a set of points.
+///
+/// No point is inside the circumcircle of any triangle, which maximizes the
+/// triangles' smallest angles, and so avoids long, skinny triangles as much as
+/// possible. The triangles cover the convex hull of the points.
+///
+/// A triangulation can be constrained to contain particular edges, in which
+/// case it is only as Delaunay as those edges allow. See
+/// `DelaunayTriangulation::with_constraints`.
+///
+/// Points are referred to by their index in the original set of points.
+/// Duplicate points are only triangulated once: the first of them is used, and
+/// the rest are left out.
+///
+/// * `U` is the unit. `ScreenSpace` or `WorldSpace` etc.
+#[derive(Clone)]
+pub struct DelaunayTriangulation<U> {
+    points: Vec<Point2D<f64, U>>,
+    /// For each point, the point that is triangulated in its place: itself,
+    /// unless it is a duplicate.
+    representatives: Vec<usize>,
+    /// Three point indices for each triangle, in counter-clockwise order. The
+    /// half-edge `e` goes from `triangles[e]` to `triangles[next(e)]`.
+    triangles: Vec<usize>,
+    /// The opposite of each half-edge, or `NONE` on the convex hull.
+    halfedges: Vec<usize>,
+    /// Whether each half-edge is constrained, and so can't be flipped.
+    constrained: Vec<bool>,
+    /// A half-edge leaving each point, or `NONE` if it isn't triangulated.
+    outgoing: Vec<usize>,
+}
+
+impl<U> fmt::Debug for DelaunayTriangulation<U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DelaunayTriangulation")
+            .field(
+                "points",
+                &self.points.iter().map(|p| (p.x, p.y)).collect::<Vec<_>>(),
+            )
+            .field("triangles", &self.triangles().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<U> DelaunayTriangulation<U> {
+    /// Compute the Delaunay triangulation of the given points.
+    ///
+    /// This is an *O(n log n)* algorithm.
+    ///
+    /// If all of the points are collinear, then there are no triangles.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::DelaunayTriangulation;
+    ///
+    /// let triangulation = DelaunayTriangulation::<UnknownUnit>::new(vec![
+    ///     point2(0.0, 0.0),
+    ///     point2(4.0, 0.0),
+    ///     point2(4.0, 4.0),
+    ///     point2(0.0, 4.0),
+    ///     point2(2.0, 1.0),
+    /// ]);
+    ///
+    /// // Every triangle uses the point in the middle.
+    /// assert_eq!(triangulation.triangles().count(), 4);
+    /// assert!(triangulation.triangles().all(|t| t.contains(&4)));
+    /// ```
+    pub fn new(points: Vec<Point2D<f64, U>>) -> DelaunayTriangulation<U> {
+        let mut triangulation = DelaunayTriangulation {
+            representatives: (0..points.len()).collect(),
+            points,
+            triangles: vec![],
+            halfedges: vec![],
+            constrained: vec![],
+            outgoing: vec![],
+        };
+        triangulation.triangulate();
+        triangulation.constrained = vec![false; triangulation.triangles.len()];
+        triangulation.outgoing = vec![NONE; triangulation.points.len()];
+        for (e, &p) in triangulation.triangles.iter().enumerate() {
+            triangulation.outgoing[p] = e;
+        }
+        triangulation
+    }
+
+    /// Compute the constrained Delaunay triangulation of the given points,
+    /// which contains every one of the given edges between them.
+    ///
+    /// The triangulation is as close to Delaunay as possible: every triangle's
+    /// circumcircle contains no points that are visible from the triangle
+    /// without crossing a constrained edge. Constrained edges that pass
+    /// through other points are split at them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the constrained edges cross each other, or refer to
+    /// points that don't exist.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::DelaunayTriangulation;
+    ///
+    /// // A long, thin kite.
+    /// let points = vec![
+    ///     point2(0.0, 0.0),
+    ///     point2(4.0, -1.0),
+    ///     point2(8.0, 0.0),
+    ///     point2(4.0, 1.0),
+    /// ];
+    ///
+    /// // Its Delaunay triangulation has the short diagonal, because each
+    /// // triangle on either side of it has a circumcircle that excludes the
+    /// // opposite point...
+    /// let unconstrained = DelaunayTriangulation::<UnknownUnit>::new(points.clone());
+    /// assert!(unconstrained.edges().any(|e| e == (1, 3) || e == (3, 1)));
+    /// assert!(!unconstrained.edges().any(|e| e == (0, 2) || e == (2, 0)));
+    ///
+    /// // ...unless it is constrained to have the long one.
+    /// let constrained = DelaunayTriangulation::<UnknownUnit>::with_constraints(points, &[(0, 2)]);
+    /// assert!(constrained.edges().any(|e| e == (0, 2) || e == (2, 0)));
+    /// assert!(!constrained.edges().any(|e| e == (1, 3) || e == (3, 1)));
+    /// ```
+    pub fn with_constraints(
+        points: Vec<Point2D<f64, U>>,
+        constraints: &[(usize, usize)],
+    ) -> DelaunayTriangulation<U> {
+        let mut triangulation = DelaunayTriangulation::new(points);
+        for &(a, b) in constraints {
+            let (a, b) = (
+                triangulation.representatives[a],
+                triangulation.representatives[b],
+            );
+            triangulation.constrain(a, b);
+        }
+        triangulation
+    }
+
+    /// Get the triangulated points.
+    #[inline]
+    pub fn points(&self) -> &[Point2D<f64, U>] {
+        &self.points
+    }
+
+    /// Iterate over the triangles, as the indices of their three points, in
+    /// counter-clockwise order.
+    pub fn triangles<'a>(&'a self) -> impl 'a + Iterator<Item = [usize; 3]> {
+        self.triangles.chunks(3).map(|t| [t[0], t[1], t[2]])
+    }
+
+    /// Iterate over the triangles' edges, as the indices of their two points.
+    ///
+    /// Edges shared by two triangles are only yielded once.
+    pub fn edges<'a>(&'a self) -> impl 'a + Iterator<Item = (usize, usize)> {
+        (0..self.triangles.len())
+            .filter(move |&e| self.halfedges[e] == NONE || e < self.halfedges[e])
+            .map(move |e| (self.triangles[e], self.triangles[next(e)]))
+    }
+
+    /// Is the edge between the given points constrained?
+    pub fn is_constrained(&self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.representatives[a], self.representatives[b]);
+        self.find_edge(a, b)
+            .or_else(|| self.find_edge(b, a))
+            .is_some_and(|e| self.constrained[e])
+    }
+
+    /// Build the triangulation by sweeping a convex hull outwards from a seed
+    /// triangle, adding points in order of their distance from it, and
+    /// flipping edges to keep it Delaunay as it goes.
+    fn triangulate(&mut self) {
+        let skipped = match Builder::seed(&self.points) {
+            None => duplicates(&self.points),
+            Some(mut builder) => {
+                let skipped = builder.sweep();
+                self.triangles = builder.triangles;
+                self.halfedges = builder.halfedges;
+                skipped
+            }
+        };
+
+        // Point each of the points that were left out at the point that was
+        // triangulated in its place: either the point it duplicates, or else
+        // the closest point that was triangulated.
+        let mut triangulated = vec![false; self.points.len()];
+        for &p in &self.triangles {
+            triangulated[p] = true;
+        }
+        for (i, j) in skipped {
+            self.representatives[i] = if j != NONE {
+                j
+            } else {
+                let p = self.points[i];
+                (0..self.points.len())
+                    .filter(|&j| triangulated[j])
+                    .min_by(|&a, &b| {
+                        let da = (self.points[a] - p).square_length();
+                        let db = (self.points[b] - p).square_length();
+                        da.partial_cmp(&db).unwrap()
+                    })
+                    .unwrap_or(i)
+            };
+        }
+        for i in 0..self.points.len() {
+            let mut r = self.representatives[i];
+            while self.representatives[r] != r {
+                r = self.representatives[r];
+            }
+            self.representatives[i] = r;
+        }
+    }
+
+    /// Iterate over the half-edges leaving the point `p`.
+    fn outgoing(&self, p: usize) -> Vec<usize> {
+        let start = self.outgoing[p];
+        if start == NONE {
+            return vec![];
+        }
+
+        // Rotate counter-clockwise around `p`, and then, if that runs into
+        // the convex hull, clockwise from where we started.
+        let mut edges = vec![start];
+        let mut e = start;
+        loop {
+            e = self.halfedges[prev(e)];
+            if e == start {
+                return edges;
+            }
+            if e == NONE {
+                break;
+            }
+            edges.push(e);
+        }
+        let mut e = start;
+        while self.halfedges[e] != NONE {
+            e = next(self.halfedges[e]);
+            edges.push(e);
+        }
+        edges
+    }
+
+    /// Find the half-edge from `a` to `b`, if there is one.
+    fn find_edge(&self, a: usize, b: usize) -> Option<usize> {
+        self.outgoing(a)
+            .into_iter()
+            .find(|&e| self.triangles[next(e)] == b)
+    }
+
+    /// Add a constrained edge between `a` and `b`, by flipping away every
+    /// edge that crosses it, and then flipping the new edges back to
+    /// Delaunay where the constraint allows.
+    fn constrain(&mut self, a: usize, b: usize) {
+        if a == b || self.outgoing[a] == NONE {
+            return;
+        }
+        let (pa, pb) = (self.points[a], self.points[b]);
+
+        // Find the triangle around `a` that the constraint leaves through. The
+        // constraint might already be an edge, or run through another point on
+        // its way.
+        let mut crossed = None;
+        for e in self.outgoing(a) {
+            let w = self.triangles[next(e)];
+            let x = self.triangles[prev(e)];
+            for &(v, edge) in &[(w, e), (x, prev(e))] {
+                if v == b {
+                    self.set_constrained(edge);
+                    return;
+                }
+                let pv = self.points[v];
+                if orient(pa, pb, pv) == 0.0 && (pv - pa).dot(pb - pa) > 0.0 {
+                    self.constrain(a, v);
+                    self.constrain(v, b);
+                    return;
+                }
+            }
+            if orient(pa, self.points[w], pb) > 0.0 && orient(pa, pb, self.points[x]) > 0.0 {
+                crossed = Some(next(e));
+            }
+        }
+        let mut c = crossed.expect("constraint must leave its start point through a triangle");
+
+        // Walk along the constraint, collecting the edges it crosses.
+        let mut crossing = VecDeque::new();
+        loop {
+            assert!(!self.constrained[c], "constrained edges must not cross");
+            crossing.push_back((self.triangles[c], self.triangles[next(c)]));
+            let t = self.halfedges[c];
+            let y = self.triangles[prev(t)];
+            if y == b {
+                break;
+            }
+            let o = orient(pa, pb, self.points[y]);
+            if o == 0.0 {
+                self.constrain(a, y);
+                self.constrain(y, b);
+                return;
+            }
+            c = if o < 0.0 { prev(t) } else { next(t) };
+        }
+
+        // Flip the crossing edges until none of them cross anymore. An edge
+        // can only be flipped once its two triangles form a convex
+        // quadrilateral, so put it back in the queue until then.
+        let mut new_edges = vec![];
+        while let Some((u, v)) = crossing.pop_front() {
+            let e = self.find_edge(u, v).unwrap();
+            let r = self.triangles[prev(e)];
+            let s = self.triangles[prev(self.halfedges[e])];
+            let (pr, ps) = (self.points[r], self.points[s]);
+            if orient(pr, ps, self.points[u]) * orient(pr, ps, self.points[v]) >= 0.0 {
+                crossing.push_back((u, v));
+                continue;
+            }
+            self.flip(e);
+            if r != a && r != b && s != a && s != b && crosses(pa, pb, pr, ps) {
+                crossing.push_back((r, s));
+            } else {
+                new_edges.push((r, s));
+            }
+        }
+
+        self.set_constrained(self.find_edge(a, b).unwrap());
+
+        // Restore the Delaunay property around the new edges.
+        let mut flipped = true;
+        while flipped {
+            flipped = false;
+            for edge in &mut new_edges {
+                let (u, v) = *edge;
+                let e = match self.find_edge(u, v) {
+                    Some(e) if !self.constrained[e] && self.halfedges[e] != NONE => e,
+                    _ => continue,
+                };
+                let r = self.triangles[prev(e)];
+                let s = self.triangles[prev(self.halfedges[e])];
+                let p = &self.points;
+                if in_circle(p[u], p[v], p[r], p[s]) {
+                    self.flip(e);
+                    *edge = (r, s);
+                    flipped = true;
+                }
+            }
+        }
+    }
+
+    /// Mark the half-edge `e`, and its opposite, as constrained.
+    fn set_constrained(&mut self, e: usize) {
+        self.constrained[e] = true;
+        if self.halfedges[e] != NONE {
+            self.constrained[self.halfedges[e]] = true;
+        }
+    }
+
+    /// Flip the half-edge `e`, and its opposite, to be the other diagonal of
+    /// the quadrilateral made by their two triangles.
+    fn flip(&mut self, e: usize) {
+        // Before: `e` goes from `p` to `q` in the triangle `(p, q, r)`, and its
+        // opposite `t` is in the triangle `(q, p, s)`.
+        //
+        // After: `e` goes from `r` to `s` in the triangle `(r, s, q)`, and `t`
+        // goes from `s` to `r` in the triangle `(s, r, p)`.
+        let t = self.halfedges[e];
+        let (e1, e2, t1, t2) = (next(e), prev(e), next(t), prev(t));
+        let (p, q, r, s) = (
+            self.triangles[e],
+            self.triangles[e1],
+            self.triangles[e2],
+            self.triangles[t2],
+        );
+        let twins = [
+            self.halfedges[e1],
+            self.halfedges[e2],
+            self.halfedges[t1],
+            self.halfedges[t2],
+        ];
+        let constrained = [
+            self.constrained[e1],
+            self.constrained[e2],
+            self.constrained[t1],
+            self.constrained[t2],
+        ];
+
+        self.triangles[e] = r;
+        self.triangles[e1] = s;
+        self.triangles[e2] = q;
+        self.triangles[t] = s;
+        self.triangles[t1] = r;
+        self.triangles[t2] = p;
+
+        for &(edge, i) in &[(e1, 3), (e2, 0), (t1, 1), (t2, 2)] {
+            self.halfedges[edge] = twins[i];
+            if twins[i] != NONE {
+                self.halfedges[twins[i]] = edge;
+            }
+            self.constrained[edge] = constrained[i];
+        }
+
+        self.outgoing[p] = t2;
+        self.outgoing[q] = e2;
+        self.outgoing[r] = e;
+        self.outgoing[s] = t;
+    }
+}
+
+/// The state of the triangulation while it is being swept out.
+struct Builder<'a, U> {
+    points: &'a [Point2D<f64, U>],
+    seed: [usize; 3],
+    triangles: Vec<usize>,
+    halfedges: Vec<usize>,
+    hull: Hull<U>,
+}
+
+/// The convex hull of the points triangulated so far, as a doubly linked list
+/// of points in counter-clockwise order, with a hash table from angles around
+/// the seed triangle's circumcenter to the points on the hull, for quickly
+/// finding where to add the next point.
+struct Hull<U> {
+    start: usize,
+    prev: Vec<usize>,
+    next: Vec<usize>,
+    /// The triangle half-edge leaving each point along the hull.
+    tri: Vec<usize>,
+    hash: Vec<usize>,
+    center: Point2D<f64, U>,
+}
+
+impl<U> Hull<U> {
+    fn new(n: usize, center: Point2D<f64, U>) -> Hull<U> {
+        Hull {
+            start: 0,
+            prev: vec![0; n],
+            next: vec![0; n],
+            tri: vec![0; n],
+            hash: vec![NONE; (n as f64).sqrt().ceil() as usize],
+            center,
+        }
+    }
+
+    /// Get the hash table key for the given point.
+    fn key(&self, p: Point2D<f64, U>) -> usize {
+        // A number between 0 and 1 that increases with the angle of `p`
+        // around the center, without any trigonometry.
+        let d = p - self.center;
+        let q = d.x / (d.x.abs() + d.y.abs());
+        let angle = (if d.y > 0.0 { 3.0 - q } else { 1.0 + q }) / 4.0;
+        let len = self.hash.len();
+        (((angle * len as f64).floor() as usize) % len + len) % len
+    }
+}
+
+impl<'a, U> Builder<'a, U> {
+    /// Start sweeping from a seed triangle: the point closest to the center
+    /// of the points, its closest neighbor, and the point making the smallest
+    /// circumcircle with them. Returns `None` if all of the points are
+    /// collinear.
+    fn seed(points: &'a [Point2D<f64, U>]) -> Option<Builder<'a, U>> {
+        let n = points.len();
+        if n < 3 {
+            return None;
+        }
+
+        let (min, max) = points
+            .iter()
+            .fold((points[0], points[0]), |(min, max), &p| {
+                (min.min(p), max.max(p))
+            });
+        let closest = |to: Point2D<f64, U>, skip: Option<usize>| {
+            (0..n)
+                .filter(|&i| Some(i) != skip && points[i] != to)
+                .min_by(|&a, &b| {
+                    let da = (points[a] - to).square_length();
+                    let db = (points[b] - to).square_length();
+                    da.partial_cmp(&db).unwrap()
+                })
+        };
+        let i0 = closest(min.lerp(max, 0.5), None).unwrap_or(0);
+        let i1 = closest(points[i0], Some(i0))?;
+        let mut i2 = NONE;
+        let mut min_radius = f64::INFINITY;
+        for i in 0..n {
+            if i == i0 || i == i1 {
+                continue;
+            }
+            let r = circumradius(points[i0], points[i1], points[i]);
+            if r < min_radius {
+                i2 = i;
+                min_radius = r;
+            }
+        }
+        if i2 == NONE {
+            return None;
+        }
+        let (i1, i2) = if orient(points[i0], points[i1], points[i2]) > 0.0 {
+            (i1, i2)
+        } else {
+            (i2, i1)
+        };
+
+        let mut hull = Hull::new(n, circumcenter(points[i0], points[i1], points[i2]));
+        hull.start = i0;
+        for &(a, b) in &[(i0, i1), (i1, i2), (i2, i0)] {
+            hull.next[a] = b;
+            hull.prev[b] = a;
+        }
+        for (t, &i) in [i0, i1, i2].iter().enumerate() {
+            hull.tri[i] = t;
+            let key = hull.key(points[i]);
+            hull.hash[key] = i;
+        }
+
+        let mut builder = Builder {
+            points,
+            seed: [i0, i1, i2],
+            triangles: Vec::with_capacity(n * 6),
+            halfedges: Vec::with_capacity(n * 6),
+            hull,
+        };
+        builder.add_triangle(i0, i1, i2, NONE, NONE, NONE);
+        Some(builder)
+    }
+
+    /// Add every point, in order of their distance from the seed triangle.
+    ///
+    /// Returns the points that were left out, paired with the point they
+    /// duplicate, or `NONE` if they were too close to the hull to tell which
+    /// side of it they were on.
+    fn sweep(&mut self) -> Vec<(usize, usize)> {
+        let points = self.points;
+        let center = self.hull.center;
+        let dists: Vec<f64> = points
+            .iter()
+            .map(|p| (*p - center).square_length())
+            .collect();
+        let mut ids: Vec<usize> = (0..points.len()).collect();
+        // Break ties by position, so that duplicates end up next to each
+        // other.
+        ids.sort_by(|&a, &b| {
+            (dists[a], points[a].x, points[a].y)
+                .partial_cmp(&(dists[b], points[b].x, points[b].y))
+                .unwrap()
+        });
+
+        let mut skipped = vec![];
+        let mut previous = NONE;
+        for i in ids {
+            if previous != NONE && points[previous] == points[i] {
+                skipped.push((i, previous));
+                continue;
+            }
+            previous = i;
+            if !self.seed.contains(&i) && !self.add_point(i) {
+                skipped.push((i, NONE));
+            }
+        }
+        skipped
+    }
+
+    /// Add a triangle with the given points, and the given opposite
+    /// half-edges.
+    fn add_triangle(
+        &mut self,
+        i0: usize,
+        i1: usize,
+        i2: usize,
+        a: usize,
+        b: usize,
+        c: usize,
+    ) -> usize {
+        let t = self.triangles.len();
+        self.triangles.extend_from_slice(&[i0, i1, i2]);
+        self.halfedges.extend_from_slice(&[NONE, NONE, NONE]);
+        self.link(t, a);
+        self.link(t + 1, b);
+        self.link(t + 2, c);
+        t
+    }
+
+    fn link(&mut self, a: usize, b: usize) {
+        self.halfedges[a] = b;
+        if b != NONE {
+            self.halfedges[b] = a;
+        }
+    }
+
+    /// Add the point `i`, which is outside of the hull, connecting it to every
+    /// hull edge that it can see. Returns `false` if it can't see any.
+    fn add_point(&mut self, i: usize) -> bool {
+        let p = self.points[i];
+
+        // Find a hull edge that is visible from the point, starting near its
+        // angle around the center.
+        let key = self.hull.key(p);
+        let len = self.hull.hash.len();
+        let mut start = 0;
+        for j in 0..len {
+            start = self.hull.hash[(key + j) % len];
+            if start != NONE && start != self.hull.next[start] {
+                break;
+            }
+        }
+        start = self.hull.prev[start];
+        let mut e = start;
+        loop {
+            let q = self.hull.next[e];
+            if orient(p, self.points[e], self.points[q]) < 0.0 {
+                break;
+            }
+            e = q;
+            if e == start {
+                return false;
+            }
+        }
+
+        let q = self.hull.next[e];
+        let t = self.add_triangle(e, i, q, NONE, NONE, self.hull.tri[e]);
+        self.hull.tri[i] = self.legalize(t + 2);
+        self.hull.tri[e] = t;
+
+        // Add triangles with the visible hull edges after it...
+        let mut n = q;
+        loop {
+            let q = self.hull.next[n];
+            if orient(p, self.points[n], self.points[q]) >= 0.0 {
+                break;
+            }
+            let t = self.add_triangle(n, i, q, self.hull.tri[i], NONE, self.hull.tri[n]);
+            self.hull.tri[i] = self.legalize(t + 2);
+            self.hull.next[n] = n;
+            n = q;
+        }
+
+        // ...and before it.
+        if e == start {
+            loop {
+                let q = self.hull.prev[e];
+                if orient(p, self.points[q], self.points[e]) >= 0.0 {
+                    break;
+                }
+                let t = self.add_triangle(q, i, e, NONE, self.hull.tri[e], self.hull.tri[q]);
+                self.legalize(t + 2);
+                self.hull.tri[q] = t;
+                self.hull.next[e] = e;
+                e = q;
+            }
+        }
+
+        self.hull.start = e;
+        self.hull.prev[i] = e;
+        self.hull.next[e] = i;
+        self.hull.prev[n] = i;
+        self.hull.next[i] = n;
+        let key = self.hull.key(p);
+        self.hull.hash[key] = i;
+        let key = self.hull.key(self.points[e]);
+        self.hull.hash[key] = e;
+        true
+    }
+
+    /// Flip the half-edge `a`, and then the edges around it, recursively,
+    /// until they are all Delaunay. Returns the half-edge that ends up where
+    /// `a`'s triangle's last edge was.
+    fn legalize(&mut self, mut a: usize) -> usize {
+        let mut stack = vec![];
+        let mut ar;
+        loop {
+            let b = self.halfedges[a];
+            let a0 = a - a % 3;
+            ar = a0 + (a + 2) % 3;
+
+            if b == NONE {
+                match stack.pop() {
+                    Some(e) => {
+                        a = e;
+                        continue;
+                    }
+                    None => break,
+                }
+            }
+
+            let b0 = b - b % 3;
+            let al = a0 + (a + 1) % 3;
+            let bl = b0 + (b + 2) % 3;
+            let p0 = self.triangles[ar];
+            let pr = self.triangles[a];
+            let pl = self.triangles[al];
+            let p1 = self.triangles[bl];
+
+            let points = self.points;
+            if in_circle(points[p0], points[pr], points[pl], points[p1]) {
+                self.triangles[a] = p1;
+                self.triangles[b] = p0;
+
+                // If the flipped edge was on the hull, fix up the hull's
+                // reference to it.
+                let hbl = self.halfedges[bl];
+                if hbl == NONE {
+                    let mut e = self.hull.start;
+                    loop {
+                        if self.hull.tri[e] == bl {
+                            self.hull.tri[e] = a;
+                            break;
+                        }
+                        e = self.hull.prev[e];
+                        if e == self.hull.start {
+                            break;
+                        }
+                    }
+                }
+                self.link(a, hbl);
+                let har = self.halfedges[ar];
+                self.link(b, har);
+                self.link(ar, bl);
+
+                stack.push(b0 + (b + 1) % 3);
+            } else {
+                match stack.pop() {
+                    Some(e) => a = e,
+                    None => break,
+                }
+            }
+        }
+        ar
+    }
+}
+
+/// Compute the Voronoi diagram of the given points, clipped to the given
+/// bounds.
+///
+/// Each point's Voronoi cell is the region that is closer to it than to any
+/// other point. The cells are returned in the same order as the points. A
+/// cell that falls entirely outside of the bounds is `None`, and duplicate
+/// points have identical cells.
+///
+/// The Voronoi diagram is the dual of the Delaunay triangulation, which is
+/// used to compute it in *O(n log n)* time.
+///
+/// ```
+/// use euclid::{point2, UnknownUnit};
+/// use fart_2d_geom::voronoi;
+/// use fart_aabb::Aabb;
+///
+/// let cells = voronoi::<UnknownUnit>(
+///     &[point2(1.0, 1.0), point2(3.0, 1.0), point2(1.0, 3.0), point2(3.0, 3.0)],
+///     &Aabb::new(point2(0.0, 0.0), point2(4.0, 4.0)),
+/// );
+///
+/// // A grid of points has a grid of cells.
+/// assert_eq!(cells.len(), 4);
+/// for cell in &cells {
+///     let cell = cell.as_ref().unwrap();
+///     assert_eq!(cell.len(), 4);
+///     assert_eq!(cell.area(), 4.0);
+/// }
+/// ```
+pub fn voronoi<U>(
+    points: &[Point2D<f64, U>],
+    bounds: &Aabb<f64, U>,
+) -> Vec<Option<ConvexPolygon<f64, U>>> {
+    let triangulation = DelaunayTriangulation::new(points.to_vec());
+
+    // Each cell is bounded by the bisectors between its point and its
+    // neighbors in the triangulation.
+    let mut neighbors = vec![vec![]; points.len()];
+    if triangulation.triangles.is_empty() {
+        // Collinear points' neighbors are the points next to them along their
+        // line.
+        let mut line: Vec<usize> = (0..points.len())
+            .filter(|&i| triangulation.representatives[i] == i)
+            .collect();
+        line.sort_by(|&a, &b| {
+            let (a, b) = (points[a], points[b]);
+            (a.x, a.y).partial_cmp(&(b.x, b.y)).unwrap()
+        });
+        for pair in line.windows(2) {
+            neighbors[pair[0]].push(pair[1]);
+            neighbors[pair[1]].push(pair[0]);
+        }
+    } else {
+        for (a, b) in triangulation.edges() {
+            neighbors[a].push(b);
+            neighbors[b].push(a);
+        }
+    }
+
+    let (min, max) = (bounds.min(), bounds.max());
+    let corners = vec![min, point2(max.x, min.y), max, point2(min.x, max.y)];
+    let mut cells: Vec<Option<ConvexPolygon<f64, U>>> = (0..points.len()).map(|_| None).collect();
+    for i in 0..points.len() {
+        if triangulation.representatives[i] != i {
+            continue;
+        }
+        let mut cell = corners.clone();
+        for &j in &neighbors[i] {
+            let middle = points[i].lerp(points[j], 0.5);
+            cell = clip_to_half_plane(&cell, middle, points[j] - points[i]);
+        }
+        cells[i] = convex_polygon(cell);
+    }
+    for i in 0..points.len() {
+        let r = triangulation.representatives[i];
+        if r != i {
+            cells[i] = cells[r]
+                .as_ref()
+                .map(|cell| ConvexPolygon::new_unchecked(cell.vertices().to_vec()));
+        }
+    }
+    cells
+}
+
+/// Turn the vertices of a clipped convex polygon into a `ConvexPolygon`,
+/// removing repeated and collinear vertices, or `None` if nothing is left.
+fn convex_polygon<U>(mut vertices: Vec<Point2D<f64, U>>) -> Option<ConvexPolygon<f64, U>> {
+    while vertices.len() >= 3 {
+        let n = vertices.len();
+        let degenerate = (0..n).find(|&i| {
+            orient(
+                vertices[(i + n - 1) % n],
+                vertices[i],
+                vertices[(i + 1) % n],
+            ) <= 0.0
+        });
+        match degenerate {
+            Some(i) => {
+                vertices.remove(i);
+            }
+            None => break,
+        }
+    }
+    if vertices.len() < 3 {
+        None
+    } else {
+        Some(ConvexPolygon::new_unchecked(vertices))
+    }
+}
+
+/// Find the points that duplicate earlier points, paired with the first point
+/// they duplicate.
+fn duplicates<U>(points: &[Point2D<f64, U>]) -> Vec<(usize, usize)> {
+    let mut first = HashMap::new();
+    let mut duplicates = vec![];
+    for (i, p) in points.iter().enumerate() {
+        // Adding zero turns negative zero into positive zero.
+        let key = ((p.x + 0.0).to_bits(), (p.y + 0.0).to_bits());
+        let j = *first.entry(key).or_insert(i);
+        if j != i {
+            duplicates.push((i, j));
+        }
+    }
+    duplicates
+}
+
+/// Clip a convex polygon to the half plane of points `p` where
+/// `(p - point) · normal <= 0`.
+fn clip_to_half_plane<U>(
+    polygon: &[Point2D<f64, U>],
+    point: Point2D<f64, U>,
+    normal: Vector2D<f64, U>,
+) -> Vec<Point2D<f64, U>> {
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    for (i, &a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        let da = (a - point).dot(normal);
+        let db = (b - point).dot(normal);
+        if da <= 0.0 {
+            clipped.push(a);
+        }
+        if (da < 0.0 && db > 0.0) || (da > 0.0 && db < 0.0) {
+            clipped.push(a.lerp(b, da / (da - db)));
+        }
+    }
+    clipped
+}
+
+#[inline]
+fn next(e: usize) -> usize {
+    if e % 3 == 2 {
+        e - 2
+    } else {
+        e + 1
+    }
+}
+
+#[inline]
+fn prev(e: usize) -> usize {
+    match e % 3 {
+        0 => e + 2,
+        _ => e - 1,
+    }
+}
+
+/// The orientation of the triangle `(a, b, c)`: positive if it is
+/// counter-clockwise, negative if it is clockwise, and zero if it is
+/// degenerate.
+#[inline]
+fn orient<U>(a: Point2D<f64, U>, b: Point2D<f64, U>, c: Point2D<f64, U>) -> f64 {
+    f64::from(orientation(a, b, c) as i8)
+}
+
+/// Do the segments `a`-`b` and `c`-`d` properly cross each other?
+fn crosses<U>(
+    a: Point2D<f64, U>,
+    b: Point2D<f64, U>,
+    c: Point2D<f64, U>,
+    d: Point2D<f64, U>,
+) -> bool {
+    orient(a, b, c) * orient(a, b, d) < 0.0 && orient(c, d, a) * orient(c, d, b) < 0.0
+}
+
+/// Is `p` inside the circumcircle of the counter-clockwise triangle
+/// `(a, b, c)`?
+fn in_circle<U>(
+    a: Point2D<f64, U>,
+    b: Point2D<f64, U>,
+    c: Point2D<f64, U>,
+    p: Point2D<f64, U>,
+) -> bool {
+    crate::in_circle(a, b, c, p) == Ordering::Less
+}
+
+/// The squared radius of the circumcircle of the triangle `(a, b, c)`, or
+/// infinity or NaN if it is degenerate.
+fn circumradius<U>(a: Point2D<f64, U>, b: Point2D<f64, U>, c: Point2D<f64, U>) -> f64 {
+    (circumcenter(a, b, c) - a).square_length()
+}
+
+fn circumcenter<U>(a: Point2D<f64, U>, b: Point2D<f64, U>, c: Point2D<f64, U>) -> Point2D<f64, U> {
+    let (d, e) = (b - a, c - a);
+    let (bl, cl) = (d.square_length(), e.square_length());
+    let k = 0.5 / d.cross(e);
+    a + Vector2D::new((e.y * bl - d.y * cl) * k, (d.x * cl - e.x * bl) * k)
+}
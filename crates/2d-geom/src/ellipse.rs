@@ -0,0 +1,234 @@
+use crate::{integrate, Scalar};
+use euclid::{vec2, Angle, Point2D, Transform2D, Vector2D};
+use fart_aabb::{Aabb, ToAabb};
+use std::f64::consts::PI;
+
+/// An ellipse.
+///
+/// Points on the ellipse are parameterized by an angle `θ`, and are at
+/// `center + rotate(rotation, (radii.x * cos θ, radii.y * sin θ))`. For a
+/// circle, `θ` is the actual angle of the point around the center, but for
+/// other ellipses it is not.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Ellipse<T, U> {
+    /// The center of the ellipse.
+    pub center: Point2D<T, U>,
+    /// The radii along the ellipse's own x and y axes.
+    pub radii: Vector2D<T, U>,
+    /// The rotation of the ellipse's x axis from the x axis.
+    pub rotation: Angle<T>,
+}
+
+impl<T, U> Ellipse<T, U> {
+    /// Create a new ellipse with the given center, radii, and rotation.
+    #[inline]
+    pub fn new(center: Point2D<T, U>, radii: Vector2D<T, U>, rotation: Angle<T>) -> Ellipse<T, U> {
+        Ellipse {
+            center,
+            radii,
+            rotation,
+        }
+    }
+}
+
+impl<T, U> Ellipse<T, U>
+where
+    T: Scalar,
+{
+    /// Cast from number representation `T` to number representation `V`.
+    ///
+    /// ```
+    /// use euclid::{point2, vec2, Angle, UnknownUnit};
+    /// use fart_2d_geom::Ellipse;
+    ///
+    /// let e = Ellipse::<f64, UnknownUnit>::new(point2(1.5, 2.5), vec2(3.0, 1.0), Angle::zero());
+    /// let e = e.cast::<i64>();
+    /// assert_eq!(e.center, point2(1, 2));
+    /// assert_eq!(e.radii, vec2(3, 1));
+    /// ```
+    #[inline]
+    pub fn cast<V>(&self) -> Ellipse<V, U>
+    where
+        V: Scalar,
+    {
+        Ellipse::new(
+            self.center.cast(),
+            self.radii.cast(),
+            Angle::radians(V::from(self.rotation.radians).unwrap()),
+        )
+    }
+}
+
+impl<U> Ellipse<f64, U> {
+    /// Get the point on this ellipse at the given parametric angle.
+    ///
+    /// ```
+    /// use euclid::{point2, vec2, Angle, UnknownUnit};
+    /// use fart_2d_geom::Ellipse;
+    ///
+    /// let e = Ellipse::<f64, UnknownUnit>::new(point2(0.0, 0.0), vec2(2.0, 1.0), Angle::zero());
+    /// assert_eq!(e.point_at(Angle::zero()), point2(2.0, 0.0));
+    ///
+    /// let top = e.point_at(Angle::frac_pi_2());
+    /// assert!((top - point2(0.0, 1.0)).length() < 1e-12);
+    /// ```
+    pub fn point_at(&self, angle: Angle<f64>) -> Point2D<f64, U> {
+        let (sin, cos) = angle.radians.sin_cos();
+        self.center + self.axes(vec2(cos, sin))
+    }
+
+    /// Get the unit tangent vector of this ellipse at the given parametric
+    /// angle, pointing in the direction of increasing angle.
+    ///
+    /// ```
+    /// use euclid::{point2, vec2, Angle, UnknownUnit};
+    /// use fart_2d_geom::Ellipse;
+    ///
+    /// let e = Ellipse::<f64, UnknownUnit>::new(point2(0.0, 0.0), vec2(2.0, 1.0), Angle::zero());
+    /// let tangent = e.tangent_at(Angle::zero());
+    /// assert!((tangent - vec2(0.0, 1.0)).length() < 1e-12);
+    /// ```
+    pub fn tangent_at(&self, angle: Angle<f64>) -> Vector2D<f64, U> {
+        let (sin, cos) = angle.radians.sin_cos();
+        self.axes(vec2(-sin, cos)).normalize()
+    }
+
+    /// Get the length of this ellipse's perimeter.
+    ///
+    /// ```
+    /// use euclid::{point2, vec2, Angle, UnknownUnit};
+    /// use fart_2d_geom::Ellipse;
+    ///
+    /// let e = Ellipse::<f64, UnknownUnit>::new(point2(0.0, 0.0), vec2(2.0, 1.0), Angle::zero());
+    /// assert!((e.length() - 9.688_448_220_547_675).abs() < 1e-9);
+    /// ```
+    pub fn length(&self) -> f64 {
+        arc_length(self.radii, 0.0, 2.0 * PI)
+    }
+
+    /// Transform this ellipse with the given linear transformation and return
+    /// the new, transformed ellipse.
+    ///
+    /// The transformed ellipse's parametric angles don't necessarily match up
+    /// with this one's.
+    ///
+    /// ```
+    /// use euclid::{point2, vec2, Angle, Transform2D, UnknownUnit};
+    /// use fart_2d_geom::Ellipse;
+    ///
+    /// let e = Ellipse::<f64, UnknownUnit>::new(point2(0.0, 0.0), vec2(2.0, 1.0), Angle::zero());
+    /// let scaled = e.transform(&Transform2D::<_, _, UnknownUnit>::create_scale(1.0, 4.0));
+    ///
+    /// // The ellipse is now taller than it is wide.
+    /// assert!((scaled.radii - vec2(4.0, 2.0)).length() < 1e-12);
+    /// assert!((scaled.rotation.radians.abs() - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+    /// ```
+    pub fn transform<V>(&self, transformation: &Transform2D<f64, U, V>) -> Ellipse<f64, V> {
+        let axes = transform_axes(self.radii, self.rotation, transformation);
+        Ellipse::new(
+            transformation.transform_point(self.center),
+            axes.radii,
+            axes.rotation,
+        )
+    }
+
+    /// Transform this ellipse in place with the given linear transformation.
+    pub fn transform_in_place(&mut self, transformation: &Transform2D<f64, U, U>) {
+        *self = self.transform(transformation);
+    }
+
+    /// Map a vector in the ellipse's unit circle space onto its axes.
+    fn axes(&self, v: Vector2D<f64, U>) -> Vector2D<f64, U> {
+        rotate(self.rotation, vec2(self.radii.x * v.x, self.radii.y * v.y))
+    }
+}
+
+impl<U> ToAabb<f64, U> for Ellipse<f64, U> {
+    fn to_aabb(&self) -> Aabb<f64, U> {
+        let (sin, cos) = self.rotation.radians.sin_cos();
+        let (rx, ry) = (self.radii.x, self.radii.y);
+        let half = vec2((rx * cos).hypot(ry * sin), (rx * sin).hypot(ry * cos));
+        Aabb::new(self.center - half, self.center + half)
+    }
+}
+
+/// Rotate `v` by the given angle.
+pub(crate) fn rotate<U>(angle: Angle<f64>, v: Vector2D<f64, U>) -> Vector2D<f64, U> {
+    let (sin, cos) = angle.radians.sin_cos();
+    vec2(cos * v.x - sin * v.y, sin * v.x + cos * v.y)
+}
+
+/// The length of an ellipse with the given radii, between the given parametric
+/// angles.
+pub(crate) fn arc_length<U>(radii: Vector2D<f64, U>, start: f64, end: f64) -> f64 {
+    let (rx, ry) = (radii.x.abs(), radii.y.abs());
+    if rx == ry {
+        return rx * (end - start).abs();
+    }
+    let speed = |theta: f64| {
+        let (sin, cos) = theta.sin_cos();
+        (rx * sin).hypot(ry * cos)
+    };
+    integrate(&speed, start.min(end), start.max(end))
+}
+
+/// An ellipse's axes after a linear transformation. See `transform_axes`.
+pub(crate) struct TransformedAxes<U> {
+    pub(crate) radii: Vector2D<f64, U>,
+    pub(crate) rotation: Angle<f64>,
+    /// How much parametric angles shift by.
+    offset: f64,
+    /// Whether the transformation is a reflection, which reverses the
+    /// direction of parametric angles.
+    reflected: bool,
+}
+
+impl<U> TransformedAxes<U> {
+    /// Map a parametric angle on the original ellipse to the same point's
+    /// parametric angle on the transformed ellipse.
+    pub(crate) fn angle(&self, angle: Angle<f64>) -> Angle<f64> {
+        if self.reflected {
+            Angle::radians(-(angle.radians + self.offset))
+        } else {
+            Angle::radians(angle.radians + self.offset)
+        }
+    }
+
+    /// Map a sweep along the original ellipse to the transformed ellipse.
+    pub(crate) fn sweep(&self, sweep: Angle<f64>) -> Angle<f64> {
+        if self.reflected {
+            -sweep
+        } else {
+            sweep
+        }
+    }
+}
+
+/// Transform the axes of an ellipse with the given radii and rotation.
+///
+/// Any linear transformation of an ellipse is another ellipse. Its axes are
+/// found with the singular value decomposition of the matrix mapping the unit
+/// circle onto the transformed ellipse.
+pub(crate) fn transform_axes<U, V>(
+    radii: Vector2D<f64, U>,
+    rotation: Angle<f64>,
+    transformation: &Transform2D<f64, U, V>,
+) -> TransformedAxes<V> {
+    let x = transformation.transform_vector(rotate(rotation, vec2(radii.x, 0.0)));
+    let y = transformation.transform_vector(rotate(rotation, vec2(0.0, radii.y)));
+
+    // The matrix `[x y]` is `rotate(φ) * scale(sx, sy) * rotate(θ)`, where
+    // `sy` is negative for reflections.
+    let (e, f) = ((x.x + y.y) / 2.0, (x.x - y.y) / 2.0);
+    let (g, h) = ((x.y + y.x) / 2.0, (x.y - y.x) / 2.0);
+    let (q, r) = (e.hypot(h), f.hypot(g));
+    let (a1, a2) = (g.atan2(f), h.atan2(e));
+    let (sx, sy) = (q + r, q - r);
+
+    TransformedAxes {
+        radii: vec2(sx, sy.abs()),
+        rotation: Angle::radians((a2 + a1) / 2.0),
+        offset: (a2 - a1) / 2.0,
+        reflected: sy < 0.0,
+    }
+}
@@ -0,0 +1,183 @@
+//! Splitting polygons into pieces, for recursive subdivision and fracturing.
+
+use crate::{line, BooleanOp, Line, Polygon};
+use euclid::vec2;
+use fart_aabb::ToAabb;
+use rand::distributions::{Distribution, Uniform};
+use rand::RngCore;
+
+impl<U> Polygon<f64, U> {
+    /// Split this polygon along the (infinite) line through `line.a` and
+    /// `line.b`.
+    ///
+    /// Returns the pieces to the line's left, followed by the pieces to its
+    /// right. A concave polygon may be split into more than two pieces, and a
+    /// polygon that the line doesn't cross is returned whole. The polygon
+    /// should be simple, and the pieces' vertices that are collinear with
+    /// their neighbors are removed, like the results of `boolean`.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::{line, Polygon};
+    ///
+    /// // A "U" shape, which a horizontal line across its arms cuts into a
+    /// // bottom and two separate arms.
+    /// let u = Polygon::<f64, UnknownUnit>::new(vec![
+    ///     point2(0.0, 0.0),
+    ///     point2(3.0, 0.0),
+    ///     point2(3.0, 3.0),
+    ///     point2(2.0, 3.0),
+    ///     point2(2.0, 1.0),
+    ///     point2(1.0, 1.0),
+    ///     point2(1.0, 3.0),
+    ///     point2(0.0, 3.0),
+    /// ]);
+    ///
+    /// let pieces = u.split_by_line(&line(point2(0.0, 2.0), point2(1.0, 2.0)));
+    /// let areas: Vec<f64> = pieces.iter().map(|p| p.area()).collect();
+    /// assert_eq!(areas, vec![1.0, 1.0, 5.0]);
+    /// ```
+    pub fn split_by_line(&self, line: &Line<f64, U>) -> Vec<Polygon<f64, U>> {
+        let d = line.b - line.a;
+        if d == vec2(0.0, 0.0) {
+            return vec![Polygon::new(self.vertices().to_vec())];
+        }
+
+        // Cover everything on the line's left, near this polygon, with a
+        // rectangle that has one side along the line, and clip to it.
+        let aabb = self.to_aabb();
+        let center = aabb.min().lerp(aabb.max(), 0.5);
+        let u = d.normalize();
+        let nearest = line.a + u * (center - line.a).dot(u);
+        let reach = 2.0 * ((aabb.max() - aabb.min()).length() + (center - nearest).length()) + 1.0;
+        let (start, end) = (nearest - u * reach, nearest + u * reach);
+        let normal = vec2(-u.y, u.x) * reach;
+        let left = Polygon::new(vec![start, end, end + normal, start + normal]);
+
+        [BooleanOp::Intersection, BooleanOp::Difference]
+            .iter()
+            .flat_map(|&op| self.boolean(&left, op))
+            .map(|p| Polygon::new(p.exterior().vertices().to_vec()))
+            .collect()
+    }
+
+    /// Split this polygon along a random chord: the line through two random
+    /// points on its boundary, on different edges.
+    ///
+    /// Like `split_by_line`, the whole line is used, and so concave polygons
+    /// may be split into more than two pieces.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::Polygon;
+    ///
+    /// let square = Polygon::<f64, UnknownUnit>::new(vec![
+    ///     point2(0.0, 0.0),
+    ///     point2(1.0, 0.0),
+    ///     point2(1.0, 1.0),
+    ///     point2(0.0, 1.0),
+    /// ]);
+    ///
+    /// let pieces = square.split_by_random_chord(&mut rand::thread_rng());
+    /// assert_eq!(pieces.len(), 2);
+    /// let area: f64 = pieces.iter().map(|p| p.area()).sum();
+    /// assert!((area - 1.0).abs() < 1e-9);
+    /// ```
+    pub fn split_by_random_chord(&self, rng: &mut dyn RngCore) -> Vec<Polygon<f64, U>> {
+        // Pick a random point along the boundary, by length, and then another
+        // along the rest of the boundary once its edge is left out.
+        let lengths: Vec<f64> = self.edges().map(|e| e.length()).collect();
+        let along = |rng: &mut dyn RngCore, skip: Option<usize>| {
+            let total: f64 = lengths
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| Some(i) != skip)
+                .map(|(_, l)| l)
+                .sum();
+            let mut s = Uniform::new(0.0, total).sample(rng);
+            let mut last = 0;
+            for (i, &length) in lengths.iter().enumerate() {
+                if Some(i) == skip {
+                    continue;
+                }
+                if s < length {
+                    return (i, self.edge(i).a.lerp(self.edge(i).b, s / length));
+                }
+                s -= length;
+                last = i;
+            }
+            // Rounding can leave `s` just past the end.
+            (last, self.edge(last).b)
+        };
+
+        let (i, a) = along(rng, None);
+        let (_, b) = along(rng, Some(i));
+        self.split_by_line(&line(a, b))
+    }
+
+    /// Split this polygon into pieces around its centroid, cutting from the
+    /// centroid to the midpoint of each of the given edges.
+    ///
+    /// Each piece spans from the midpoint of one of the given edges to the
+    /// next, counter-clockwise. Cutting to every edge splits the polygon into
+    /// one piece per vertex. Fewer than two edges leave it whole.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the edges is out of bounds, or if the cuts leave the
+    /// polygon, which can't happen if the polygon is star-shaped around its
+    /// centroid, such as any convex polygon.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::Polygon;
+    ///
+    /// let square = Polygon::<f64, UnknownUnit>::new(vec![
+    ///     point2(0.0, 0.0),
+    ///     point2(2.0, 0.0),
+    ///     point2(2.0, 2.0),
+    ///     point2(0.0, 2.0),
+    /// ]);
+    ///
+    /// // Quarters, one around each corner.
+    /// let quarters = square.split_from_centroid(&[0, 1, 2, 3]);
+    /// assert_eq!(quarters.len(), 4);
+    /// assert!(quarters.iter().all(|q| q.area() == 1.0));
+    ///
+    /// // Halves, cut across the middle.
+    /// let halves = square.split_from_centroid(&[0, 2]);
+    /// assert_eq!(halves.len(), 2);
+    /// assert!(halves.iter().all(|h| h.area() == 2.0));
+    /// ```
+    pub fn split_from_centroid(&self, edges: &[usize]) -> Vec<Polygon<f64, U>> {
+        let mut edges = edges.to_vec();
+        edges.sort();
+        edges.dedup();
+        assert!(edges.iter().all(|&e| e < self.len()));
+        if edges.len() < 2 {
+            return vec![Polygon::new(self.vertices().to_vec())];
+        }
+
+        let centroid = self.centroid();
+        let midpoint = |e: usize| {
+            let e = self.edge(e);
+            e.a.lerp(e.b, 0.5)
+        };
+        (0..edges.len())
+            .map(|k| {
+                let (i, j) = (edges[k], edges[(k + 1) % edges.len()]);
+                let mut piece = vec![centroid, midpoint(i)];
+                let mut v = self.next(i);
+                loop {
+                    piece.push(self.vertices()[v]);
+                    if v == j {
+                        break;
+                    }
+                    v = self.next(v);
+                }
+                piece.push(midpoint(j));
+                Polygon::new(piece)
+            })
+            .collect()
+    }
+}
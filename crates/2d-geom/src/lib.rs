@@ -1,25 +1,87 @@
 //! Two-dimensional geometry algorithms built on top of `euclid`.
+//!
+//! Geometry is generic over its coordinates, and anything that makes sense for
+//! both integer and floating point coordinates works with any `Scalar`, such as
+//! `i64` or `f64`. Operations whose results generally can't be represented with
+//! integers, such as offsetting, boolean operations, and curves, are only
+//! implemented for `f64`.
 
 #![deny(missing_docs, missing_debug_implementations)]
 
+mod arc;
+mod bezier;
+mod boolean;
+mod circle;
 mod convex_polygon;
+mod delaunay;
+mod ellipse;
+mod fracture;
 mod line;
+mod offset;
 mod polygon;
+mod polygon_with_holes;
+mod predicates;
+mod simplify;
+mod skeleton;
+mod smooth;
+mod sweep;
 
-pub use crate::{convex_polygon::*, line::*, polygon::*};
+pub use crate::{
+    arc::*, bezier::*, boolean::*, circle::*, convex_polygon::*, delaunay::*, ellipse::*, line::*,
+    offset::*, polygon::*, polygon_with_holes::*, predicates::*, simplify::*, skeleton::*,
+    smooth::*, sweep::*,
+};
 
 use euclid::{point2, Point2D};
-use num_traits::{Num, NumAssign, NumCast, Signed};
+pub use fart_utils::Scalar;
 use std::cmp::Ordering;
 
 #[inline]
 fn area2<T, U>(a: Point2D<T, U>, b: Point2D<T, U>, c: Point2D<T, U>) -> T
 where
-    T: Copy + Num,
+    T: Scalar,
 {
     (b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y)
 }
 
+/// Integrate `f` from `a` to `b` with adaptive Gauss-Legendre quadrature.
+///
+/// Used for the arc lengths of curves that don't have a closed form for them.
+fn integrate(f: &dyn Fn(f64) -> f64, a: f64, b: f64) -> f64 {
+    // Bound the recursion, in case `f` is not smooth.
+    const MAX_DEPTH: u32 = 16;
+
+    fn gauss_legendre(f: &dyn Fn(f64) -> f64, a: f64, b: f64) -> f64 {
+        // The five point rule's nodes on `[-1, 1]`, and their weights.
+        const NODES: [(f64, f64); 5] = [
+            (0.0, 0.568_888_888_888_888_9),
+            (-0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+            (0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+            (-0.906_179_845_938_664, 0.236_926_885_056_189_1),
+            (0.906_179_845_938_664, 0.236_926_885_056_189_1),
+        ];
+        let (middle, half) = ((a + b) / 2.0, (b - a) / 2.0);
+        half * NODES
+            .iter()
+            .map(|&(x, w)| w * f(middle + half * x))
+            .sum::<f64>()
+    }
+
+    fn adaptive(f: &dyn Fn(f64) -> f64, a: f64, b: f64, whole: f64, depth: u32) -> f64 {
+        let middle = (a + b) / 2.0;
+        let left = gauss_legendre(f, a, middle);
+        let right = gauss_legendre(f, middle, b);
+        let halves = left + right;
+        if depth == MAX_DEPTH || (halves - whole).abs() <= 1e-12 * halves.abs() {
+            halves
+        } else {
+            adaptive(f, a, middle, left, depth + 1) + adaptive(f, middle, b, right, depth + 1)
+        }
+    }
+
+    adaptive(f, a, b, gauss_legendre(f, a, b), 0)
+}
+
 /// Find the center (mean) of a set of points.
 ///
 /// # Panics
@@ -42,7 +104,7 @@ where
 /// ```
 pub fn center<T, U>(points: &[Point2D<T, U>]) -> Point2D<T, U>
 where
-    T: Copy + NumAssign + NumCast,
+    T: Scalar,
 {
     assert!(!points.is_empty());
 
@@ -50,21 +112,13 @@ where
     let mut sum_x = 0.0_f64;
     let mut sum_y = 0.0_f64;
     for p in points {
-        sum_x += p.x.to_f64().unwrap();
-        sum_y += p.y.to_f64().unwrap();
+        sum_x += p.x.to_float();
+        sum_y += p.y.to_float();
     }
     let n = points.len() as f64;
 
-    let cx = sum_x / n;
-    let cy = sum_y / n;
-
-    let (cx, cy) = if T::from(0.1) == T::from(0.9) {
-        (T::from(cx.round()).unwrap(), T::from(cy.round()).unwrap())
-    } else {
-        (T::from(cx).unwrap(), T::from(cy).unwrap())
-    };
-
-    point2(cx, cy)
+    // Integer centers are rounded to the nearest point.
+    point2(T::from_float(sum_x / n), T::from_float(sum_y / n))
 }
 
 /// Sort the given `points` around the given `pivot` point in counter-clockwise
@@ -93,7 +147,7 @@ where
 /// ```
 pub fn sort_around<T, U>(pivot: Point2D<T, U>, points: &mut [Point2D<T, U>])
 where
-    T: Copy + NumAssign + PartialOrd + Signed,
+    T: Scalar,
 {
     points.sort_by(|&a, &b| {
         let zero = T::zero();
@@ -113,16 +167,15 @@ where
                 b.y.partial_cmp(&a.y).unwrap()
             }
         } else {
-            let c = (a - pivot).cross(b - pivot);
-            if c < zero {
-                Ordering::Greater
-            } else if c > zero {
-                Ordering::Less
-            } else {
-                // Again, break ties with distance to the pivot.
-                let d1 = a.to_vector().cross(pivot.to_vector());
-                let d2 = b.to_vector().cross(pivot.to_vector());
-                d1.partial_cmp(&d2).unwrap()
+            match orientation(pivot, a, b) {
+                RelativeDirection::Right => Ordering::Greater,
+                RelativeDirection::Left => Ordering::Less,
+                RelativeDirection::Collinear => {
+                    // Again, break ties with distance to the pivot.
+                    let d1 = a.to_vector().cross(pivot.to_vector());
+                    let d2 = b.to_vector().cross(pivot.to_vector());
+                    d1.partial_cmp(&d2).unwrap()
+                }
             }
         }
     });
@@ -132,6 +185,10 @@ where
 
 /// Are the given vertices in counter-clockwise order?
 ///
+/// Vertices that enclose no area, such as collinear vertices, are considered
+/// counter-clockwise. This is exact, even for floating point coordinates. See
+/// `orientation`.
+///
 /// ```
 /// use euclid::{point2, UnknownUnit};
 /// use fart_2d_geom::is_counter_clockwise;
@@ -152,13 +209,7 @@ where
 /// ```
 pub fn is_counter_clockwise<T, U>(vertices: &[Point2D<T, U>]) -> bool
 where
-    T: Copy + NumAssign + Signed + PartialOrd,
+    T: Scalar,
 {
-    let mut sum = T::zero();
-    for (i, j) in (0..vertices.len()).zip((1..vertices.len()).chain(Some(0))) {
-        let a = vertices[i];
-        let b = vertices[j];
-        sum += (b.x - a.x) * (b.y + a.y);
-    }
-    sum <= T::zero()
+    predicates::ring_orientation(vertices) != RelativeDirection::Right
 }
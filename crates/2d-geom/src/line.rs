@@ -1,10 +1,8 @@
-use crate::area2;
+use crate::predicates::crossing_point;
+use crate::{orientation, Scalar};
 use euclid::{point2, Point2D};
 use fart_aabb::{Aabb, ToAabb};
-use fart_utils::NoMorePartial;
-use num_traits::{Num, NumCast};
 use partial_min_max::{max, min};
-use std::cmp::Ordering;
 
 /// A line between two points.
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
@@ -37,7 +35,7 @@ pub fn line<T, U>(a: Point2D<T, U>, b: Point2D<T, U>) -> Line<T, U> {
 
 impl<T, U> Line<T, U>
 where
-    T: Copy + Num + PartialOrd,
+    T: Scalar,
 {
     /// Create a new line between the given points.
     #[inline]
@@ -46,15 +44,11 @@ where
     }
 
     /// Get the direction of the point relative to this line.
+    ///
+    /// This is exact, even for floating point coordinates. See `orientation`.
     #[inline]
     pub fn relative_direction_of(&self, point: Point2D<T, U>) -> RelativeDirection {
-        let zero = NoMorePartial(T::zero());
-        let det = NoMorePartial(area2(self.a, self.b, point));
-        match det.cmp(&zero) {
-            Ordering::Greater => RelativeDirection::Left,
-            Ordering::Equal => RelativeDirection::Collinear,
-            Ordering::Less => RelativeDirection::Right,
-        }
+        orientation(self.a, self.b, point)
     }
 
     /// Is the given point on the left of this line?
@@ -269,7 +263,7 @@ where
 
 impl<T, U> Line<T, U>
 where
-    T: Copy + NumCast,
+    T: Scalar,
 {
     /// Cast from number representation `T` to number representation `V`.
     ///
@@ -285,7 +279,7 @@ where
     #[inline]
     pub fn cast<V>(&self) -> Line<V, U>
     where
-        V: NumCast + Copy,
+        V: Scalar,
     {
         line(self.a.cast(), self.b.cast())
     }
@@ -293,7 +287,7 @@ where
 
 impl<T, U> Line<T, U>
 where
-    T: Copy + Num + PartialOrd
+    T: Scalar,
 {
     /// Transform this line with the given linear transformation and return the
     /// new, transformed line.
@@ -336,10 +330,25 @@ where
     }
 }
 
-impl<U> Line<f64, U> {
+impl<T, U> Line<T, U>
+where
+    T: Scalar,
+{
     /// Returns the length of the vector `self.a - self.b`.
+    ///
+    /// The length is an `f64`, even for integer coordinates.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::{line, Line};
+    ///
+    /// let l: Line<i64, UnknownUnit> = line(point2(0, 0), point2(3, 4));
+    /// assert_eq!(l.length(), 5.0);
+    /// ```
     pub fn length(&self) -> f64 {
-        (self.a - self.b).length()
+        let d = self.a - self.b;
+        let (x, y) = (d.x.to_float(), d.y.to_float());
+        (x * x + y * y).sqrt()
     }
 
     /// Get the intersection between two line segments.
@@ -347,7 +356,12 @@ impl<U> Line<f64, U> {
     /// The kind of intersection is broken down by whether it is proper,
     /// improper, or collinear. If you don't care what kind of intersection it
     /// is, use `LineIntersection::point` to just get the point of intersection,
-    /// if any.
+    /// if any. The point is an `f64`, even for integer coordinates.
+    ///
+    /// The kind of intersection is decided exactly, with `orientation`, even
+    /// for floating point coordinates. An improper intersection's point is the
+    /// end point that lands on the other line segment, and a proper
+    /// intersection's point is rounded to the nearest `f64`.
     ///
     /// ```
     /// use euclid::{point2, UnknownUnit};
@@ -427,41 +441,40 @@ impl<U> Line<f64, U> {
     ///     Some(point2(1.0, 1.0)),
     /// );
     /// ```
-    pub fn intersection(&self, other: &Line<f64, U>) -> LineIntersection<U> {
-        let denominator = self.a.x * (other.b.y - other.a.y)
-            + self.b.x * (other.a.y - other.b.y)
-            + other.b.x * (self.b.y - self.a.y)
-            + other.a.x * (self.a.y - self.b.y);
-
-        if denominator == 0.0 {
+    pub fn intersection(&self, other: &Line<T, U>) -> LineIntersection<U> {
+        let (a, b, c, d) = (self.a, self.b, other.a, other.b);
+        let (c_side, d_side) = (orientation(a, b, c), orientation(a, b, d));
+        let (a_side, b_side) = (orientation(c, d, a), orientation(c, d, b));
+
+        use RelativeDirection::Collinear;
+        if (c_side == Collinear && d_side == Collinear)
+            || (a_side == Collinear && b_side == Collinear)
+        {
             return self.parallel_intersection(other);
         }
 
-        let numerator = self.a.x * (other.b.y - other.a.y)
-            + other.a.x * (self.a.y - other.b.y)
-            + other.b.x * (other.a.y - self.a.y);
-
-        let s = numerator / denominator;
-
-        let numerator = -(self.a.x * (other.a.y - self.b.y)
-            + self.b.x * (self.a.y - other.a.y)
-            + other.a.x * (self.b.y - self.a.y));
-
-        let t = numerator / denominator;
-
-        let p = self.a.lerp(self.b, s);
+        // Both ends of one segment on the same side of the other's line.
+        if (c_side == d_side) || (a_side == b_side) {
+            return LineIntersection::None;
+        }
 
-        if numerator == 0.0 || numerator == denominator {
-            LineIntersection::Improper(p)
-        } else if 0.0 < s && s < 1.0 && 0.0 < t && t < 1.0 {
-            LineIntersection::Proper(p)
+        // Otherwise the lines cross between both segments' ends, and if any end
+        // is on the other line, then that is where.
+        if c_side == Collinear {
+            LineIntersection::Improper(c.cast())
+        } else if d_side == Collinear {
+            LineIntersection::Improper(d.cast())
+        } else if a_side == Collinear {
+            LineIntersection::Improper(a.cast())
+        } else if b_side == Collinear {
+            LineIntersection::Improper(b.cast())
         } else {
-            LineIntersection::None
+            LineIntersection::Proper(crossing_point(a.cast(), b.cast(), c.cast(), d.cast()))
         }
     }
 
-    fn parallel_intersection(&self, other: &Line<f64, U>) -> LineIntersection<U> {
-        let between = |l: &Self, p: euclid::Point2D<f64, U>| {
+    fn parallel_intersection(&self, other: &Line<T, U>) -> LineIntersection<U> {
+        let between = |l: &Self, p: Point2D<T, U>| {
             if l.a.x != l.b.x {
                 (l.a.x <= p.x && p.x <= l.b.x) || (l.b.x <= p.x && p.x <= l.a.x)
             } else {
@@ -472,13 +485,13 @@ impl<U> Line<f64, U> {
         if !self.is_collinear(other.a) {
             LineIntersection::None
         } else if between(self, other.a) {
-            LineIntersection::Collinear(other.a)
+            LineIntersection::Collinear(other.a.cast())
         } else if between(self, other.b) {
-            LineIntersection::Collinear(other.a)
+            LineIntersection::Collinear(other.b.cast())
         } else if between(other, self.a) {
-            LineIntersection::Collinear(self.a)
+            LineIntersection::Collinear(self.a.cast())
         } else if between(other, self.b) {
-            LineIntersection::Collinear(self.b)
+            LineIntersection::Collinear(self.b.cast())
         } else {
             LineIntersection::None
         }
@@ -557,7 +570,7 @@ impl<U> LineIntersection<U> {
 
 impl<T, U> ToAabb<T, U> for Line<T, U>
 where
-    T: Copy + Num + PartialOrd,
+    T: Scalar,
 {
     fn to_aabb(&self) -> Aabb<T, U> {
         let min = point2(min(self.a.x, self.b.x), min(self.a.y, self.b.y));
@@ -0,0 +1,408 @@
+use crate::{area2, boolean, is_counter_clockwise, BooleanOp, Polygon, PolygonWithHoles};
+use euclid::{point2, vec2, Point2D, Vector2D};
+use std::slice;
+
+/// How far a round join's chords may stray from a true arc, relative to the
+/// offset distance.
+const ROUND_TOLERANCE: f64 = 0.002;
+
+/// How the offset edges of a polygon are joined together at its corners.
+///
+/// See `Polygon::offset`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JoinStyle {
+    /// Extend the offset edges until they meet at a sharp point.
+    ///
+    /// Very sharp corners would put that point very far away, so if it would
+    /// be more than `limit` times the offset distance from the corner, the
+    /// corner is beveled instead.
+    Miter {
+        /// The miter limit. Must be at least `1.0`. A limit of `2.0` bevels
+        /// corners sharper than 60 degrees.
+        limit: f64,
+    },
+
+    /// Round the corner off with a circular arc, keeping every point of the
+    /// result exactly the offset distance from the original polygon.
+    Round,
+
+    /// Cut the corner off with a straight line between the ends of the offset
+    /// edges.
+    Bevel,
+}
+
+/// How the open ends of a stroke are capped off.
+///
+/// See `stroke`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LineCap {
+    /// End the stroke flat, exactly at its end point.
+    Butt,
+
+    /// End the stroke with a half circle around its end point.
+    Round,
+
+    /// End the stroke flat, but extended past its end point by half of the
+    /// stroke's width.
+    Square,
+}
+
+impl<U> Polygon<f64, U> {
+    /// Grow this polygon outwards by `distance`, or shrink it inwards if
+    /// `distance` is negative.
+    ///
+    /// Each edge is pushed out (or in) along its normal, and the gaps this
+    /// opens up at corners are filled according to the `join` style. The
+    /// result is cleaned up with `boolean`, so the self-intersections that
+    /// naively offsetting each edge creates are resolved, and the result is a
+    /// set of non-overlapping polygons with holes: shrinking a concave polygon
+    /// can split it into several pieces (or none at all, if it shrinks away
+    /// entirely), and growing one can close its concavities off into holes.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::{JoinStyle, Polygon};
+    ///
+    /// // Two 4x4 squares, joined by a 1-wide corridor.
+    /// let dumbbell = Polygon::<f64, UnknownUnit>::new(vec![
+    ///     point2(0.0, 0.0),
+    ///     point2(4.0, 0.0),
+    ///     point2(4.0, 1.5),
+    ///     point2(6.0, 1.5),
+    ///     point2(6.0, 0.0),
+    ///     point2(10.0, 0.0),
+    ///     point2(10.0, 4.0),
+    ///     point2(6.0, 4.0),
+    ///     point2(6.0, 2.5),
+    ///     point2(4.0, 2.5),
+    ///     point2(4.0, 4.0),
+    ///     point2(0.0, 4.0),
+    /// ]);
+    ///
+    /// // Growing it with miter joins keeps its corners sharp, and fills in the
+    /// // notches on either side of the corridor.
+    /// let grown = dumbbell.offset(1.0, JoinStyle::Miter { limit: 2.0 });
+    /// assert_eq!(grown.len(), 1);
+    /// assert_eq!(grown[0].exterior().len(), 4);
+    /// assert_eq!(grown[0].exterior().area(), 12.0 * 6.0);
+    ///
+    /// // Shrinking it pinches the corridor away, leaving two 2x2 squares.
+    /// let shrunk = dumbbell.offset(-1.0, JoinStyle::Miter { limit: 2.0 });
+    /// assert_eq!(shrunk.len(), 2);
+    /// assert_eq!(shrunk[0].exterior().area(), 4.0);
+    /// assert_eq!(shrunk[1].exterior().area(), 4.0);
+    /// ```
+    pub fn offset(&self, distance: f64, join: JoinStyle) -> Vec<PolygonWithHoles<f64, U>> {
+        let polygon = Polygon::new(self.vertices().to_vec());
+        offset(&[polygon.into()], distance, join)
+    }
+}
+
+impl<U> PolygonWithHoles<f64, U> {
+    /// Grow this polygon outwards by `distance`, or shrink it inwards if
+    /// `distance` is negative. Its holes shrink as it grows, and grow as it
+    /// shrinks.
+    ///
+    /// See `Polygon::offset` for details.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::{JoinStyle, Polygon, PolygonWithHoles};
+    ///
+    /// let square = |min, max| -> Polygon<f64, UnknownUnit> {
+    ///     Polygon::new(vec![
+    ///         point2(min, min),
+    ///         point2(max, min),
+    ///         point2(max, max),
+    ///         point2(min, max),
+    ///     ])
+    /// };
+    /// let frame = PolygonWithHoles::new(square(0.0, 10.0), vec![square(4.0, 6.0)]);
+    ///
+    /// let thinner = frame.offset(-1.0, JoinStyle::Bevel);
+    /// assert_eq!(thinner.len(), 1);
+    /// assert_eq!(thinner[0].holes().len(), 1);
+    ///
+    /// // Growing the frame by more than half of its hole's width fills the
+    /// // hole in.
+    /// let filled = frame.offset(1.5, JoinStyle::Bevel);
+    /// assert_eq!(filled.len(), 1);
+    /// assert!(filled[0].holes().is_empty());
+    /// ```
+    pub fn offset(&self, distance: f64, join: JoinStyle) -> Vec<PolygonWithHoles<f64, U>> {
+        offset(slice::from_ref(self), distance, join)
+    }
+}
+
+/// Get the outline of stroking the given polylines with a pen of the given
+/// `width`.
+///
+/// The result is the region within half of `width` of the polylines, with
+/// their corners joined according to the `join` style, and their ends capped
+/// according to the `cap` style. A polyline whose last point is its first
+/// point is closed, and is joined there instead of capped. A polyline with a
+/// single point is a dot, which only has caps.
+///
+/// Like `Polygon::offset`, the result is cleaned up with `boolean`, so
+/// polylines that cross themselves or each other are fine, and closed
+/// polylines' outlines have holes in them.
+///
+/// ```
+/// use euclid::{point2, UnknownUnit};
+/// use fart_2d_geom::{stroke, JoinStyle, LineCap};
+///
+/// // An "L".
+/// let l = vec![point2(0.0, 10.0), point2(0.0, 0.0), point2(5.0, 0.0)];
+///
+/// let outline = stroke::<UnknownUnit>(&[l], 2.0, LineCap::Butt, JoinStyle::Miter { limit: 2.0 });
+/// assert_eq!(outline.len(), 1);
+/// assert_eq!(outline[0].exterior().len(), 6);
+/// assert_eq!(outline[0].exterior().area(), 11.0 * 2.0 + 4.0 * 2.0);
+///
+/// // A square outline, closed back at its start.
+/// let square = vec![
+///     point2(0.0, 0.0),
+///     point2(10.0, 0.0),
+///     point2(10.0, 10.0),
+///     point2(0.0, 10.0),
+///     point2(0.0, 0.0),
+/// ];
+///
+/// let outline = stroke::<UnknownUnit>(&[square], 2.0, LineCap::Butt, JoinStyle::Miter { limit: 2.0 });
+/// assert_eq!(outline.len(), 1);
+/// assert_eq!(outline[0].exterior().area(), 12.0 * 12.0);
+/// assert_eq!(outline[0].holes().len(), 1);
+/// assert_eq!(outline[0].holes()[0].area(), 8.0 * 8.0);
+/// ```
+pub fn stroke<U>(
+    polylines: &[Vec<Point2D<f64, U>>],
+    width: f64,
+    cap: LineCap,
+    join: JoinStyle,
+) -> Vec<PolygonWithHoles<f64, U>> {
+    let mut pieces = vec![];
+    if width > 0.0 {
+        for polyline in polylines {
+            stroke_polyline(polyline.clone(), width / 2.0, cap, join, &mut pieces);
+        }
+    }
+    boolean(&pieces, &[], BooleanOp::Union)
+}
+
+/// Offset the region covered by the given polygons with holes.
+///
+/// Growing the region is the union of the region and, along its boundary, a
+/// rectangle swept out by each edge and a join piece at each convex corner.
+/// Shrinking it is the difference of the region and the same pieces on the
+/// inside of its boundary, where the joins go at the reflex corners instead.
+fn offset<U>(
+    region: &[PolygonWithHoles<f64, U>],
+    distance: f64,
+    join: JoinStyle,
+) -> Vec<PolygonWithHoles<f64, U>> {
+    let mut pieces = vec![];
+    if distance != 0.0 {
+        for polygon in region {
+            let exterior = polygon.exterior().vertices().iter().cloned();
+            offset_ring(exterior.collect(), distance, join, &mut pieces);
+            // Holes are reversed, so that the region is always on the left of
+            // the boundary.
+            for hole in polygon.holes() {
+                let hole = hole.vertices().iter().rev().cloned();
+                offset_ring(hole.collect(), distance, join, &mut pieces);
+            }
+        }
+    }
+
+    let op = if distance < 0.0 {
+        BooleanOp::Difference
+    } else {
+        BooleanOp::Union
+    };
+    boolean(region, &pieces, op)
+}
+
+/// Push the edge rectangles and corner joins for offsetting a ring, whose
+/// inside is on its left, by `distance`.
+fn offset_ring<U>(
+    mut ring: Vec<Point2D<f64, U>>,
+    distance: f64,
+    join: JoinStyle,
+    pieces: &mut Vec<PolygonWithHoles<f64, U>>,
+) {
+    ring.dedup();
+    while ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    if ring.len() < 2 {
+        return;
+    }
+
+    let n = ring.len();
+    let directions: Vec<Vector2D<f64, U>> = (0..n)
+        .map(|i| (ring[(i + 1) % n] - ring[i]).normalize())
+        .collect();
+
+    for i in 0..n {
+        // The offset edge is on the right of the ring's edge when growing, and
+        // on its left when shrinking.
+        let (a, b) = (ring[i], ring[(i + 1) % n]);
+        let d = directions[i];
+        let o = vec2(d.y, -d.x) * distance;
+        push_piece(vec![a, b, b + o, a + o], pieces);
+
+        let e1 = directions[(i + n - 1) % n];
+        let e2 = d;
+        let cross = e1.cross(e2);
+        if cross * distance > 0.0 || (cross == 0.0 && e1.dot(e2) < 0.0) {
+            push_piece(join_piece(a, e1, e2, distance, join), pieces);
+        }
+    }
+}
+
+/// Push the edge rectangles, corner joins, and end caps for stroking a
+/// polyline `radius` out to either side.
+fn stroke_polyline<U>(
+    mut polyline: Vec<Point2D<f64, U>>,
+    radius: f64,
+    cap: LineCap,
+    join: JoinStyle,
+    pieces: &mut Vec<PolygonWithHoles<f64, U>>,
+) {
+    polyline.dedup();
+    match polyline.len() {
+        0 => return,
+        1 => {
+            for &d in &[vec2(1.0, 0.0), vec2(-1.0, 0.0)] {
+                push_cap(polyline[0], d, radius, cap, pieces);
+            }
+            return;
+        }
+        _ => {}
+    }
+
+    let closed = polyline.len() > 2 && polyline.first() == polyline.last();
+    if closed {
+        polyline.pop();
+    }
+    let n = polyline.len();
+    let segments = if closed { n } else { n - 1 };
+    let directions: Vec<Vector2D<f64, U>> = (0..segments)
+        .map(|i| (polyline[(i + 1) % n] - polyline[i]).normalize())
+        .collect();
+
+    for i in 0..segments {
+        let (a, b) = (polyline[i], polyline[(i + 1) % n]);
+        let d = directions[i];
+        let o = vec2(d.y, -d.x) * radius;
+        push_piece(vec![a + o, b + o, b - o, a - o], pieces);
+
+        if i == 0 && !closed {
+            continue;
+        }
+        // Join on the outside of the turn, which is the right when turning
+        // left.
+        let e1 = directions[(i + segments - 1) % segments];
+        let e2 = d;
+        let cross = e1.cross(e2);
+        if cross != 0.0 || e1.dot(e2) < 0.0 {
+            let distance = if cross < 0.0 { -radius } else { radius };
+            push_piece(join_piece(a, e1, e2, distance, join), pieces);
+        }
+    }
+
+    if !closed {
+        push_cap(polyline[0], -directions[0], radius, cap, pieces);
+        push_cap(
+            polyline[n - 1],
+            directions[segments - 1],
+            radius,
+            cap,
+            pieces,
+        );
+    }
+}
+
+/// Push the cap for the end of a stroke at `p`, where the stroke points out
+/// of `p` in direction `d`.
+fn push_cap<U>(
+    p: Point2D<f64, U>,
+    d: Vector2D<f64, U>,
+    radius: f64,
+    cap: LineCap,
+    pieces: &mut Vec<PolygonWithHoles<f64, U>>,
+) {
+    let o = vec2(d.y, -d.x) * radius;
+    match cap {
+        LineCap::Butt => {}
+        LineCap::Round => push_piece(join_piece(p, d, -d, radius, JoinStyle::Round), pieces),
+        LineCap::Square => {
+            let e = d * radius;
+            push_piece(vec![p + o, p + o + e, p - o + e, p - o], pieces);
+        }
+    }
+}
+
+/// Get the piece that fills the gap between the offset edges before and after
+/// the corner at `v`, whose incoming and outgoing directions are `e1` and
+/// `e2`.
+fn join_piece<U>(
+    v: Point2D<f64, U>,
+    e1: Vector2D<f64, U>,
+    e2: Vector2D<f64, U>,
+    distance: f64,
+    join: JoinStyle,
+) -> Vec<Point2D<f64, U>> {
+    let radius = distance.abs();
+    let sign = distance.signum();
+    let u1 = vec2(e1.y, -e1.x) * sign;
+    let u2 = vec2(e2.y, -e2.x) * sign;
+    let p1 = v + u1 * radius;
+    let p2 = v + u2 * radius;
+
+    // The corner's bisector points into the gap, and halves the angle from
+    // `u1` to `u2`. This also works when the ring doubles back on itself and
+    // `u1` and `u2` are opposite each other.
+    let bisector = (e1 - e2).normalize();
+    let half = u1.cross(bisector).atan2(u1.dot(bisector));
+
+    match join {
+        JoinStyle::Miter { limit } if half.cos() * limit >= 1.0 => {
+            vec![v, p1, v + bisector * (radius / half.cos()), p2]
+        }
+        JoinStyle::Miter { .. } | JoinStyle::Bevel => vec![v, p1, p2],
+        JoinStyle::Round => {
+            let max_step = 2.0 * (1.0 - ROUND_TOLERANCE).acos();
+            let sweep = 2.0 * half;
+            let steps = (sweep.abs() / max_step).ceil().max(1.0) as usize;
+            let mut points = vec![v, p1];
+            for k in 1..steps {
+                let (sin, cos) = (sweep * k as f64 / steps as f64).sin_cos();
+                let u = vec2(u1.x * cos - u1.y * sin, u1.x * sin + u1.y * cos);
+                points.push(v + u * radius);
+            }
+            points.push(p2);
+            points
+        }
+    }
+}
+
+/// Push the polygon with the given vertices, in either order, unless it is
+/// degenerate.
+fn push_piece<U>(mut vertices: Vec<Point2D<f64, U>>, pieces: &mut Vec<PolygonWithHoles<f64, U>>) {
+    let origin = point2(0.0, 0.0);
+    let area: f64 = (0..vertices.len())
+        .map(|i| area2(origin, vertices[i], vertices[(i + 1) % vertices.len()]))
+        .sum();
+    if area == 0.0 || !area.is_finite() {
+        return;
+    }
+    if !is_counter_clockwise(&vertices) {
+        vertices.reverse();
+        // Slivers can be too thin to have a consistent orientation.
+        if !is_counter_clockwise(&vertices) {
+            return;
+        }
+    }
+    pieces.push(Polygon::new(vertices).into());
+}
@@ -1,8 +1,8 @@
-use crate::{area2, is_counter_clockwise, line, Line};
+use crate::predicates::ring_orientation;
+use crate::{area2, is_counter_clockwise, line, orientation, Line, RelativeDirection, Scalar};
 use euclid::{point2, Point2D};
 use fart_aabb::{Aabb, ToAabb};
 use fart_utils::NoMorePartial;
-use num_traits::{Num, NumAssign, NumCast, Signed};
 use rand::prelude::*;
 use std::collections::{BTreeSet, HashSet};
 use std::fmt;
@@ -40,7 +40,7 @@ where
 
 impl<T, U> Polygon<T, U>
 where
-    T: Copy + NumAssign + PartialOrd + Signed + fmt::Debug,
+    T: Scalar,
 {
     /// Construct a new polygon.
     pub fn new(vertices: Vec<Point2D<T, U>>) -> Polygon<T, U> {
@@ -74,10 +74,7 @@ where
         x_dist: &mut impl Distribution<T>,
         y_dist: &mut impl Distribution<T>,
         n: usize,
-    ) -> Polygon<T, U>
-    where
-        T: NumCast,
-    {
+    ) -> Polygon<T, U> {
         assert!(n >= 3);
 
         let mut vertices_set = BTreeSet::new();
@@ -132,7 +129,7 @@ where
             m: Line<T, U>,
         ) -> bool
         where
-            T: Num + Copy + PartialOrd,
+            T: Scalar,
         {
             for j in 0..vertices.len() {
                 let a = vertices[j];
@@ -214,18 +211,12 @@ where
     /// assert_eq!(triangle.area(), 18);
     /// ```
     #[inline]
-    pub fn area(&self) -> T
-    where
-        T: Signed,
-    {
+    pub fn area(&self) -> T {
         let two = T::one() + T::one();
         (self.signed_double_area() / two).abs()
     }
 
-    fn signed_double_area(&self) -> T
-    where
-        T: Signed,
-    {
+    fn signed_double_area(&self) -> T {
         let mut sum = T::zero();
         for i in 1..self.vertices.len() - 1 {
             sum += area2(self.vertices[0], self.vertices[i], self.vertices[i + 1]);
@@ -233,6 +224,187 @@ where
         sum
     }
 
+    /// Get the winding number of this polygon around the given point: the
+    /// number of times that its boundary goes counter-clockwise around the
+    /// point, minus the number of times it goes clockwise around it.
+    ///
+    /// Points exactly on the boundary have a winding number of zero.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::Polygon;
+    ///
+    /// let square: Polygon<i32, UnknownUnit> = Polygon::new(vec![
+    ///     point2(0, 0),
+    ///     point2(10, 0),
+    ///     point2(10, 10),
+    ///     point2(0, 10),
+    /// ]);
+    ///
+    /// assert_eq!(square.winding_number(point2(5, 5)), 1);
+    /// assert_eq!(square.winding_number(point2(15, 5)), 0);
+    /// assert_eq!(square.winding_number(point2(0, 5)), 0);
+    /// ```
+    pub fn winding_number(&self, point: Point2D<T, U>) -> i32 {
+        winding_number(&self.vertices, point).unwrap_or(0)
+    }
+
+    /// Does this polygon properly contain the given point, under the non-zero
+    /// fill rule?
+    ///
+    /// A point is contained if this polygon's winding number around it is not
+    /// zero. Points exactly on the boundary are not considered contained.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::Polygon;
+    ///
+    /// // A "C" shape.
+    /// let c: Polygon<i32, UnknownUnit> = Polygon::new(vec![
+    ///     point2(0, 0),
+    ///     point2(10, 0),
+    ///     point2(10, 2),
+    ///     point2(2, 2),
+    ///     point2(2, 8),
+    ///     point2(10, 8),
+    ///     point2(10, 10),
+    ///     point2(0, 10),
+    /// ]);
+    ///
+    /// assert!(c.contains_point(point2(1, 5)));
+    /// assert!(c.contains_point(point2(5, 1)));
+    /// assert!(!c.contains_point(point2(5, 5)));
+    ///
+    /// // Points exactly on the edge are not considered contained.
+    /// assert!(!c.contains_point(point2(2, 5)));
+    /// ```
+    pub fn contains_point(&self, point: Point2D<T, U>) -> bool {
+        self.winding_number(point) != 0
+    }
+
+    /// Does this polygon properly contain the given point, under the even-odd
+    /// fill rule?
+    ///
+    /// A point is contained if a ray from it crosses this polygon's boundary
+    /// an odd number of times. This only differs from `contains_point` for
+    /// polygons that are not simple. Points exactly on the boundary are not
+    /// considered contained.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::Polygon;
+    ///
+    /// // A pentagram, whose boundary goes around its center twice.
+    /// let star: Polygon<i32, UnknownUnit> = Polygon::new(vec![
+    ///     point2(0, 10),
+    ///     point2(-6, -8),
+    ///     point2(10, 3),
+    ///     point2(-10, 3),
+    ///     point2(6, -8),
+    /// ]);
+    ///
+    /// assert_eq!(star.winding_number(point2(0, 0)), 2);
+    /// assert!(star.contains_point(point2(0, 0)));
+    /// assert!(!star.contains_point_even_odd(point2(0, 0)));
+    ///
+    /// // The star's points are only gone around once.
+    /// assert!(star.contains_point_even_odd(point2(0, 8)));
+    /// ```
+    pub fn contains_point_even_odd(&self, point: Point2D<T, U>) -> bool {
+        self.winding_number(point) % 2 != 0
+    }
+
+    /// Is this polygon simple?
+    ///
+    /// A simple polygon's boundary doesn't touch or cross itself: the only
+    /// place that any two of its edges meet is the vertex between two
+    /// consecutive edges.
+    ///
+    /// This is an *O(n<sup>2</sup>)* algorithm.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::Polygon;
+    ///
+    /// let square: Polygon<i32, UnknownUnit> = Polygon::new(vec![
+    ///     point2(0, 0),
+    ///     point2(10, 0),
+    ///     point2(10, 10),
+    ///     point2(0, 10),
+    /// ]);
+    /// assert!(square.is_simple());
+    ///
+    /// let bowtie: Polygon<i32, UnknownUnit> = Polygon::new(vec![
+    ///     point2(0, 0),
+    ///     point2(10, 10),
+    ///     point2(10, 0),
+    ///     point2(0, 10),
+    ///     point2(-1, 5),
+    /// ]);
+    /// assert!(!bowtie.is_simple());
+    /// ```
+    pub fn is_simple(&self) -> bool {
+        let n = self.vertices.len();
+        for i in 0..n {
+            let edge = self.edge(i);
+            if edge.a == edge.b {
+                return false;
+            }
+
+            // Consecutive edges may only meet at their shared vertex, and so
+            // must not double back over each other.
+            let next = self.edge(self.next(i));
+            if edge.is_collinear(next.b) && (edge.is_on(next.b) || next.is_on(edge.a)) {
+                return false;
+            }
+
+            // Other edges must not meet at all.
+            for j in i + 2..n {
+                if i == 0 && j == n - 1 {
+                    continue;
+                }
+                if edge.improperly_intersects(&self.edge(j)) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Is this polygon convex?
+    ///
+    /// A convex polygon is simple, has non-zero area, and turns left (or goes
+    /// straight) at every vertex.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::Polygon;
+    ///
+    /// let square: Polygon<i32, UnknownUnit> = Polygon::new(vec![
+    ///     point2(0, 0),
+    ///     point2(10, 0),
+    ///     point2(10, 10),
+    ///     point2(0, 10),
+    /// ]);
+    /// assert!(square.is_convex());
+    ///
+    /// let arrow: Polygon<i32, UnknownUnit> = Polygon::new(vec![
+    ///     point2(0, 0),
+    ///     point2(10, 5),
+    ///     point2(0, 10),
+    ///     point2(3, 5),
+    /// ]);
+    /// assert!(!arrow.is_convex());
+    /// ```
+    pub fn is_convex(&self) -> bool {
+        ring_orientation(&self.vertices) == RelativeDirection::Left
+            && (0..self.len()).all(|i| {
+                line(self.vertices[self.prev(i)], self.vertices[i])
+                    .is_left_or_collinear(self.vertices[self.next(i)])
+            })
+            && self.is_simple()
+    }
+
     /// Do the `a`<sup>th</sup> and `b`<sup>th</sup> vertices within this
     /// polygon form a diagonal?
     ///
@@ -474,7 +646,7 @@ where
 
 impl<T, U> Polygon<T, U>
 where
-    T: Copy + NumCast,
+    T: Scalar,
 {
     /// Cast from number representation `T` to number representation `V`.
     ///
@@ -493,7 +665,7 @@ where
     /// ```
     pub fn cast<V>(&self) -> Polygon<V, U>
     where
-        V: NumCast + Copy,
+        V: Scalar,
     {
         Polygon {
             vertices: self.vertices.iter().map(|p| p.cast()).collect(),
@@ -503,7 +675,7 @@ where
 
 impl<T, U> Polygon<T, U>
 where
-    T: Copy + Num + PartialOrd
+    T: Scalar,
 {
     /// Transform this polygon with the given linear transformation and return
     /// the new, transformed polygon.
@@ -557,9 +729,69 @@ where
     }
 }
 
+impl<U> Polygon<f64, U> {
+    /// Get the centroid of this polygon: the center of mass of its area.
+    ///
+    /// Polygons whose area is zero have no such center, and so the mean of
+    /// their vertices is returned instead.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::Polygon;
+    ///
+    /// // Unlike the mean of the vertices, the centroid isn't pulled towards
+    /// // the corner with extra vertices.
+    /// let square: Polygon<f64, UnknownUnit> = Polygon::new(vec![
+    ///     point2(0.0, 0.0),
+    ///     point2(1.0, 0.0),
+    ///     point2(2.0, 0.0),
+    ///     point2(2.0, 1.0),
+    ///     point2(2.0, 2.0),
+    ///     point2(0.0, 2.0),
+    /// ]);
+    ///
+    /// assert_eq!(square.centroid(), point2(1.0, 1.0));
+    /// ```
+    pub fn centroid(&self) -> Point2D<f64, U> {
+        let origin = self.vertices[0];
+        let (mut x, mut y, mut double_area) = (0.0, 0.0, 0.0);
+        for i in 1..self.vertices.len() - 1 {
+            let (a, b) = (self.vertices[i], self.vertices[i + 1]);
+            let w = area2(origin, a, b);
+            x += w * (origin.x + a.x + b.x);
+            y += w * (origin.y + a.y + b.y);
+            double_area += w;
+        }
+
+        if double_area == 0.0 {
+            crate::center(&self.vertices)
+        } else {
+            point2(x / (3.0 * double_area), y / (3.0 * double_area))
+        }
+    }
+
+    /// Get the perimeter of this polygon: the sum of its edges' lengths.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::Polygon;
+    ///
+    /// let triangle: Polygon<f64, UnknownUnit> = Polygon::new(vec![
+    ///     point2(0.0, 0.0),
+    ///     point2(3.0, 0.0),
+    ///     point2(3.0, 4.0),
+    /// ]);
+    ///
+    /// assert_eq!(triangle.perimeter(), 12.0);
+    /// ```
+    pub fn perimeter(&self) -> f64 {
+        self.edges().map(|e| e.length()).sum()
+    }
+}
+
 impl<T, U> ToAabb<T, U> for Polygon<T, U>
 where
-    T: Copy + Num + PartialOrd,
+    T: Scalar,
 {
     fn to_aabb(&self) -> Aabb<T, U> {
         Aabb::for_vertices(self.vertices.iter().cloned())
@@ -568,7 +800,7 @@ where
 
 impl<T, U> From<Aabb<T, U>> for Polygon<T, U>
 where
-    T: Copy + NumAssign + PartialOrd + Signed + fmt::Debug,
+    T: Scalar,
 {
     fn from(aabb: Aabb<T, U>) -> Self {
         Polygon::new(vec![
@@ -579,3 +811,29 @@ where
         ])
     }
 }
+
+/// Get the winding number of the ring with the given vertices around the given
+/// point, or `None` if the point is exactly on the ring.
+pub(crate) fn winding_number<T, U>(ring: &[Point2D<T, U>], point: Point2D<T, U>) -> Option<i32>
+where
+    T: Scalar,
+{
+    let mut winding = 0;
+    for (i, &a) in ring.iter().enumerate() {
+        let b = ring[(i + 1) % ring.len()];
+        let direction = orientation(a, b, point);
+        if direction == RelativeDirection::Collinear && line(a, b).is_on(point) {
+            return None;
+        }
+
+        // Count the edges that cross the ray from `point` in the positive x
+        // direction: upwards edges that it is left of, and downwards edges
+        // that it is right of.
+        if a.y <= point.y && point.y < b.y && direction == RelativeDirection::Left {
+            winding += 1;
+        } else if b.y <= point.y && point.y < a.y && direction == RelativeDirection::Right {
+            winding -= 1;
+        }
+    }
+    Some(winding)
+}
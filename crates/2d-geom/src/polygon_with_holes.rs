@@ -0,0 +1,228 @@
+use crate::polygon::winding_number;
+use crate::{DelaunayTriangulation, Polygon, Scalar};
+use euclid::{point2, Point2D};
+use fart_aabb::{Aabb, ToAabb};
+use std::convert::TryFrom;
+use std::fmt;
+
+/// A polygon with holes cut out of it.
+///
+/// The exterior and each of the holes are themselves `Polygon`s, and so their
+/// vertices are in counter-clockwise order. The holes are expected to be
+/// inside the exterior, and to not overlap each other.
+///
+/// * `T` is the numeric type. `i32` or `f64` etc.
+/// * `U` is the unit. `ScreenSpace` or `WorldSpace` etc.
+#[derive(Clone)]
+pub struct PolygonWithHoles<T, U> {
+    exterior: Polygon<T, U>,
+    holes: Vec<Polygon<T, U>>,
+}
+
+impl<T, U> fmt::Debug for PolygonWithHoles<T, U>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PolygonWithHoles")
+            .field("exterior", &self.exterior)
+            .field("holes", &self.holes)
+            .finish()
+    }
+}
+
+impl<T, U> PolygonWithHoles<T, U> {
+    /// Construct a new polygon with the given exterior and holes.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::{Polygon, PolygonWithHoles};
+    ///
+    /// let square = |min, max| -> Polygon<i32, UnknownUnit> {
+    ///     Polygon::new(vec![
+    ///         point2(min, min),
+    ///         point2(max, min),
+    ///         point2(max, max),
+    ///         point2(min, max),
+    ///     ])
+    /// };
+    ///
+    /// let frame = PolygonWithHoles::new(square(0, 10), vec![square(2, 8)]);
+    /// assert_eq!(frame.holes().len(), 1);
+    /// ```
+    pub fn new(exterior: Polygon<T, U>, holes: Vec<Polygon<T, U>>) -> PolygonWithHoles<T, U> {
+        PolygonWithHoles { exterior, holes }
+    }
+
+    /// Get this polygon's exterior.
+    pub fn exterior(&self) -> &Polygon<T, U> {
+        &self.exterior
+    }
+
+    /// Get this polygon's holes.
+    pub fn holes(&self) -> &[Polygon<T, U>] {
+        &self.holes
+    }
+}
+
+impl<T, U> PolygonWithHoles<T, U>
+where
+    T: Scalar,
+{
+    /// Get the area of this polygon: the area of its exterior, minus the area
+    /// of its holes.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::{Polygon, PolygonWithHoles};
+    ///
+    /// let square = |min, max| -> Polygon<i32, UnknownUnit> {
+    ///     Polygon::new(vec![
+    ///         point2(min, min),
+    ///         point2(max, min),
+    ///         point2(max, max),
+    ///         point2(min, max),
+    ///     ])
+    /// };
+    ///
+    /// let frame = PolygonWithHoles::new(square(0, 10), vec![square(2, 8)]);
+    /// assert_eq!(frame.area(), 64);
+    /// ```
+    pub fn area(&self) -> T {
+        let mut area = self.exterior.area();
+        for hole in &self.holes {
+            area -= hole.area();
+        }
+        area
+    }
+
+    /// Does this polygon contain the given point?
+    ///
+    /// Points inside a hole are not contained. Neither are points exactly on
+    /// the exterior's or a hole's boundary.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::{Polygon, PolygonWithHoles};
+    ///
+    /// let square = |min, max| -> Polygon<i32, UnknownUnit> {
+    ///     Polygon::new(vec![
+    ///         point2(min, min),
+    ///         point2(max, min),
+    ///         point2(max, max),
+    ///         point2(min, max),
+    ///     ])
+    /// };
+    ///
+    /// let frame = PolygonWithHoles::new(square(0, 10), vec![square(2, 8)]);
+    /// assert!(frame.contains_point(point2(1, 5)));
+    /// assert!(!frame.contains_point(point2(5, 5)));
+    /// assert!(!frame.contains_point(point2(11, 5)));
+    ///
+    /// // Points exactly on the boundary are not considered contained.
+    /// assert!(!frame.contains_point(point2(0, 5)));
+    /// assert!(!frame.contains_point(point2(2, 5)));
+    /// ```
+    pub fn contains_point(&self, point: Point2D<T, U>) -> bool {
+        self.exterior.contains_point(point)
+            && self
+                .holes
+                .iter()
+                .all(|h| winding_number(h.vertices(), point) == Some(0))
+    }
+}
+
+impl<U> PolygonWithHoles<f64, U> {
+    /// Triangulate this polygon.
+    ///
+    /// The given function `f` is invoked with the vertices that make up each
+    /// triangle in this polygon's triangulation. Every triangle is inside the
+    /// exterior and outside of the holes, and every triangle's vertices are
+    /// vertices of the exterior or of a hole.
+    ///
+    /// The triangulation is the constrained Delaunay triangulation of the
+    /// polygon's vertices and edges. See `DelaunayTriangulation`.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::{Polygon, PolygonWithHoles};
+    ///
+    /// let square = |min, max| -> Polygon<f64, UnknownUnit> {
+    ///     Polygon::new(vec![
+    ///         point2(min, min),
+    ///         point2(max, min),
+    ///         point2(max, max),
+    ///         point2(min, max),
+    ///     ])
+    /// };
+    ///
+    /// let frame = PolygonWithHoles::new(square(0.0, 10.0), vec![square(2.0, 8.0)]);
+    ///
+    /// let mut triangles = 0;
+    /// frame.triangulate(|a, b, c| {
+    ///     assert!(frame.contains_point(point2(
+    ///         (a.x + b.x + c.x) / 3.0,
+    ///         (a.y + b.y + c.y) / 3.0,
+    ///     )));
+    ///     triangles += 1;
+    /// });
+    /// assert_eq!(triangles, 8);
+    /// ```
+    pub fn triangulate<F>(&self, mut f: F)
+    where
+        F: FnMut(Point2D<f64, U>, Point2D<f64, U>, Point2D<f64, U>),
+    {
+        let mut points = vec![];
+        let mut constraints = vec![];
+        for ring in Some(&self.exterior).into_iter().chain(&self.holes) {
+            let start = points.len();
+            let n = ring.len();
+            points.extend(ring.vertices().iter().cloned());
+            constraints.extend((0..n).map(|i| (start + i, start + (i + 1) % n)));
+        }
+
+        // Every constrained edge is on the polygon's boundary, so each
+        // triangle is either entirely inside of the polygon or entirely
+        // outside of it.
+        let triangulation = DelaunayTriangulation::with_constraints(points, &constraints);
+        let points = triangulation.points();
+        for [a, b, c] in triangulation.triangles() {
+            let (a, b, c) = (points[a], points[b], points[c]);
+            let centroid = point2((a.x + b.x + c.x) / 3.0, (a.y + b.y + c.y) / 3.0);
+            if self.contains_point(centroid) {
+                f(a, b, c);
+            }
+        }
+    }
+}
+
+impl<T, U> ToAabb<T, U> for PolygonWithHoles<T, U>
+where
+    T: Scalar,
+{
+    fn to_aabb(&self) -> Aabb<T, U> {
+        self.exterior.to_aabb()
+    }
+}
+
+impl<T, U> From<Polygon<T, U>> for PolygonWithHoles<T, U> {
+    #[inline]
+    fn from(exterior: Polygon<T, U>) -> PolygonWithHoles<T, U> {
+        PolygonWithHoles::new(exterior, vec![])
+    }
+}
+
+impl<T, U> TryFrom<PolygonWithHoles<T, U>> for Polygon<T, U> {
+    type Error = PolygonWithHoles<T, U>;
+
+    /// Convert a polygon with holes into a plain polygon, if it doesn't have
+    /// any holes. If it does, it is returned back as the error.
+    #[inline]
+    fn try_from(polygon: PolygonWithHoles<T, U>) -> Result<Polygon<T, U>, Self::Error> {
+        if polygon.holes.is_empty() {
+            Ok(polygon.exterior)
+        } else {
+            Err(polygon)
+        }
+    }
+}
@@ -0,0 +1,526 @@
+use crate::{RelativeDirection, Scalar};
+use euclid::Point2D;
+use num_traits::Num;
+use std::cmp::Ordering;
+use std::mem;
+
+/// Which side of the line from `a` to `b` does `c` lie on? Equivalently, are
+/// `a`, `b`, and `c` in counter-clockwise order (`Left`), clockwise order
+/// (`Right`), or collinear?
+///
+/// The answer is always exact. With floating point coordinates, rounding
+/// errors in the cross product can give wrong answers for nearly collinear
+/// points, and worse, answers that contradict each other, which is what sends
+/// geometric algorithms off the rails. So this uses Shewchuk's adaptive
+/// precision arithmetic: the cross product is computed as usual, and only if
+/// it is too close to zero for its sign to be trusted is it recomputed
+/// exactly.
+///
+/// Integer coordinates are converted to `f64`s first, so that the cross
+/// product can't overflow, which is exact for integers up to `2^53` in
+/// magnitude.
+///
+/// ```
+/// use euclid::{point2, UnknownUnit};
+/// use fart_2d_geom::{orientation, RelativeDirection};
+///
+/// let a = point2::<f64, UnknownUnit>(0.5, 0.5);
+/// let b = point2(12.0, 12.0);
+/// let c = point2(24.0, 24.0);
+/// assert_eq!(orientation(a, b, c), RelativeDirection::Collinear);
+///
+/// // Nudging `a` by the smallest possible amount is enough to move it off of
+/// // the line, which is more than the plain cross product can tell.
+/// let a = point2(0.5 + std::f64::EPSILON / 2.0, 0.5);
+/// assert_eq!(orientation(a, b, c), RelativeDirection::Right);
+/// assert_eq!(orientation(b, c, a), RelativeDirection::Right);
+/// assert_eq!(orientation(c, b, a), RelativeDirection::Left);
+/// ```
+pub fn orientation<T, U>(a: Point2D<T, U>, b: Point2D<T, U>, c: Point2D<T, U>) -> RelativeDirection
+where
+    T: Scalar,
+{
+    cross(a, c, b, c)
+}
+
+/// The sign of the cross product `(a - b) × (c - d)`, exactly.
+///
+/// `orientation(a, b, c)` is `cross(a, c, b, c)`, and comparing the directions
+/// of two line segments is `cross(a.b, a.a, b.b, b.a)`.
+pub(crate) fn cross<T, U>(
+    a: Point2D<T, U>,
+    b: Point2D<T, U>,
+    c: Point2D<T, U>,
+    d: Point2D<T, U>,
+) -> RelativeDirection
+where
+    T: Scalar,
+{
+    if T::IS_INTEGER {
+        return cross(a.to_f64(), b.to_f64(), c.to_f64(), d.to_f64());
+    }
+
+    let zero = T::zero();
+    let left = (a.x - b.x) * (c.y - d.y);
+    let right = (a.y - b.y) * (c.x - d.x);
+    let det = left - right;
+
+    // If the two products have different signs, then there is no cancellation
+    // in their difference, and its sign is right.
+    let sum = if left > zero && right > zero {
+        left + right
+    } else if left < zero && right < zero {
+        zero - left - right
+    } else {
+        return direction(det);
+    };
+
+    let float = match Float::new() {
+        Some(f) => f,
+        None => return direction(det),
+    };
+    if abs(det) >= float.orientation_bound * sum {
+        return direction(det);
+    }
+
+    let ab_x = exact_difference(a.x, b.x);
+    let ab_y = exact_difference(a.y, b.y);
+    let cd_x = exact_difference(c.x, d.x);
+    let cd_y = exact_difference(c.y, d.y);
+    let det = sum_expansions(
+        &float.product(&ab_x, &cd_y),
+        &negate(&float.product(&ab_y, &cd_x)),
+    );
+    direction(*det.last().unwrap())
+}
+
+/// Where is `d` relative to the circle through `a`, `b`, and `c`?
+///
+/// Returns `Ordering::Less` if `d` is inside the circle, `Ordering::Equal` if
+/// it is on the circle, and `Ordering::Greater` if it is outside, as if
+/// comparing its distance from the circle's center with the circle's radius.
+/// If `a`, `b`, and `c` are collinear, then there is no circle through them,
+/// and `d` is considered outside of it.
+///
+/// Like `orientation`, the answer is always exact, even for floating point
+/// coordinates, and integer coordinates are converted to `f64`s first so that
+/// the determinant can't overflow.
+///
+/// ```
+/// use euclid::{point2, UnknownUnit};
+/// use fart_2d_geom::in_circle;
+/// use std::cmp::Ordering;
+///
+/// // The unit circle.
+/// let a = point2::<f64, UnknownUnit>(1.0, 0.0);
+/// let b = point2(0.0, 1.0);
+/// let c = point2(-1.0, 0.0);
+///
+/// assert_eq!(in_circle(a, b, c, point2(0.0, 0.0)), Ordering::Less);
+/// assert_eq!(in_circle(a, b, c, point2(0.0, -1.0)), Ordering::Equal);
+/// assert_eq!(in_circle(a, b, c, point2(1.0, 1.0)), Ordering::Greater);
+///
+/// // The order of the points on the circle doesn't matter.
+/// assert_eq!(in_circle(c, b, a, point2(0.0, 0.0)), Ordering::Less);
+/// ```
+pub fn in_circle<T, U>(
+    a: Point2D<T, U>,
+    b: Point2D<T, U>,
+    c: Point2D<T, U>,
+    d: Point2D<T, U>,
+) -> Ordering
+where
+    T: Scalar,
+{
+    if T::IS_INTEGER {
+        return in_circle(a.to_f64(), b.to_f64(), c.to_f64(), d.to_f64());
+    }
+
+    let det = match orientation(a, b, c) {
+        RelativeDirection::Left => lifted_orientation(a, b, c, d),
+        RelativeDirection::Right => lifted_orientation(c, b, a, d),
+        RelativeDirection::Collinear => return Ordering::Greater,
+    };
+    // The determinant is positive when `d` is inside.
+    match det {
+        RelativeDirection::Left => Ordering::Less,
+        RelativeDirection::Collinear => Ordering::Equal,
+        RelativeDirection::Right => Ordering::Greater,
+    }
+}
+
+/// The sign of the in-circle determinant of the counter-clockwise triangle
+/// `(a, b, c)` and the point `d`, which is the orientation of `d` relative to
+/// the plane through `a`, `b`, and `c` when lifted onto a paraboloid.
+fn lifted_orientation<T, U>(
+    a: Point2D<T, U>,
+    b: Point2D<T, U>,
+    c: Point2D<T, U>,
+    d: Point2D<T, U>,
+) -> RelativeDirection
+where
+    T: Scalar,
+{
+    let (ad_x, ad_y) = (a.x - d.x, a.y - d.y);
+    let (bd_x, bd_y) = (b.x - d.x, b.y - d.y);
+    let (cd_x, cd_y) = (c.x - d.x, c.y - d.y);
+
+    let (bc_1, bc_2) = (bd_x * cd_y, cd_x * bd_y);
+    let (ca_1, ca_2) = (cd_x * ad_y, ad_x * cd_y);
+    let (ab_1, ab_2) = (ad_x * bd_y, bd_x * ad_y);
+    let a_lift = ad_x * ad_x + ad_y * ad_y;
+    let b_lift = bd_x * bd_x + bd_y * bd_y;
+    let c_lift = cd_x * cd_x + cd_y * cd_y;
+    let det = a_lift * (bc_1 - bc_2) + b_lift * (ca_1 - ca_2) + c_lift * (ab_1 - ab_2);
+
+    let float = match Float::new() {
+        Some(f) => f,
+        None => return direction(det),
+    };
+    let permanent = (abs(bc_1) + abs(bc_2)) * a_lift
+        + (abs(ca_1) + abs(ca_2)) * b_lift
+        + (abs(ab_1) + abs(ab_2)) * c_lift;
+    if abs(det) > float.in_circle_bound * permanent {
+        return direction(det);
+    }
+
+    let ad_x = exact_difference(a.x, d.x);
+    let ad_y = exact_difference(a.y, d.y);
+    let bd_x = exact_difference(b.x, d.x);
+    let bd_y = exact_difference(b.y, d.y);
+    let cd_x = exact_difference(c.x, d.x);
+    let cd_y = exact_difference(c.y, d.y);
+
+    let lift = |x: &[T], y: &[T]| sum_expansions(&float.product(x, x), &float.product(y, y));
+    let cross = |x1: &[T], y1: &[T], x2: &[T], y2: &[T]| {
+        sum_expansions(&float.product(x1, y2), &negate(&float.product(x2, y1)))
+    };
+    let a_term = float.product(&lift(&ad_x, &ad_y), &cross(&bd_x, &bd_y, &cd_x, &cd_y));
+    let b_term = float.product(&lift(&bd_x, &bd_y), &cross(&cd_x, &cd_y, &ad_x, &ad_y));
+    let c_term = float.product(&lift(&cd_x, &cd_y), &cross(&ad_x, &ad_y, &bd_x, &bd_y));
+    let det = sum_expansions(&sum_expansions(&a_term, &b_term), &c_term);
+    direction(*det.last().unwrap())
+}
+
+/// Which way does the ring with the given vertices wind? `Left` if it is
+/// counter-clockwise, enclosing positive area, `Right` if it is clockwise, and
+/// `Collinear` if its signed area is zero.
+///
+/// Like `orientation`, the answer is always exact.
+pub(crate) fn ring_orientation<T, U>(vertices: &[Point2D<T, U>]) -> RelativeDirection
+where
+    T: Scalar,
+{
+    let zero = T::zero();
+    let edge = |i: usize| (vertices[i], vertices[(i + 1) % vertices.len()]);
+
+    // The shoelace formula, negated: this is negative twice the signed area.
+    let mut sum = zero;
+    let mut magnitude = zero;
+    let mut count = zero;
+    for i in 0..vertices.len() {
+        let (a, b) = edge(i);
+        let term = (b.x - a.x) * (b.y + a.y);
+        sum += term;
+        magnitude += abs(term);
+        count += T::one();
+    }
+
+    let float = match Float::new() {
+        Some(f) => f,
+        None => return direction(zero - sum),
+    };
+    // Each term is off by at most three roundings, and each addition adds
+    // another. Double that, to be safe.
+    let two = T::one() + T::one();
+    let three = two + T::one();
+    if abs(sum) > two * (count + three) * float.epsilon * magnitude {
+        return direction(zero - sum);
+    }
+
+    let sum = (0..vertices.len()).fold(vec![zero], |sum, i| {
+        let (a, b) = edge(i);
+        let term = float.product(&exact_difference(b.x, a.x), &exact_sum(b.y, a.y));
+        sum_expansions(&sum, &term)
+    });
+    direction(zero - *sum.last().unwrap())
+}
+
+/// Where the line through `a` and `b` crosses the line through `c` and `d`,
+/// with each coordinate rounded to the nearest `f64`. The lines must not be
+/// parallel.
+///
+/// Because the rounding is exact, lines that all cross at the same point agree
+/// on where that is, whichever two of them it is computed from.
+pub(crate) fn crossing_point<U>(
+    a: Point2D<f64, U>,
+    b: Point2D<f64, U>,
+    c: Point2D<f64, U>,
+    d: Point2D<f64, U>,
+) -> Point2D<f64, U> {
+    let float = Float::<f64>::new().unwrap();
+    let (ba_x, ba_y) = (exact_difference(b.x, a.x), exact_difference(b.y, a.y));
+    let (ca_x, ca_y) = (exact_difference(c.x, a.x), exact_difference(c.y, a.y));
+    let (dc_x, dc_y) = (exact_difference(d.x, c.x), exact_difference(d.y, c.y));
+    let cross = |x0: &[f64], y0: &[f64], x1: &[f64], y1: &[f64]| {
+        sum_expansions(&float.product(x0, y1), &negate(&float.product(y0, x1)))
+    };
+
+    // The crossing is `a + (b - a) * t`, where `t = n / denominator`.
+    let denominator = cross(&ba_x, &ba_y, &dc_x, &dc_y);
+    let n = cross(&ca_x, &ca_y, &dc_x, &dc_y);
+    let coordinate = |a: f64, ba: &[f64]| {
+        let numerator = sum_expansions(
+            &float.scale_expansion(&denominator, a),
+            &float.product(ba, &n),
+        );
+        divide(&float, &numerator, &denominator)
+    };
+    Point2D::new(coordinate(a.x, &ba_x), coordinate(a.y, &ba_y))
+}
+
+/// Divide the expansion `n` by the nonzero expansion `d`, rounding to the
+/// nearest `f64`, and rounding up when it is exactly halfway between two.
+fn divide(float: &Float<f64>, n: &[f64], d: &[f64]) -> f64 {
+    let (n, d) = if *d.last().unwrap() < 0.0 {
+        (negate(n), negate(d))
+    } else {
+        (n.to_vec(), d.to_vec())
+    };
+    let estimate = |e: &[f64]| e.iter().sum::<f64>();
+    let mut q = estimate(&n) / estimate(&d);
+    if !q.is_finite() {
+        return q;
+    }
+
+    // The estimate is within a few ulps. Nudge it until `n / d` is within
+    // half an ulp of it, by comparing `2n` with `d` times the sum of `q` and
+    // its neighbor, exactly.
+    let twice_n = float.scale_expansion(&n, 2.0);
+    let above = |q: f64, r: f64| {
+        let midpoint = sum_expansions(&float.scale_expansion(&d, q), &float.scale_expansion(&d, r));
+        *sum_expansions(&twice_n, &negate(&midpoint)).last().unwrap()
+    };
+    loop {
+        let (up, down) = (next_up(q), -next_up(-q));
+        if above(q, up) >= 0.0 {
+            q = up;
+        } else if above(q, down) < 0.0 {
+            q = down;
+        } else {
+            return q;
+        }
+    }
+}
+
+/// The smallest `f64` greater than `x`.
+fn next_up(x: f64) -> f64 {
+    if x == 0.0 {
+        f64::from_bits(1)
+    } else if x > 0.0 {
+        f64::from_bits(x.to_bits() + 1)
+    } else {
+        f64::from_bits(x.to_bits() - 1)
+    }
+}
+
+fn direction<T>(x: T) -> RelativeDirection
+where
+    T: Num + PartialOrd,
+{
+    if x > T::zero() {
+        RelativeDirection::Left
+    } else if x < T::zero() {
+        RelativeDirection::Right
+    } else {
+        RelativeDirection::Collinear
+    }
+}
+
+fn abs<T>(x: T) -> T
+where
+    T: Scalar,
+{
+    if x < T::zero() {
+        T::zero() - x
+    } else {
+        x
+    }
+}
+
+/// The properties of a floating point type that exact arithmetic needs.
+struct Float<T> {
+    /// The largest power of two such that `1 + epsilon == 1`, which bounds the
+    /// relative error of rounding.
+    epsilon: T,
+
+    /// `2^ceil(p / 2) + 1`, where `p` is the number of bits of precision, used
+    /// to split numbers in half to multiply them exactly.
+    splitter: T,
+
+    /// Bounds on the error of the straightforward determinants, relative to
+    /// the magnitudes of their terms.
+    orientation_bound: T,
+    in_circle_bound: T,
+}
+
+impl<T> Float<T>
+where
+    T: Scalar,
+{
+    /// Get `T`'s floating point properties, or `None` if it is an integer type,
+    /// whose arithmetic is already exact.
+    fn new() -> Option<Float<T>> {
+        if T::IS_INTEGER {
+            return None;
+        }
+        let one = T::one();
+        let two = one + one;
+        let half = one / two;
+
+        // The usual suspects are IEEE 754 `f32`s and `f64`s, whose precisions
+        // we can guess from their size. Otherwise, find the precision the same
+        // way that Shewchuk's `exactinit` does, by halving until adding to one
+        // makes no difference.
+        let bits = match mem::size_of::<T>() {
+            4 => 24,
+            8 => 53,
+            _ => 0,
+        };
+        let epsilon = num_traits::pow(half, bits);
+        let (epsilon, bits) = if bits > 0 && one + epsilon == one && one + epsilon * two != one {
+            (epsilon, bits)
+        } else {
+            let mut epsilon = one;
+            let mut bits = 0;
+            loop {
+                epsilon *= half;
+                bits += 1;
+                if one + epsilon == one {
+                    break;
+                }
+                // Not a binary floating point type after all.
+                if bits > 1024 {
+                    return None;
+                }
+            }
+            (epsilon, bits)
+        };
+
+        let three = two + one;
+        let eight = two * two * two;
+        Some(Float {
+            epsilon,
+            splitter: num_traits::pow(two, bits.div_ceil(2)) + one,
+            orientation_bound: (three + eight * two * epsilon) * epsilon,
+            in_circle_bound: (eight + two + eight * three * two * two * epsilon) * epsilon,
+        })
+    }
+
+    /// Split `a` into two halves, each with at most half of the bits of
+    /// precision, so that their products with other halves are exact.
+    fn split(&self, a: T) -> (T, T) {
+        let c = self.splitter * a;
+        let big = c - a;
+        let high = c - big;
+        (high, a - high)
+    }
+
+    /// Multiply `a` by `b`, exactly.
+    fn two_product(&self, a: T, b: T) -> [T; 2] {
+        let x = a * b;
+        let (a_high, a_low) = self.split(a);
+        let (b_high, b_low) = self.split(b);
+        let error = x - a_high * b_high - a_low * b_high - a_high * b_low;
+        [a_low * b_low - error, x]
+    }
+
+    /// Multiply the expansion `e` by `b`, exactly.
+    fn scale_expansion(&self, e: &[T], b: T) -> Vec<T> {
+        let mut product = vec![];
+        for &x in e {
+            let [low, high] = self.two_product(x, b);
+            product = grow_expansion(&grow_expansion(&product, low), high);
+        }
+        product
+    }
+
+    /// Multiply two expansions, exactly.
+    fn product(&self, e: &[T], f: &[T]) -> Vec<T> {
+        f.iter().fold(vec![], |product, &b| {
+            sum_expansions(&product, &self.scale_expansion(e, b))
+        })
+    }
+}
+
+// An "expansion" is a sum of floating point numbers that don't overlap, sorted
+// by increasing magnitude, which can represent any sum, difference, or product
+// of floating point numbers exactly. The largest, last number has the sign of
+// the whole sum. See "Adaptive Precision Floating-Point Arithmetic and Fast
+// Robust Geometric Predicates" by Jonathan Richard Shewchuk.
+
+/// Add `a` and `b`, exactly.
+fn two_sum<T>(a: T, b: T) -> [T; 2]
+where
+    T: Scalar,
+{
+    let x = a + b;
+    let b_virtual = x - a;
+    let a_virtual = x - b_virtual;
+    [(a - a_virtual) + (b - b_virtual), x]
+}
+
+/// Add `a` and `b`, exactly, as an expansion.
+fn exact_sum<T>(a: T, b: T) -> Vec<T>
+where
+    T: Scalar,
+{
+    let [low, high] = two_sum(a, b);
+    if low == T::zero() {
+        vec![high]
+    } else {
+        vec![low, high]
+    }
+}
+
+/// Subtract `b` from `a`, exactly, as an expansion.
+fn exact_difference<T>(a: T, b: T) -> Vec<T>
+where
+    T: Scalar,
+{
+    exact_sum(a, T::zero() - b)
+}
+
+/// Add `b` to the expansion `e`, dropping any zeros from the result.
+fn grow_expansion<T>(e: &[T], b: T) -> Vec<T>
+where
+    T: Scalar,
+{
+    let mut sum = Vec::with_capacity(e.len() + 1);
+    let mut q = b;
+    for &x in e {
+        let [low, high] = two_sum(q, x);
+        if low != T::zero() {
+            sum.push(low);
+        }
+        q = high;
+    }
+    if q != T::zero() || sum.is_empty() {
+        sum.push(q);
+    }
+    sum
+}
+
+fn sum_expansions<T>(e: &[T], f: &[T]) -> Vec<T>
+where
+    T: Scalar,
+{
+    f.iter().fold(e.to_vec(), |sum, &b| grow_expansion(&sum, b))
+}
+
+fn negate<T>(e: &[T]) -> Vec<T>
+where
+    T: Scalar,
+{
+    e.iter().map(|&x| T::zero() - x).collect()
+}
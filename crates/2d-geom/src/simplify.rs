@@ -0,0 +1,283 @@
+use crate::{is_counter_clockwise, Polygon, Scalar};
+use euclid::Point2D;
+use fart_utils::NoMorePartial;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// An algorithm for simplifying polylines and polygons by removing vertices.
+///
+/// See `simplify_polyline` and `Polygon::simplify`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SimplifyAlgorithm {
+    /// The Ramer–Douglas–Peucker algorithm.
+    ///
+    /// Keeps the vertex furthest from the line between the first and last
+    /// vertices, and recursively simplifies either side of it, until every
+    /// removed vertex is within the tolerance of the simplified line. This
+    /// bounds how far the result strays from the original.
+    RamerDouglasPeucker,
+
+    /// The Visvalingam–Whyatt algorithm.
+    ///
+    /// Repeatedly removes the vertex whose triangle with its two neighbors has
+    /// the smallest area, until every remaining triangle's area is at least
+    /// the tolerance squared. This tends to keep the overall shape better than
+    /// Ramer–Douglas–Peucker does, at the cost of not bounding the distance to
+    /// the original.
+    VisvalingamWhyatt,
+}
+
+/// Simplify the given polyline by removing vertices that contribute little to
+/// its shape, using the given algorithm. How much is "little" is determined by
+/// `tolerance`: see `SimplifyAlgorithm` for details.
+///
+/// The polyline's first and last vertices are always kept, and so a closed
+/// polyline, whose first and last vertices are the same, stays closed. The
+/// kept vertices are not moved.
+///
+/// Polylines with fewer than three vertices are returned as they are.
+///
+/// ```
+/// use euclid::{point2, UnknownUnit};
+/// use fart_2d_geom::{simplify_polyline, SimplifyAlgorithm};
+///
+/// // A zig-zag along the x axis that wobbles by 0.1 either side of it, and
+/// // then turns up at the end.
+/// let mut zigzag = vec![];
+/// for x in 0..100 {
+///     let y = if x % 2 == 0 { 0.1 } else { -0.1 };
+///     zigzag.push(point2::<f64, UnknownUnit>(x as f64, y));
+/// }
+/// zigzag.push(point2(99.0, 10.0));
+///
+/// let simple = simplify_polyline(&zigzag, 0.5, SimplifyAlgorithm::RamerDouglasPeucker);
+/// assert_eq!(simple, vec![point2(0.0, 0.1), point2(99.0, -0.1), point2(99.0, 10.0)]);
+/// ```
+pub fn simplify_polyline<T, U>(
+    polyline: &[Point2D<T, U>],
+    tolerance: T,
+    algorithm: SimplifyAlgorithm,
+) -> Vec<Point2D<T, U>>
+where
+    T: Scalar,
+{
+    if polyline.len() < 3 {
+        return polyline.to_vec();
+    }
+
+    let points = to_f64(polyline);
+    let tolerance = tolerance.to_float();
+    let keep = match algorithm {
+        SimplifyAlgorithm::RamerDouglasPeucker => {
+            let mut keep = vec![false; points.len()];
+            ramer_douglas_peucker(&points, 0, points.len() - 1, tolerance, &mut keep);
+            keep
+        }
+        SimplifyAlgorithm::VisvalingamWhyatt => {
+            visvalingam_whyatt(&points, false, 2, tolerance * tolerance)
+        }
+    };
+
+    kept(polyline, &keep)
+}
+
+impl<T, U> Polygon<T, U>
+where
+    T: Scalar,
+{
+    /// Simplify this polygon by removing vertices that contribute little to
+    /// its shape, using the given algorithm. How much is "little" is
+    /// determined by `tolerance`: see `SimplifyAlgorithm` for details.
+    ///
+    /// The kept vertices are not moved, and at least three of them are always
+    /// kept. If simplifying would turn the polygon inside out, which can only
+    /// happen when the tolerance is about as large as the polygon itself, it
+    /// is returned unsimplified.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::{Polygon, SimplifyAlgorithm};
+    ///
+    /// // A square with an extra vertex in the middle of each side, pushed out
+    /// // slightly.
+    /// let square = Polygon::<i32, UnknownUnit>::new(vec![
+    ///     point2(0, 0),
+    ///     point2(50, -1),
+    ///     point2(100, 0),
+    ///     point2(101, 50),
+    ///     point2(100, 100),
+    ///     point2(50, 101),
+    ///     point2(0, 100),
+    ///     point2(-1, 50),
+    /// ]);
+    ///
+    /// for &(tolerance, algorithm) in &[
+    ///     (2, SimplifyAlgorithm::RamerDouglasPeucker),
+    ///     (10, SimplifyAlgorithm::VisvalingamWhyatt),
+    /// ] {
+    ///     let simple = square.simplify(tolerance, algorithm);
+    ///     assert_eq!(simple.len(), 4);
+    ///     assert_eq!(simple.area(), 100 * 100);
+    /// }
+    /// ```
+    pub fn simplify(&self, tolerance: T, algorithm: SimplifyAlgorithm) -> Polygon<T, U> {
+        let points = to_f64(self.vertices());
+        let tolerance = tolerance.to_float();
+        let n = points.len();
+        let keep = match algorithm {
+            SimplifyAlgorithm::RamerDouglasPeucker => {
+                // Split the ring into two polylines, between the first vertex
+                // and the one furthest from it, and simplify each of them.
+                let far = (1..n)
+                    .max_by_key(|&i| NoMorePartial((points[i] - points[0]).square_length()))
+                    .unwrap();
+                let mut ring = points.clone();
+                ring.push(points[0]);
+                let mut keep = vec![false; n + 1];
+                ramer_douglas_peucker(&ring, 0, far, tolerance, &mut keep);
+                ramer_douglas_peucker(&ring, far, n, tolerance, &mut keep);
+                keep.truncate(n);
+
+                // If both sides were simplified away, keep whichever vertex is
+                // furthest from the line between the two that are left.
+                if keep.iter().filter(|&&k| k).count() < 3 {
+                    let from_line = |i: usize| distance(points[i], points[0], points[far]);
+                    let third = (1..n)
+                        .filter(|&i| i != far)
+                        .max_by_key(|&i| NoMorePartial(from_line(i)))
+                        .unwrap();
+                    keep[third] = true;
+                }
+                keep
+            }
+            SimplifyAlgorithm::VisvalingamWhyatt => {
+                visvalingam_whyatt(&points, true, 3, tolerance * tolerance)
+            }
+        };
+
+        let vertices = kept(self.vertices(), &keep);
+        if is_counter_clockwise(&vertices) {
+            Polygon::new(vertices)
+        } else {
+            Polygon::new(self.vertices().to_vec())
+        }
+    }
+}
+
+fn to_f64<T, U>(points: &[Point2D<T, U>]) -> Vec<Point2D<f64, U>>
+where
+    T: Scalar,
+{
+    points.iter().map(|p| p.cast()).collect()
+}
+
+fn kept<T, U>(points: &[Point2D<T, U>], keep: &[bool]) -> Vec<Point2D<T, U>>
+where
+    T: Copy,
+{
+    points
+        .iter()
+        .zip(keep)
+        .filter(|(_, &k)| k)
+        .map(|(p, _)| *p)
+        .collect()
+}
+
+/// The distance from `p` to the line segment from `a` to `b`.
+fn distance<U>(p: Point2D<f64, U>, a: Point2D<f64, U>, b: Point2D<f64, U>) -> f64 {
+    let ab = b - a;
+    let length = ab.square_length();
+    if length == 0.0 {
+        return (p - a).length();
+    }
+    let t = ((p - a).dot(ab) / length).clamp(0.0, 1.0);
+    (p - (a + ab * t)).length()
+}
+
+/// Mark the vertices from `first` to `last`, inclusive, that Ramer–Douglas–
+/// Peucker keeps.
+fn ramer_douglas_peucker<U>(
+    points: &[Point2D<f64, U>],
+    first: usize,
+    last: usize,
+    tolerance: f64,
+    keep: &mut [bool],
+) {
+    keep[first] = true;
+    keep[last] = true;
+
+    // Recursing on long, noisy polylines could overflow the stack, so keep our
+    // own stack of spans to simplify.
+    let mut spans = vec![(first, last)];
+    while let Some((first, last)) = spans.pop() {
+        let furthest = (first + 1..last)
+            .map(|i| (i, distance(points[i], points[first], points[last])))
+            .max_by_key(|&(_, d)| NoMorePartial(d));
+        if let Some((i, d)) = furthest {
+            if d > tolerance {
+                keep[i] = true;
+                spans.push((first, i));
+                spans.push((i, last));
+            }
+        }
+    }
+}
+
+/// Find which vertices Visvalingam–Whyatt keeps, removing vertices until every
+/// remaining triangle's area is at least `min_area`, or there are only
+/// `min_len` vertices left.
+///
+/// If `closed`, then the first and last vertices are neighbors, and may be
+/// removed just like any other. Otherwise, they are always kept.
+fn visvalingam_whyatt<U>(
+    points: &[Point2D<f64, U>],
+    closed: bool,
+    min_len: usize,
+    min_area: f64,
+) -> Vec<bool> {
+    let n = points.len();
+    let mut keep = vec![true; n];
+    let mut prev: Vec<usize> = (0..n).map(|i| (i + n - 1) % n).collect();
+    let mut next: Vec<usize> = (0..n).map(|i| (i + 1) % n).collect();
+
+    let area = |prev: &[usize], next: &[usize], i: usize| {
+        let (a, b, c) = (points[prev[i]], points[i], points[next[i]]);
+        ((b - a).cross(c - a) / 2.0).abs()
+    };
+    let removable = |i: usize| closed || (i != 0 && i != n - 1);
+
+    // A min-heap of each vertex's triangle's area. When a vertex is removed,
+    // its neighbors' areas change, and their old entries in the heap go stale.
+    // Each vertex's current area is tracked, so that stale entries can be
+    // skipped over.
+    let mut areas = vec![0.0; n];
+    let mut heap = BinaryHeap::new();
+    for i in (0..n).filter(|&i| removable(i)) {
+        areas[i] = area(&prev, &next, i);
+        heap.push(Reverse((NoMorePartial(areas[i]), i)));
+    }
+
+    let mut len = n;
+    while let Some(Reverse((NoMorePartial(a), i))) = heap.pop() {
+        if len <= min_len || a >= min_area {
+            break;
+        }
+        if !keep[i] || a != areas[i] {
+            continue;
+        }
+
+        keep[i] = false;
+        len -= 1;
+        let (p, q) = (prev[i], next[i]);
+        next[p] = q;
+        prev[q] = p;
+        for &j in &[p, q] {
+            if removable(j) {
+                areas[j] = area(&prev, &next, j);
+                heap.push(Reverse((NoMorePartial(areas[j]), j)));
+            }
+        }
+    }
+
+    keep
+}
@@ -0,0 +1,524 @@
+//! Straight skeletons and medial axes of polygons, for roof-like shading,
+//! inward stripes, and the "rivers" running down the middle of shapes.
+
+use crate::{line, DelaunayTriangulation, Line, Polygon};
+use euclid::{Point2D, Vector2D};
+use fart_aabb::ToAabb;
+use std::collections::BTreeMap;
+
+/// A line of a polygon's straight skeleton or medial axis, along with how far
+/// each of its ends is from the polygon's boundary.
+///
+/// See `Polygon::straight_skeleton` and `Polygon::medial_axis`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SkeletonLine<U> {
+    /// The line.
+    pub line: Line<f64, U>,
+
+    /// The offset at `line.a`.
+    pub a_offset: f64,
+
+    /// The offset at `line.b`.
+    pub b_offset: f64,
+}
+
+impl<U> Polygon<f64, U> {
+    /// Compute this polygon's straight skeleton.
+    ///
+    /// Imagine every edge moving inwards at the same speed, parallel to
+    /// itself, with the polygon's corners sliding along between them. The
+    /// straight skeleton is the set of paths the corners trace out until the
+    /// polygon shrinks away to nothing. Each line's offsets are how far the
+    /// edges had moved when its corner passed through its ends: its nodes'
+    /// times. Every vertex of the polygon starts a line with an offset of
+    /// zero, and lines continue from the nodes where corners meet edges or
+    /// each other, like the ridges and valleys of a roof.
+    ///
+    /// The lines point inwards, from the smaller offset to the larger. The
+    /// polygon should be simple. This is an *O(n<sup>2</sup> r)* algorithm,
+    /// where *r* is the number of reflex vertices.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::Polygon;
+    ///
+    /// let rectangle = Polygon::<f64, UnknownUnit>::new(vec![
+    ///     point2(0.0, 0.0),
+    ///     point2(4.0, 0.0),
+    ///     point2(4.0, 2.0),
+    ///     point2(0.0, 2.0),
+    /// ]);
+    ///
+    /// // A line from each corner, and the ridge that they meet at.
+    /// let skeleton = rectangle.straight_skeleton();
+    /// assert_eq!(skeleton.len(), 5);
+    /// let ridge = skeleton
+    ///     .iter()
+    ///     .find(|s| s.a_offset == 1.0 && s.b_offset == 1.0)
+    ///     .unwrap();
+    /// let mut ends = [ridge.line.a, ridge.line.b];
+    /// ends.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap());
+    /// assert_eq!(ends, [point2(1.0, 1.0), point2(3.0, 1.0)]);
+    /// ```
+    pub fn straight_skeleton(&self) -> Vec<SkeletonLine<U>> {
+        let mut vertices = self.vertices().to_vec();
+        vertices.dedup();
+        while vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+        if vertices.len() < 3 {
+            return vec![];
+        }
+
+        let aabb = self.to_aabb();
+        let size = aabb.width().max(aabb.height());
+        let n = vertices.len();
+        let directions: Vec<Vector2D<f64, U>> = (0..n)
+            .map(|i| (vertices[(i + 1) % n] - vertices[i]).normalize())
+            .collect();
+        let ring = vertices
+            .iter()
+            .enumerate()
+            .map(|(edge, &point)| Vertex {
+                point,
+                node: point,
+                node_offset: 0.0,
+                edge,
+            })
+            .collect();
+
+        let mut wavefront = Wavefront {
+            normals: directions
+                .iter()
+                .map(|d| Vector2D::new(-d.y, d.x))
+                .collect(),
+            directions,
+            rings: vec![ring],
+            offset: 0.0,
+            epsilon: size * 1e-9,
+            lines: vec![],
+        };
+
+        // Every event removes a vertex or splits a ring at a reflex vertex, so
+        // this is plenty, unless rounding has gone very wrong.
+        for _ in 0..4 * n * n + 16 {
+            wavefront.tidy();
+            match wavefront.next_event() {
+                None => break,
+                Some((dt, event)) => {
+                    wavefront.advance(dt);
+                    wavefront.apply(event);
+                }
+            }
+        }
+        while let Some(ring) = wavefront.rings.pop() {
+            wavefront.collapse(ring);
+        }
+        wavefront.lines
+    }
+
+    /// Approximate this polygon's medial axis: the points inside it that are
+    /// equally close to two or more points on its boundary.
+    ///
+    /// The boundary is sampled at most `spacing` apart, and the medial axis is
+    /// approximated by the edges of those samples' Voronoi diagram that are
+    /// inside the polygon, leaving out the edges between samples that are
+    /// close together along the boundary. That trims the branches that run
+    /// into convex corners back by about `spacing`. Each line's offsets are
+    /// the distances from its ends to the nearest samples, which is the radius
+    /// of the largest circle around them that fits in the polygon, give or
+    /// take `spacing`.
+    ///
+    /// Unlike the straight skeleton, the medial axis curves around reflex
+    /// vertices, and so it is made up of many short lines.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `spacing` is not positive.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::Polygon;
+    ///
+    /// let rectangle = Polygon::<f64, UnknownUnit>::new(vec![
+    ///     point2(0.0, 0.0),
+    ///     point2(4.0, 0.0),
+    ///     point2(4.0, 2.0),
+    ///     point2(0.0, 2.0),
+    /// ]);
+    ///
+    /// // The middle of the rectangle is on its medial axis, one away from
+    /// // its long edges.
+    /// let axis = rectangle.medial_axis(0.1);
+    /// assert!(axis.iter().all(|s| rectangle.contains_point(s.line.a)));
+    /// assert!(axis.iter().any(|s| {
+    ///     (s.line.a - point2(2.0, 1.0)).length() < 0.1 && (s.a_offset - 1.0).abs() < 0.01
+    /// }));
+    /// ```
+    pub fn medial_axis(&self, spacing: f64) -> Vec<SkeletonLine<U>> {
+        assert!(spacing > 0.0);
+
+        // Sample the boundary, remembering how far along it each sample is.
+        let mut samples = vec![];
+        let mut along = vec![];
+        let mut perimeter = 0.0;
+        for edge in self.edges() {
+            let length = edge.length();
+            let count = (length / spacing).ceil().max(1.0) as usize;
+            for i in 0..count {
+                let t = i as f64 / count as f64;
+                samples.push(edge.a.lerp(edge.b, t));
+                along.push(perimeter + length * t);
+            }
+            perimeter += length;
+        }
+
+        let triangulation = DelaunayTriangulation::new(samples);
+        let points = triangulation.points();
+        let mut centers = vec![];
+        let mut triangles_by_edge = BTreeMap::new();
+        for [a, b, c] in triangulation.triangles() {
+            let (ab, ac) = (points[b] - points[a], points[c] - points[a]);
+            let d = 2.0 * ab.cross(ac);
+            if d == 0.0 {
+                centers.push(None);
+            } else {
+                let (ab2, ac2) = (ab.square_length(), ac.square_length());
+                let center =
+                    points[a] + Vector2D::new(ac.y * ab2 - ab.y * ac2, ab.x * ac2 - ac.x * ab2) / d;
+                centers.push(if self.contains_point(center) {
+                    Some((center, (points[a] - center).length()))
+                } else {
+                    None
+                });
+            }
+            let t = centers.len() - 1;
+            for &(i, j) in &[(a, b), (b, c), (c, a)] {
+                triangles_by_edge
+                    .entry((i.min(j), i.max(j)))
+                    .or_insert_with(Vec::new)
+                    .push(t);
+            }
+        }
+
+        let mut lines = vec![];
+        for ((i, j), triangles) in triangles_by_edge {
+            let gap = (along[i] - along[j]).abs();
+            if triangles.len() != 2 || gap.min(perimeter - gap) <= 2.0 * spacing {
+                continue;
+            }
+            if let (Some((a, a_offset)), Some((b, b_offset))) =
+                (centers[triangles[0]], centers[triangles[1]])
+            {
+                if a != b {
+                    lines.push(SkeletonLine {
+                        line: line(a, b),
+                        a_offset,
+                        b_offset,
+                    });
+                }
+            }
+        }
+        lines
+    }
+}
+
+/// A corner of the shrinking polygon, in the middle of computing a straight
+/// skeleton.
+struct Vertex<U> {
+    /// Where the corner is now.
+    point: Point2D<f64, U>,
+    /// The skeleton node that the corner started from, and its offset.
+    node: Point2D<f64, U>,
+    node_offset: f64,
+    /// The original edge whose line the corner's outgoing edge is on. Its
+    /// incoming edge is the previous corner's outgoing edge.
+    edge: usize,
+}
+
+impl<U> Clone for Vertex<U> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<U> Copy for Vertex<U> {}
+
+/// Something that changes the shape of the shrinking polygon.
+#[derive(Clone, Copy, Debug)]
+enum Event {
+    /// The edge from the given vertex in the given ring shrinks away.
+    Edge { ring: usize, vertex: usize },
+    /// The given reflex vertex runs into the edge from another vertex in the
+    /// same ring, and splits the ring in two.
+    Split {
+        ring: usize,
+        vertex: usize,
+        edge: usize,
+    },
+}
+
+/// The shrinking polygon, which may have split into many rings.
+struct Wavefront<U> {
+    directions: Vec<Vector2D<f64, U>>,
+    normals: Vec<Vector2D<f64, U>>,
+    rings: Vec<Vec<Vertex<U>>>,
+    offset: f64,
+    epsilon: f64,
+    lines: Vec<SkeletonLine<U>>,
+}
+
+impl<U> Wavefront<U> {
+    fn emit(&mut self, a: Point2D<f64, U>, a_offset: f64, b: Point2D<f64, U>, b_offset: f64) {
+        if (b - a).length() > self.epsilon {
+            self.lines.push(SkeletonLine {
+                line: line(a, b),
+                a_offset,
+                b_offset,
+            });
+        }
+    }
+
+    /// Finish the line that the given vertex has traced out, at `point`.
+    fn retire(&mut self, v: Vertex<U>, point: Point2D<f64, U>) {
+        self.emit(v.node, v.node_offset, point, self.offset);
+    }
+
+    /// The velocity of the `k`th vertex of the ring, which keeps it on both
+    /// of its edges' lines as they move inwards at unit speed. `None` if its
+    /// edges point in opposite directions, and it is the tip of a spike with
+    /// no width.
+    fn velocity(&self, ring: &[Vertex<U>], k: usize) -> Option<Vector2D<f64, U>> {
+        let incoming = ring[(k + ring.len() - 1) % ring.len()].edge;
+        let (n1, n2) = (self.normals[incoming], self.normals[ring[k].edge]);
+        // This is `(n1 + n2) / (1 + n1 . n2)`, without the cancellation that
+        // loses precision at very sharp corners.
+        let bisector = n1 + n2;
+        let denominator = bisector.square_length() / 2.0;
+        if denominator < 1e-16 {
+            None
+        } else {
+            Some(bisector / denominator)
+        }
+    }
+
+    fn is_reflex(&self, ring: &[Vertex<U>], k: usize) -> bool {
+        let incoming = ring[(k + ring.len() - 1) % ring.len()].edge;
+        self.directions[incoming].cross(self.directions[ring[k].edge]) < 0.0
+    }
+
+    /// Deal with the rings that have shrunk to nothing, and with spikes.
+    fn tidy(&mut self) {
+        let mut r = 0;
+        while r < self.rings.len() {
+            let ring = &self.rings[r];
+            let n = ring.len();
+            let (mut area, mut perimeter) = (0.0, 0.0);
+            for k in 0..n {
+                let (a, b) = (ring[k].point, ring[(k + 1) % n].point);
+                area += a.to_vector().cross(b.to_vector());
+                perimeter += (b - a).length();
+            }
+            if n < 3 || area / 2.0 <= self.epsilon * perimeter {
+                let ring = self.rings.swap_remove(r);
+                self.collapse(ring);
+                continue;
+            }
+
+            match (0..n).find(|&k| self.velocity(&self.rings[r], k).is_none()) {
+                Some(k) => self.remove_spike(r, k),
+                None => r += 1,
+            }
+        }
+    }
+
+    /// Retract a spike with no width, back to the nearer of its tip's
+    /// neighbors. The other neighbor carries on along the spike's line.
+    fn remove_spike(&mut self, r: usize, k: usize) {
+        let n = self.rings[r].len();
+        let (p, q) = ((k + n - 1) % n, (k + 1) % n);
+        let tip = self.rings[r][k];
+        let (before, after) = (self.rings[r][p], self.rings[r][q]);
+        self.retire(tip, tip.point);
+
+        let nearer = if (before.point - tip.point).square_length()
+            <= (after.point - tip.point).square_length()
+        {
+            // The corner before the tip now goes straight to the one after.
+            self.rings[r][p].edge = tip.edge;
+            p
+        } else {
+            q
+        };
+        let v = self.rings[r][nearer];
+        let offset = self.offset;
+        self.emit(tip.point, offset, v.point, offset);
+        self.retire(v, v.point);
+        self.rings[r][nearer].node = v.point;
+        self.rings[r][nearer].node_offset = offset;
+        self.rings[r].remove(k);
+    }
+
+    /// Finish off a ring that has shrunk to nothing, or to a tree of lines
+    /// with no area.
+    fn collapse(&mut self, ring: Vec<Vertex<U>>) {
+        for &v in &ring {
+            self.retire(v, v.point);
+        }
+
+        // The ring goes up and down each of the lines it has become, so only
+        // keep the first time around.
+        let start = self.lines.len();
+        let offset = self.offset;
+        for k in 0..ring.len() {
+            let (a, b) = (ring[k].point, ring[(k + 1) % ring.len()].point);
+            let middle = a.lerp(b, 0.5);
+            let seen = self.lines[start..]
+                .iter()
+                .any(|s| distance_to_line(middle, &s.line) <= self.epsilon);
+            if !seen {
+                self.emit(a, offset, b, offset);
+            }
+        }
+    }
+
+    /// Find the next event, and how long until it happens.
+    fn next_event(&self) -> Option<(f64, Event)> {
+        let mut next: Option<(f64, Event)> = None;
+        let mut consider = |dt: f64, event| {
+            if next.is_none_or(|(t, _)| dt < t) {
+                next = Some((dt, event));
+            }
+        };
+
+        for (r, ring) in self.rings.iter().enumerate() {
+            let n = ring.len();
+            let velocities: Vec<_> = (0..n).map(|k| self.velocity(ring, k).unwrap()).collect();
+
+            for k in 0..n {
+                let j = (k + 1) % n;
+                let d = self.directions[ring[k].edge];
+                let length = (ring[j].point - ring[k].point).dot(d);
+                let rate = (velocities[j] - velocities[k]).dot(d);
+                if rate < 0.0 {
+                    consider(length.max(0.0) / -rate, Event::Edge { ring: r, vertex: k });
+                }
+            }
+
+            for k in (0..n).filter(|&k| self.is_reflex(ring, k)) {
+                let (p, v) = (ring[k].point, velocities[k]);
+                for e in (0..n).filter(|&e| e != k && (e + 1) % n != k) {
+                    let f = (e + 1) % n;
+                    let normal = self.normals[ring[e].edge];
+                    let distance = (p - ring[e].point).dot(normal);
+                    let closing = 1.0 - v.dot(normal);
+                    if closing <= 0.0 || distance < -self.epsilon {
+                        continue;
+                    }
+                    let dt = distance.max(0.0) / closing;
+
+                    // It has to hit the edge itself, not just its line.
+                    let d = self.directions[ring[e].edge];
+                    let a = ring[e].point + velocities[e] * dt;
+                    let b = ring[f].point + velocities[f] * dt;
+                    let along = (p + v * dt - a).dot(d);
+                    if along >= -self.epsilon && along <= (b - a).dot(d) + self.epsilon {
+                        consider(
+                            dt,
+                            Event::Split {
+                                ring: r,
+                                vertex: k,
+                                edge: e,
+                            },
+                        );
+                    }
+                }
+            }
+        }
+        next
+    }
+
+    /// Move every vertex along for `dt`.
+    fn advance(&mut self, dt: f64) {
+        for r in 0..self.rings.len() {
+            let velocities: Vec<_> = (0..self.rings[r].len())
+                .map(|k| self.velocity(&self.rings[r], k).unwrap())
+                .collect();
+            for (v, velocity) in self.rings[r].iter_mut().zip(velocities) {
+                v.point += velocity * dt;
+            }
+        }
+        self.offset += dt;
+    }
+
+    fn apply(&mut self, event: Event) {
+        let offset = self.offset;
+        match event {
+            Event::Edge { ring: r, .. } if self.rings[r].len() == 3 => {
+                // A triangle shrinks towards a single point, where all of its
+                // edges vanish at once, but rounding can keep its corners
+                // from quite meeting there.
+                let ring = self.rings.swap_remove(r);
+                let points: Vec<_> = ring.iter().map(|v| v.point).collect();
+                let point = crate::center(&points);
+                for v in ring {
+                    self.retire(v, point);
+                }
+            }
+            Event::Edge { ring: r, vertex: k } => {
+                let j = (k + 1) % self.rings[r].len();
+                let (a, b) = (self.rings[r][k], self.rings[r][j]);
+                let point = a.point.lerp(b.point, 0.5);
+                self.retire(a, point);
+                self.retire(b, point);
+                self.rings[r][k] = Vertex {
+                    point,
+                    node: point,
+                    node_offset: offset,
+                    edge: b.edge,
+                };
+                self.rings[r].remove(j);
+            }
+            Event::Split {
+                ring: r,
+                vertex: k,
+                edge: e,
+            } => {
+                let ring = self.rings.swap_remove(r);
+                let n = ring.len();
+                let v = ring[k];
+                self.retire(v, v.point);
+                let corner = |edge| Vertex {
+                    point: v.point,
+                    node: v.point,
+                    node_offset: offset,
+                    edge,
+                };
+
+                // One ring goes from the split vertex along the rest of the
+                // edge it hit, and back around to it; the other goes from the
+                // start of that edge to the split vertex, and on from there.
+                let mut first = vec![corner(ring[e].edge)];
+                let mut i = (e + 1) % n;
+                while i != k {
+                    first.push(ring[i]);
+                    i = (i + 1) % n;
+                }
+                let mut second = vec![corner(v.edge)];
+                let mut i = (k + 1) % n;
+                while i != (e + 1) % n {
+                    second.push(ring[i]);
+                    i = (i + 1) % n;
+                }
+                self.rings.push(first);
+                self.rings.push(second);
+            }
+        }
+    }
+}
+
+fn distance_to_line<U>(p: Point2D<f64, U>, l: &Line<f64, U>) -> f64 {
+    let d = l.b - l.a;
+    let t = ((p - l.a).dot(d) / d.square_length()).clamp(0.0, 1.0);
+    (p - l.a.lerp(l.b, t)).length()
+}
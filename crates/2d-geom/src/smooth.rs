@@ -0,0 +1,237 @@
+use crate::{CubicBezier, Polygon};
+use euclid::Point2D;
+
+/// How a Catmull-Rom spline spaces out its knots, which determines how tightly
+/// it follows the points it passes through.
+///
+/// See `catmull_rom`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CatmullRom {
+    /// Evenly spaced knots. This is the classic Catmull-Rom spline, which can
+    /// overshoot, and form cusps and loops, where the points are unevenly
+    /// spaced.
+    Uniform,
+
+    /// Knots spaced by the square root of the distance between points. This
+    /// never forms cusps or loops within a curve, and follows the points more
+    /// tightly.
+    Centripetal,
+
+    /// Knots spaced by the distance between points. This follows the points
+    /// most tightly of all, but bulges out more around sharp turns.
+    Chordal,
+}
+
+impl CatmullRom {
+    /// The exponent applied to the distance between points to get the spacing
+    /// between their knots.
+    fn alpha(self) -> f64 {
+        match self {
+            CatmullRom::Uniform => 0.0,
+            CatmullRom::Centripetal => 0.5,
+            CatmullRom::Chordal => 1.0,
+        }
+    }
+}
+
+/// Get the cubic Bézier curves that make up the Catmull-Rom spline through the
+/// given points.
+///
+/// The spline passes through every point, with one curve between each pair
+/// of consecutive points, and is smooth where the curves meet. If `closed`,
+/// then there is also a curve from the last point back to the first, and the
+/// spline is smooth there, too. Otherwise, the spline starts at the first
+/// point, heading towards the second, and ends at the last point, heading
+/// away from the second to last.
+///
+/// Consecutive duplicate points are skipped over. If fewer than two points
+/// remain, there are no curves, and if only two remain, the spline is never
+/// closed.
+///
+/// ```
+/// use euclid::{point2, UnknownUnit};
+/// use fart_2d_geom::{catmull_rom, CatmullRom};
+///
+/// let points = vec![
+///     point2::<f64, UnknownUnit>(0.0, 0.0),
+///     point2(10.0, 10.0),
+///     point2(20.0, 0.0),
+///     point2(30.0, 10.0),
+/// ];
+///
+/// let curves = catmull_rom(&points, false, CatmullRom::Centripetal);
+/// assert_eq!(curves.len(), 3);
+/// assert_eq!(curves[1].start, points[1]);
+/// assert_eq!(curves[1].end, points[2]);
+///
+/// let curves = catmull_rom(&points, true, CatmullRom::Centripetal);
+/// assert_eq!(curves.len(), 4);
+/// assert_eq!(curves[3].end, points[0]);
+/// ```
+pub fn catmull_rom<U>(
+    points: &[Point2D<f64, U>],
+    closed: bool,
+    parameterization: CatmullRom,
+) -> Vec<CubicBezier<f64, U>> {
+    let mut points = points.to_vec();
+    points.dedup();
+    if closed && points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+
+    let n = points.len();
+    if n < 2 {
+        return vec![];
+    }
+    // Two points can't enclose anything, so don't double back between them.
+    let closed = closed && n > 2;
+
+    // The points before and after each curve's end points. An open spline's
+    // ends are extended by reflecting their neighbors across them.
+    let before = |i: usize| {
+        if i > 0 {
+            points[i - 1]
+        } else if closed {
+            points[n - 1]
+        } else {
+            points[0] + (points[0] - points[1])
+        }
+    };
+    let after = |i: usize| {
+        if i + 1 < n {
+            points[i + 1]
+        } else if closed {
+            points[0]
+        } else {
+            points[n - 1] + (points[n - 1] - points[n - 2])
+        }
+    };
+
+    let alpha = parameterization.alpha();
+    let segments = if closed { n } else { n - 1 };
+    (0..segments)
+        .map(|i| {
+            let (p0, p1) = (before(i), points[i]);
+            let (p2, p3) = (points[(i + 1) % n], after((i + 1) % n));
+
+            // The spacing between each pair of consecutive points' knots.
+            let d1 = (p1 - p0).length().powf(alpha);
+            let d2 = (p2 - p1).length().powf(alpha);
+            let d3 = (p3 - p2).length().powf(alpha);
+
+            // See "On the Parameterization of Catmull-Rom Curves" by Yuksel,
+            // Schaefer, and Keyser.
+            let control_1 = if d1 == 0.0 {
+                p1
+            } else {
+                let scale = 3.0 * d1 * (d1 + d2);
+                let p = (p2.to_vector() * (d1 * d1) - p0.to_vector() * (d2 * d2)
+                    + p1.to_vector() * (2.0 * d1 * d1 + 3.0 * d1 * d2 + d2 * d2))
+                    / scale;
+                p.to_point()
+            };
+            let control_2 = if d3 == 0.0 {
+                p2
+            } else {
+                let scale = 3.0 * d3 * (d3 + d2);
+                let p = (p1.to_vector() * (d3 * d3) - p3.to_vector() * (d2 * d2)
+                    + p2.to_vector() * (2.0 * d3 * d3 + 3.0 * d3 * d2 + d2 * d2))
+                    / scale;
+                p.to_point()
+            };
+
+            CubicBezier::new(p1, control_1, control_2, p2)
+        })
+        .collect()
+}
+
+/// Smooth the given polyline with Chaikin's corner cutting algorithm.
+///
+/// Each iteration cuts every corner off, replacing each line segment with its
+/// middle half. The polyline converges towards a quadratic B-spline, roughly
+/// doubling its number of vertices with each iteration.
+///
+/// The polyline's first and last vertices are kept in place. Polylines with
+/// fewer than three vertices have no corners to cut, and are returned as they
+/// are.
+///
+/// ```
+/// use euclid::{point2, UnknownUnit};
+/// use fart_2d_geom::chaikin;
+///
+/// let corner = vec![
+///     point2::<f64, UnknownUnit>(0.0, 0.0),
+///     point2(4.0, 0.0),
+///     point2(4.0, 4.0),
+/// ];
+///
+/// let smooth = chaikin(&corner, 1);
+/// assert_eq!(smooth, vec![
+///     point2(0.0, 0.0),
+///     point2(3.0, 0.0),
+///     point2(4.0, 1.0),
+///     point2(4.0, 4.0),
+/// ]);
+/// ```
+pub fn chaikin<U>(polyline: &[Point2D<f64, U>], iterations: usize) -> Vec<Point2D<f64, U>> {
+    let mut polyline = polyline.to_vec();
+    if polyline.len() < 3 {
+        return polyline;
+    }
+
+    for _ in 0..iterations {
+        let n = polyline.len();
+        let mut smooth = Vec::with_capacity(2 * n);
+        smooth.push(polyline[0]);
+        for (i, w) in polyline.windows(2).enumerate() {
+            if i > 0 {
+                smooth.push(w[0].lerp(w[1], 0.25));
+            }
+            if i < n - 2 {
+                smooth.push(w[0].lerp(w[1], 0.75));
+            }
+        }
+        smooth.push(polyline[n - 1]);
+        polyline = smooth;
+    }
+    polyline
+}
+
+impl<U> Polygon<f64, U> {
+    /// Smooth this polygon with Chaikin's corner cutting algorithm.
+    ///
+    /// Each iteration cuts every corner off, replacing each edge with its
+    /// middle half, and doubling the number of vertices. The polygon
+    /// converges towards a closed quadratic B-spline.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_2d_geom::Polygon;
+    ///
+    /// let square = Polygon::<f64, UnknownUnit>::new(vec![
+    ///     point2(0.0, 0.0),
+    ///     point2(4.0, 0.0),
+    ///     point2(4.0, 4.0),
+    ///     point2(0.0, 4.0),
+    /// ]);
+    ///
+    /// // One iteration turns the square into an octagon, by cutting off
+    /// // triangles with area 0.5 from each corner.
+    /// let octagon = square.chaikin(1);
+    /// assert_eq!(octagon.len(), 8);
+    /// assert_eq!(octagon.area(), 16.0 - 4.0 * 0.5);
+    /// ```
+    pub fn chaikin(&self, iterations: usize) -> Polygon<f64, U> {
+        let mut vertices = self.vertices().to_vec();
+        for _ in 0..iterations {
+            let n = vertices.len();
+            vertices = (0..n)
+                .flat_map(|i| {
+                    let (a, b) = (vertices[i], vertices[(i + 1) % n]);
+                    vec![a.lerp(b, 0.25), a.lerp(b, 0.75)]
+                })
+                .collect();
+        }
+        Polygon::new(vertices)
+    }
+}
@@ -0,0 +1,347 @@
+//! Finding all of the intersections among a set of line segments with a
+//! Bentley–Ottmann sweep.
+
+use crate::predicates::{cross, crossing_point};
+use crate::{orientation, Line, RelativeDirection};
+use euclid::{point2, Point2D};
+use fart_utils::NoMorePartial;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+/// A point where two or more line segments meet. Returned by
+/// `segment_intersections`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SegmentIntersection<U> {
+    /// Where the line segments meet.
+    pub point: Point2D<f64, U>,
+
+    /// The indices of the line segments that meet here, in increasing order.
+    pub segments: Vec<usize>,
+}
+
+/// Find every point where two or more of the given line segments meet.
+///
+/// This is a Bentley–Ottmann sweep, which only tests line segments for
+/// intersection when they are next to each other along the sweep line, rather
+/// than testing every pair of them. Its running time grows with the number of
+/// line segments and intersections, not the number of pairs. The intersections
+/// are returned in order of increasing `x`, and then increasing `y`.
+///
+/// Line segments that share an endpoint, or where one's endpoint touches the
+/// other, intersect there. Collinear line segments that overlap intersect at
+/// both ends of their overlap. Zero-length line segments intersect with the
+/// line segments that they lie on.
+///
+/// Whether line segments meet is decided exactly (see `orientation`), but
+/// where two of them cross is rounded to the nearest representable point, and
+/// so it may not lie exactly on either of them.
+///
+/// ```
+/// use euclid::{point2, UnknownUnit};
+/// use fart_2d_geom::{line, segment_intersections, Line};
+///
+/// let segments: Vec<Line<f64, UnknownUnit>> = vec![
+///     line(point2(0.0, 0.0), point2(4.0, 4.0)),
+///     line(point2(0.0, 4.0), point2(4.0, 0.0)),
+///     line(point2(4.0, 4.0), point2(8.0, 4.0)),
+///     line(point2(6.0, 4.0), point2(10.0, 4.0)),
+/// ];
+///
+/// let intersections = segment_intersections(&segments);
+///
+/// let found: Vec<_> = intersections
+///     .iter()
+///     .map(|i| (i.point, i.segments.clone()))
+///     .collect();
+/// assert_eq!(found, vec![
+///     // The first two cross.
+///     (point2(2.0, 2.0), vec![0, 1]),
+///     // The first and the third share an endpoint.
+///     (point2(4.0, 4.0), vec![0, 2]),
+///     // The last two overlap between these points.
+///     (point2(6.0, 4.0), vec![2, 3]),
+///     (point2(8.0, 4.0), vec![2, 3]),
+/// ]);
+/// ```
+pub fn segment_intersections<U>(segments: &[Line<f64, U>]) -> Vec<SegmentIntersection<U>> {
+    let mut sweep = Sweep::new(segments);
+    let mut intersections: Vec<SegmentIntersection<U>> = vec![];
+
+    while let Some((point, segments)) = sweep.step() {
+        if segments.len() < 2 {
+            continue;
+        }
+        match intersections.last_mut() {
+            // Crossings that are found late are swept at the same point again.
+            Some(last) if last.point == point => {
+                last.segments.extend(segments);
+                last.segments.sort();
+                last.segments.dedup();
+            }
+            _ => intersections.push(SegmentIntersection { point, segments }),
+        }
+    }
+
+    intersections
+}
+
+/// Split the given line segments at every point where they meet, so that the
+/// resulting line segments only ever meet at their endpoints.
+///
+/// This is the planar arrangement of the line segments: any two of the
+/// resulting line segments either don't intersect, or share an endpoint.
+/// Where line segments overlap, only one copy of the overlap is kept, and
+/// zero-length line segments are dropped. Line segments that meet at a point
+/// all end at exactly the same point. See `segment_intersections` for details.
+///
+/// The resulting line segments point in the direction of increasing `x`, and
+/// then increasing `y`.
+///
+/// ```
+/// use euclid::{point2, UnknownUnit};
+/// use fart_2d_geom::{line, split_segments, Line};
+///
+/// let segments: Vec<Line<f64, UnknownUnit>> = vec![
+///     line(point2(0.0, 0.0), point2(4.0, 4.0)),
+///     line(point2(0.0, 4.0), point2(4.0, 0.0)),
+///     line(point2(4.0, 4.0), point2(8.0, 4.0)),
+///     line(point2(6.0, 4.0), point2(10.0, 4.0)),
+/// ];
+///
+/// assert_eq!(split_segments(&segments), vec![
+///     line(point2(0.0, 0.0), point2(2.0, 2.0)),
+///     line(point2(2.0, 2.0), point2(4.0, 4.0)),
+///     line(point2(0.0, 4.0), point2(2.0, 2.0)),
+///     line(point2(2.0, 2.0), point2(4.0, 0.0)),
+///     line(point2(4.0, 4.0), point2(6.0, 4.0)),
+///     line(point2(6.0, 4.0), point2(8.0, 4.0)),
+///     line(point2(8.0, 4.0), point2(10.0, 4.0)),
+/// ]);
+/// ```
+pub fn split_segments<U>(segments: &[Line<f64, U>]) -> Vec<Line<f64, U>> {
+    let mut splits: Vec<Vec<Point2D<f64, U>>> = segments
+        .iter()
+        .map(|l| {
+            let (a, b) = sweep_order(l);
+            vec![a, b]
+        })
+        .collect();
+    for intersection in segment_intersections(segments) {
+        for &i in &intersection.segments {
+            splits[i].push(intersection.point);
+        }
+    }
+
+    let mut seen = BTreeSet::new();
+    let mut pieces = vec![];
+    for mut points in splits {
+        points.sort_by_key(|p| key(*p));
+        points.dedup();
+        for w in points.windows(2) {
+            if seen.insert((key(w[0]), key(w[1]))) {
+                pieces.push(Line::new(w[0], w[1]));
+            }
+        }
+    }
+    pieces
+}
+
+type Key = NoMorePartial<(f64, f64)>;
+
+/// The order that points are swept in.
+fn key<U>(p: Point2D<f64, U>) -> Key {
+    NoMorePartial((p.x, p.y))
+}
+
+/// The endpoints of `l`, in the order that they are swept.
+fn sweep_order<U>(l: &Line<f64, U>) -> (Point2D<f64, U>, Point2D<f64, U>) {
+    if key(l.a) <= key(l.b) {
+        (l.a, l.b)
+    } else {
+        (l.b, l.a)
+    }
+}
+
+/// The line segments that meet at an event point, as far as is known before
+/// the sweep reaches it.
+#[derive(Default)]
+struct Event {
+    /// Line segments that start here.
+    starts: Vec<usize>,
+    /// Line segments that end here.
+    ends: Vec<usize>,
+    /// Line segments that were found to cross each other here.
+    crosses: Vec<usize>,
+}
+
+struct Sweep<U> {
+    /// The line segments, with `a` swept before `b`.
+    segments: Vec<Line<f64, U>>,
+    events: BTreeMap<Key, Event>,
+    /// The line segments that cross the sweep line, from bottom to top.
+    status: Vec<usize>,
+    /// The pairs of line segments whose crossing has been scheduled or swept.
+    scheduled: HashSet<(usize, usize)>,
+}
+
+impl<U> Sweep<U> {
+    fn new(segments: &[Line<f64, U>]) -> Sweep<U> {
+        let segments: Vec<_> = segments
+            .iter()
+            .map(|l| {
+                let (a, b) = sweep_order(l);
+                Line::new(a, b)
+            })
+            .collect();
+
+        let mut events: BTreeMap<Key, Event> = BTreeMap::new();
+        for (i, l) in segments.iter().enumerate() {
+            events.entry(key(l.a)).or_default().starts.push(i);
+            events.entry(key(l.b)).or_default().ends.push(i);
+        }
+
+        Sweep {
+            segments,
+            events,
+            status: vec![],
+            scheduled: HashSet::new(),
+        }
+    }
+
+    /// Sweep the next event point, returning it along with every line segment
+    /// that meets there.
+    fn step(&mut self) -> Option<(Point2D<f64, U>, Vec<usize>)> {
+        let current = *self.events.keys().next()?;
+        let event = self.events.remove(&current).unwrap();
+        let p = point2((current.0).0, (current.0).1);
+
+        // Line segments that pass through `p` are next to each other in the
+        // status. Those that were found to cross here might not exactly, since
+        // `p` is rounded, but the others must.
+        let segments = &self.segments;
+        let below = self.status.partition_point(|&s| {
+            orientation(segments[s].a, segments[s].b, p) == RelativeDirection::Left
+        });
+        let through = self.status[below..].iter().take_while(|&&s| {
+            orientation(segments[s].a, segments[s].b, p) == RelativeDirection::Collinear
+        });
+
+        let mut involved: Vec<usize> = event
+            .starts
+            .iter()
+            .chain(&event.ends)
+            .chain(&event.crosses)
+            .chain(through)
+            .cloned()
+            .collect();
+        involved.sort();
+        involved.dedup();
+
+        // Take every involved line segment out of the status, and then put
+        // back the ones that continue past `p`, in their order just after it.
+        //
+        // Any line segment between two that meet at `p` passes through `p`
+        // too, or else it would have met one of them first. When crossings
+        // are rounded to slightly different points, they are found in more
+        // than one step, and these are the line segments that were missed.
+        let removed = {
+            let mut positions = self
+                .status
+                .iter()
+                .enumerate()
+                .filter(|&(_, s)| involved.binary_search(s).is_ok())
+                .map(|(i, _)| i);
+            let first = positions.next();
+            first.map(|first| (first, positions.next_back().unwrap_or(first)))
+        };
+        if let Some((first, last)) = removed {
+            involved.extend(self.status.drain(first..=last));
+            involved.sort();
+            involved.dedup();
+        }
+
+        let mut continuing: Vec<usize> = involved
+            .iter()
+            .cloned()
+            .filter(|&s| key(segments[s].a) <= current && current < key(segments[s].b))
+            .collect();
+        continuing.sort_by(|&s, &t| {
+            let (s_line, t_line) = (&segments[s], &segments[t]);
+            match cross(s_line.b, s_line.a, t_line.b, t_line.a) {
+                RelativeDirection::Left => Ordering::Less,
+                RelativeDirection::Right => Ordering::Greater,
+                RelativeDirection::Collinear => s.cmp(&t),
+            }
+        });
+
+        let at = removed.map(|(first, _)| first).unwrap_or_else(|| {
+            self.status.partition_point(|&s| {
+                orientation(segments[s].a, segments[s].b, p) == RelativeDirection::Left
+            })
+        });
+        // Line segments that continue on from the same point can't cross each
+        // other again.
+        for (i, &s) in continuing.iter().enumerate() {
+            for &t in &continuing[i + 1..] {
+                self.scheduled.insert((s.min(t), s.max(t)));
+            }
+        }
+
+        let n = continuing.len();
+        self.status.splice(at..at, continuing);
+
+        // Check the newly adjacent line segments for intersections further
+        // along.
+        if n == 0 {
+            if at > 0 && at < self.status.len() {
+                self.check(self.status[at - 1], self.status[at], current);
+            }
+        } else {
+            if at > 0 {
+                self.check(self.status[at - 1], self.status[at], current);
+            }
+            if at + n < self.status.len() {
+                self.check(self.status[at + n - 1], self.status[at + n], current);
+            }
+        }
+
+        Some((p, involved))
+    }
+
+    /// Schedule the intersection of line segments `s` and `t`, which are next
+    /// to each other in the status, if they haven't been yet.
+    fn check(&mut self, s: usize, t: usize, current: Key) {
+        let (l, m) = (&self.segments[s], &self.segments[t]);
+        if !l.improperly_intersects(m) || (l.is_collinear(m.a) && l.is_collinear(m.b)) {
+            // Collinear line segments meet at the endpoints of their overlap,
+            // where the sweep finds one of them lying on the other.
+            return;
+        }
+
+        let point = if let Some(p) = [m.a, m.b].iter().find(|&&p| l.is_on(p)) {
+            let p = key(*p);
+            if p <= current {
+                // This endpoint has already been swept, which found every
+                // line segment through it.
+                return;
+            }
+            p
+        } else if let Some(p) = [l.a, l.b].iter().find(|&&p| m.is_on(p)) {
+            let p = key(*p);
+            if p <= current {
+                return;
+            }
+            p
+        } else {
+            // A crossing that is rounded back behind `current` is swept at
+            // `current` again.
+            key(crossing_point(l.a, l.b, m.a, m.b)).max(current)
+        };
+
+        if self.scheduled.insert((s.min(t), s.max(t))) {
+            let crosses = &mut self.events.entry(point).or_default().crosses;
+            crosses.push(s);
+            crosses.push(t);
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use fart_2d_geom::{boolean, BooleanOp, Polygon};
+use quickcheck::quickcheck;
+
+mod common;
+use common::{area, polygon, square, ArbitraryPolygon, UnknownUnit};
+
+fn approx_eq(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-6
+}
+
+fn check_area_identities(a: Polygon<i64, UnknownUnit>, b: Polygon<i64, UnknownUnit>) -> bool {
+    if !a.is_simple() || !b.is_simple() {
+        return true;
+    }
+    let (a, b) = (a.cast::<f64>(), b.cast::<f64>());
+
+    let intersection = area(&a.intersection(&b));
+    let union = area(&a.union(&b));
+    let difference = area(&a.difference(&b));
+    let xor = area(&a.xor(&b));
+    println!(
+        "intersection = {}, union = {}, difference = {}, xor = {}",
+        intersection, union, difference, xor
+    );
+
+    approx_eq(union + intersection, a.area() + b.area())
+        && approx_eq(difference, a.area() - intersection)
+        && approx_eq(xor, union - intersection)
+}
+
+quickcheck! {
+    fn area_identities(a: ArbitraryPolygon, b: ArbitraryPolygon) -> bool {
+        check_area_identities(a.0, b.0)
+    }
+
+    fn area_identities_with_self(a: ArbitraryPolygon) -> bool {
+        check_area_identities(a.0.clone(), a.0)
+    }
+}
+
+#[test]
+fn identical_polygons() {
+    let a = polygon(&[(0.0, 0.0), (4.0, 0.0), (2.0, 1.0), (4.0, 4.0), (0.0, 4.0)]);
+    assert!(approx_eq(area(&a.union(&a)), a.area()));
+    assert!(approx_eq(area(&a.intersection(&a)), a.area()));
+    assert!(a.difference(&a).is_empty());
+    assert!(a.xor(&a).is_empty());
+}
+
+#[test]
+fn shared_edge() {
+    let union = square(0.0, 0.0, 1.0).union(&square(1.0, 0.0, 1.0));
+    assert_eq!(union.len(), 1);
+    assert_eq!(union[0].exterior().len(), 4);
+    assert!(approx_eq(area(&union), 2.0));
+
+    let intersection = square(0.0, 0.0, 1.0).intersection(&square(1.0, 0.0, 1.0));
+    assert!(intersection.is_empty());
+}
+
+#[test]
+fn touching_at_a_vertex() {
+    let union = square(0.0, 0.0, 1.0).union(&square(1.0, 1.0, 1.0));
+    assert_eq!(union.len(), 2);
+    assert!(approx_eq(area(&union), 2.0));
+}
+
+#[test]
+fn hole() {
+    let difference = square(0.0, 0.0, 10.0).difference(&square(2.0, 2.0, 2.0));
+    assert_eq!(difference.len(), 1);
+    assert_eq!(difference[0].holes().len(), 1);
+    assert!(approx_eq(area(&difference), 96.0));
+}
+
+#[test]
+fn hole_touching_exterior() {
+    // A triangular notch whose tip touches the square's bottom edge.
+    let notch = polygon(&[(5.0, 0.0), (7.0, 2.0), (3.0, 2.0)]);
+    let difference = square(0.0, 0.0, 10.0).difference(&notch);
+    assert_eq!(difference.len(), 1);
+    assert_eq!(difference[0].holes().len(), 1);
+    assert!(approx_eq(area(&difference), 96.0));
+}
+
+#[test]
+fn concave_comb() {
+    let comb = polygon(&[
+        (0.0, 0.0),
+        (5.0, 0.0),
+        (5.0, 4.0),
+        (4.0, 4.0),
+        (4.0, 1.0),
+        (3.0, 1.0),
+        (3.0, 4.0),
+        (2.0, 4.0),
+        (2.0, 1.0),
+        (1.0, 1.0),
+        (1.0, 4.0),
+        (0.0, 4.0),
+    ]);
+    let bar = polygon(&[(-1.0, 2.0), (6.0, 2.0), (6.0, 3.0), (-1.0, 3.0)]);
+
+    let intersection = comb.intersection(&bar);
+    assert_eq!(intersection.len(), 3);
+    assert!(approx_eq(area(&intersection), 3.0));
+
+    let union = comb.union(&bar);
+    assert_eq!(union.len(), 1);
+    assert_eq!(union[0].holes().len(), 2);
+    assert!(approx_eq(area(&union), comb.area() + bar.area() - 3.0));
+}
+
+#[test]
+fn self_intersecting_bow_tie() {
+    let bow_tie = polygon(&[(0.0, 0.0), (2.0, 2.0), (2.0, 0.0), (0.0, 2.0)]);
+    let intersection = bow_tie.intersection(&square(-1.0, -1.0, 4.0));
+    assert_eq!(intersection.len(), 2);
+    assert!(approx_eq(area(&intersection), 2.0));
+}
+
+#[test]
+fn multiple_polygons_with_holes() {
+    let frame = boolean(
+        &[square(0.0, 0.0, 9.0).into()],
+        &[square(3.0, 3.0, 3.0).into()],
+        BooleanOp::Difference,
+    );
+    let clip = [square(4.0, 4.0, 1.0).into(), square(8.0, 8.0, 2.0).into()];
+
+    let union = boolean(&frame, &clip, BooleanOp::Union);
+    assert!(approx_eq(area(&union), 72.0 + 1.0 + 3.0));
+
+    let intersection = boolean(&frame, &clip, BooleanOp::Intersection);
+    assert!(approx_eq(area(&intersection), 1.0));
+}
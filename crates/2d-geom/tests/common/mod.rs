@@ -0,0 +1,80 @@
+//! Test fixtures shared between the integration tests.
+
+// Each test only uses some of these.
+#![allow(dead_code)]
+
+use euclid::point2;
+use fart_2d_geom::{Polygon, PolygonWithHoles};
+use quickcheck::{Arbitrary, Gen};
+use rand::distributions::{Distribution, Uniform};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UnknownUnit;
+
+/// A random polygon on a small grid. It isn't always simple.
+#[derive(Clone, Debug)]
+pub struct ArbitraryPolygon(pub Polygon<i64, UnknownUnit>);
+
+impl Arbitrary for ArbitraryPolygon {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let n = Uniform::new(3, 10).sample(g);
+        ArbitraryPolygon(Polygon::random(
+            g,
+            &mut Uniform::new(-20, 20),
+            &mut Uniform::new(-20, 20),
+            n,
+        ))
+    }
+}
+
+/// A random polygon, with even coordinates so that the midpoints between its
+/// vertices are integers too.
+#[derive(Clone, Debug)]
+pub struct ArbitraryEvenPolygon(pub Polygon<i64, UnknownUnit>);
+
+impl Arbitrary for ArbitraryEvenPolygon {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let polygon = ArbitraryPolygon::arbitrary(g).0;
+        ArbitraryEvenPolygon(Polygon::new(
+            polygon.vertices().iter().map(|v| *v * 2).collect(),
+        ))
+    }
+}
+
+/// A random polygon that is star-shaped around the origin.
+#[derive(Clone, Debug)]
+pub struct ArbitraryStar(pub Polygon<f64, UnknownUnit>);
+
+impl Arbitrary for ArbitraryStar {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let n = Uniform::new(3, 100).sample(g);
+        let radius = Uniform::new(5.0, 10.0);
+        let jitter = Uniform::new(0.0, 0.9);
+        let step = 2.0 * std::f64::consts::PI / n as f64;
+        ArbitraryStar(Polygon::new(
+            (0..n)
+                .map(|i| {
+                    let angle = (i as f64 + jitter.sample(g)) * step;
+                    let r = radius.sample(g);
+                    point2(r * angle.cos(), r * angle.sin())
+                })
+                .collect(),
+        ))
+    }
+}
+
+pub fn polygon(vertices: &[(f64, f64)]) -> Polygon<f64, UnknownUnit> {
+    Polygon::new(vertices.iter().map(|&(x, y)| point2(x, y)).collect())
+}
+
+pub fn square(x: f64, y: f64, size: f64) -> Polygon<f64, UnknownUnit> {
+    polygon(&[(x, y), (x + size, y), (x + size, y + size), (x, y + size)])
+}
+
+/// The total area of the polygons, minus their holes.
+pub fn area(polygons: &[PolygonWithHoles<f64, UnknownUnit>]) -> f64 {
+    polygons
+        .iter()
+        .map(|p| p.exterior().area() - p.holes().iter().map(|h| h.area()).sum::<f64>())
+        .sum()
+}
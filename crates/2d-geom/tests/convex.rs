@@ -0,0 +1,264 @@
+use euclid::{point2, Point2D};
+use fart_2d_geom::{line, Circle, ConvexPolygon, Polygon};
+use quickcheck::{quickcheck, Arbitrary, Gen};
+use rand::distributions::{Distribution, Uniform};
+use std::collections::BTreeSet;
+
+mod common;
+use common::{area, UnknownUnit};
+
+/// The convex hull of random points on a small grid, which often has long
+/// edges parallel to other hulls' edges.
+#[derive(Clone, Debug)]
+struct GridHull(ConvexPolygon<i64, UnknownUnit>);
+
+impl Arbitrary for GridHull {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let n = Uniform::new(3, 12);
+        let coordinate = Uniform::new_inclusive(-8, 8);
+        loop {
+            let points = (0..n.sample(g))
+                .map(|_| point2(coordinate.sample(g), coordinate.sample(g)))
+                .collect();
+            if let Some(hull) = ConvexPolygon::hull(points) {
+                return GridHull(hull);
+            }
+        }
+    }
+}
+
+/// The convex hull of random points anywhere.
+#[derive(Clone, Debug)]
+struct RandomHull(ConvexPolygon<f64, UnknownUnit>);
+
+impl Arbitrary for RandomHull {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let n = Uniform::new(3, 20);
+        let coordinate = Uniform::new(-10.0, 10.0);
+        loop {
+            let points = (0..n.sample(g))
+                .map(|_| point2(coordinate.sample(g), coordinate.sample(g)))
+                .collect();
+            if let Some(hull) = ConvexPolygon::hull(points) {
+                return RandomHull(hull);
+            }
+        }
+    }
+}
+
+fn check_intersection(
+    a: &ConvexPolygon<f64, UnknownUnit>,
+    b: &ConvexPolygon<f64, UnknownUnit>,
+) -> bool {
+    let expected = area(&Polygon::clone(a).intersection(b));
+    match a.convex_intersection(b) {
+        None => expected < 1e-9,
+        Some(c) => {
+            (c.area() - expected).abs() < 1e-9 * expected.max(1.0)
+                && c.is_convex()
+                && c.vertices().iter().all(|&v| {
+                    [a, b]
+                        .iter()
+                        .all(|p| p.edges().all(|e| (e.b - e.a).cross(v - e.a) > -1e-9))
+                })
+        }
+    }
+}
+
+fn check_enclosing_circle(points: &[Point2D<f64, UnknownUnit>]) -> bool {
+    let circle = match Circle::enclosing(points) {
+        None => return points.is_empty(),
+        Some(c) => c,
+    };
+    let encloses = |c: &Circle<f64, UnknownUnit>| {
+        points
+            .iter()
+            .all(|&p| (p - c.center).length() <= c.radius * (1.0 + 1e-9) + 1e-9)
+    };
+
+    // The smallest enclosing circle is either the smallest circle through two
+    // of the points, or the circle through three of them.
+    let mut smallest = f64::INFINITY;
+    let n = points.len();
+    for i in 0..n {
+        for j in i + 1..n {
+            let (a, b) = (points[i], points[j]);
+            let c = Circle::new(a.lerp(b, 0.5), (b - a).length() / 2.0);
+            if encloses(&c) {
+                smallest = smallest.min(c.radius);
+            }
+            for &c in &points[j + 1..] {
+                let (ab, ac) = (b - a, c - a);
+                let d = 2.0 * ab.cross(ac);
+                if d.abs() < 1e-9 {
+                    continue;
+                }
+                let (ab2, ac2) = (ab.square_length(), ac.square_length());
+                let center = a + euclid::vec2(ac.y * ab2 - ab.y * ac2, ab.x * ac2 - ac.x * ab2) / d;
+                let c = Circle::new(center, (a - center).length());
+                if encloses(&c) {
+                    smallest = smallest.min(c.radius);
+                }
+            }
+        }
+    }
+    encloses(&circle) && (n < 2 || circle.radius <= smallest * (1.0 + 1e-9) + 1e-9)
+}
+
+quickcheck! {
+    fn grid_intersection_matches_boolean_intersection(a: GridHull, b: GridHull) -> bool {
+        check_intersection(&a.0.cast(), &b.0.cast())
+    }
+
+    fn random_intersection_matches_boolean_intersection(a: RandomHull, b: RandomHull) -> bool {
+        check_intersection(&a.0, &b.0)
+    }
+
+    fn intersection_with_self_is_self(a: RandomHull) -> bool {
+        let a = a.0;
+        a.convex_intersection(&a).is_some_and(|c| (c.area() - a.area()).abs() < 1e-9)
+    }
+
+    fn minkowski_sum_is_hull_of_sums(a: GridHull, b: GridHull) -> bool {
+        let sums = a
+            .0
+            .vertices()
+            .iter()
+            .flat_map(|&p| b.0.vertices().iter().map(move |&q| p + q.to_vector()))
+            .collect();
+        let expected: BTreeSet<_> = ConvexPolygon::hull(sums)
+            .unwrap()
+            .vertices()
+            .iter()
+            .map(|p| (p.x, p.y))
+            .collect();
+        let sum = a.0.minkowski_sum(&b.0);
+        let actual: BTreeSet<_> = sum.vertices().iter().map(|p| (p.x, p.y)).collect();
+        actual == expected && actual.len() == sum.len() && sum.is_convex()
+    }
+
+    fn minkowski_difference_contains_origin_when_overlapping(a: GridHull, b: GridHull) -> bool {
+        let difference = a.0.minkowski_difference(&b.0);
+        difference.contains_point(point2(0, 0)) == a.0.overlaps(&b.0)
+    }
+
+    fn overlaps_when_intersection_has_area(a: GridHull, b: GridHull) -> bool {
+        let (a, b) = (a.0, b.0);
+        a.overlaps(&b) == a.cast::<f64>().convex_intersection(&b.cast()).is_some()
+            && a.overlaps(&b) == b.overlaps(&a)
+    }
+
+    fn diameter_is_farthest_pair(a: GridHull) -> bool {
+        let v = a.0.vertices();
+        let farthest = v
+            .iter()
+            .flat_map(|&p| v.iter().map(move |&q| (q - p).square_length()))
+            .max()
+            .unwrap();
+        let d = a.0.diameter();
+        (d.b - d.a).square_length() == farthest && v.contains(&d.a) && v.contains(&d.b)
+    }
+
+    fn minimum_bounding_rectangle_is_minimal(a: RandomHull) -> bool {
+        let a = a.0;
+        let rectangle = a.minimum_bounding_rectangle();
+
+        // Try a rectangle along every edge.
+        let smallest = a
+            .edges()
+            .map(|e| {
+                let u = (e.b - e.a).normalize();
+                let (mut lo, mut hi, mut height) = (0.0_f64, 0.0_f64, 0.0_f64);
+                for &v in a.vertices() {
+                    let d = v - e.a;
+                    lo = lo.min(d.dot(u));
+                    hi = hi.max(d.dot(u));
+                    height = height.max(u.cross(d));
+                }
+                (hi - lo) * height
+            })
+            .fold(f64::INFINITY, f64::min);
+
+        rectangle.len() == 4
+            && (rectangle.area() - smallest).abs() < 1e-9 * smallest.max(1.0)
+            && a.vertices().iter().all(|&v| {
+                rectangle
+                    .edges()
+                    .all(|e| (e.b - e.a).normalize().cross(v - e.a) > -1e-9)
+            })
+    }
+
+    fn enclosing_circle_is_smallest(a: RandomHull) -> bool {
+        check_enclosing_circle(a.0.vertices())
+    }
+
+    fn enclosing_circle_of_grid_points_is_smallest(points: Vec<(i8, i8)>) -> bool {
+        let points: Vec<_> = points
+            .into_iter()
+            .take(20)
+            .map(|(x, y)| point2(x as f64, y as f64))
+            .collect();
+        check_enclosing_circle(&points)
+    }
+
+    fn minimum_translation_separates(a: GridHull, b: GridHull) -> bool {
+        let (a, b) = (a.0.cast::<f64>(), b.0.cast::<f64>());
+        match a.minimum_translation(&b) {
+            None => !a.overlaps(&b),
+            Some(t) => {
+                // Moving a little less than the translation still overlaps, and
+                // a little more doesn't.
+                let moved = |scale: f64| {
+                    ConvexPolygon::hull(
+                        a.vertices().iter().map(|&v| v + t * scale).collect(),
+                    )
+                    .unwrap()
+                };
+                a.overlaps(&b) && moved(0.99).overlaps(&b) && !moved(1.01).overlaps(&b)
+            }
+        }
+    }
+}
+
+#[test]
+fn touching_squares_have_no_intersection() {
+    let square = |x: f64, y: f64| {
+        ConvexPolygon::<f64, UnknownUnit>::hull(vec![
+            point2(x, y),
+            point2(x + 1.0, y),
+            point2(x + 1.0, y + 1.0),
+            point2(x, y + 1.0),
+        ])
+        .unwrap()
+    };
+    assert!(square(0.0, 0.0)
+        .convex_intersection(&square(1.0, 0.0))
+        .is_none());
+    assert!(square(0.0, 0.0)
+        .convex_intersection(&square(1.0, 1.0))
+        .is_none());
+    assert!(!square(0.0, 0.0).overlaps(&square(1.0, 0.5)));
+    assert!(square(0.0, 0.0)
+        .minimum_translation(&square(1.0, 0.5))
+        .is_none());
+}
+
+#[test]
+fn diameter_of_square_is_a_diagonal() {
+    let square = ConvexPolygon::<i64, UnknownUnit>::hull(vec![
+        point2(0, 0),
+        point2(3, 0),
+        point2(3, 3),
+        point2(0, 3),
+        point2(1, 0),
+        point2(2, 0),
+    ])
+    .unwrap();
+    let d = square.diameter();
+    assert!([
+        line(point2(0, 0), point2(3, 3)),
+        line(point2(3, 0), point2(0, 3))
+    ]
+    .iter()
+    .any(|l| (l.a == d.a && l.b == d.b) || (l.a == d.b && l.b == d.a)));
+}
@@ -0,0 +1,291 @@
+use euclid::{point2, vec2, Angle, Point2D, Transform2D, Vector2D};
+use fart_2d_geom::{Arc, Circle, CubicBezier, QuadraticBezier};
+use fart_aabb::{Aabb, ToAabb};
+use quickcheck::{quickcheck, Arbitrary, Gen};
+use rand::distributions::{Distribution, Uniform};
+use std::f64::consts::PI;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct UnknownUnit;
+
+type Point = Point2D<f64, UnknownUnit>;
+type Vector = Vector2D<f64, UnknownUnit>;
+
+fn arbitrary_point<G: Gen>(g: &mut G) -> Point {
+    let coordinate = Uniform::new(-20.0, 20.0);
+    point2(coordinate.sample(g), coordinate.sample(g))
+}
+
+fn arbitrary_angle<G: Gen>(g: &mut G, max: f64) -> Angle<f64> {
+    Angle::radians(Uniform::new(-max, max).sample(g))
+}
+
+#[derive(Clone, Debug)]
+struct ArbitraryQuadratic(QuadraticBezier<f64, UnknownUnit>);
+
+impl Arbitrary for ArbitraryQuadratic {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        ArbitraryQuadratic(QuadraticBezier::new(
+            arbitrary_point(g),
+            arbitrary_point(g),
+            arbitrary_point(g),
+        ))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ArbitraryCubic(CubicBezier<f64, UnknownUnit>);
+
+impl Arbitrary for ArbitraryCubic {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        ArbitraryCubic(CubicBezier::new(
+            arbitrary_point(g),
+            arbitrary_point(g),
+            arbitrary_point(g),
+            arbitrary_point(g),
+        ))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ArbitraryArc(Arc<f64, UnknownUnit>);
+
+impl Arbitrary for ArbitraryArc {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let radius = Uniform::new(0.5, 10.0);
+        ArbitraryArc(Arc::new(
+            arbitrary_point(g),
+            vec2(radius.sample(g), radius.sample(g)),
+            arbitrary_angle(g, PI),
+            arbitrary_angle(g, PI),
+            arbitrary_angle(g, 2.0 * PI),
+        ))
+    }
+}
+
+/// A transformation that doesn't squash everything onto a line.
+#[derive(Clone, Debug)]
+struct ArbitraryTransform(Transform2D<f64, UnknownUnit, UnknownUnit>);
+
+impl Arbitrary for ArbitraryTransform {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let m = Uniform::new(-3.0_f64, 3.0);
+        loop {
+            let t = Transform2D::row_major(
+                m.sample(g),
+                m.sample(g),
+                m.sample(g),
+                m.sample(g),
+                m.sample(g),
+                m.sample(g),
+            );
+            if t.determinant().abs() > 0.1 {
+                return ArbitraryTransform(t);
+            }
+        }
+    }
+}
+
+fn close(a: Point, b: Point) -> bool {
+    (a - b).length() < 1e-6
+}
+
+/// Sample points along a curve.
+fn samples(point_at: impl Fn(f64) -> Point) -> Vec<Point> {
+    (0..=10_000)
+        .map(|i| point_at(i as f64 / 10_000.0))
+        .collect()
+}
+
+/// Check that the bounding box contains every sampled point, and that the
+/// sampled points reach every side of it.
+fn check_aabb(aabb: Aabb<f64, UnknownUnit>, points: &[Point]) -> bool {
+    let sampled = Aabb::for_vertices(points.iter().cloned());
+    let slack = 1e-6;
+    let contains = aabb.min().x <= sampled.min().x + slack
+        && aabb.min().y <= sampled.min().y + slack
+        && aabb.max().x >= sampled.max().x - slack
+        && aabb.max().y >= sampled.max().y - slack;
+    let tight = (aabb.min() - sampled.min()).length() < 1e-2
+        && (aabb.max() - sampled.max()).length() < 1e-2;
+    if !contains || !tight {
+        println!("aabb = {:?}, sampled = {:?}", aabb, sampled);
+    }
+    contains && tight
+}
+
+/// Check that the length is that of a fine polyline through the curve.
+fn check_length(length: f64, points: &[Point]) -> bool {
+    let polyline: f64 = points.windows(2).map(|w| (w[1] - w[0]).length()).sum();
+    let ok = (length - polyline).abs() <= 1e-6 * length.max(1.0);
+    if !ok {
+        println!("length = {}, polyline = {}", length, polyline);
+    }
+    ok
+}
+
+/// Check that the tangent points the same way as a finite difference along
+/// the curve.
+fn check_tangent(tangent: Vector, point_at: impl Fn(f64) -> Point, t: f64) -> bool {
+    let (a, b) = ((t - 1e-7).max(0.0), (t + 1e-7).min(1.0));
+    let difference = point_at(b) - point_at(a);
+    if difference.length() < 1e-9 {
+        return true;
+    }
+    let ok = (tangent - difference.normalize()).length() < 1e-4;
+    if !ok {
+        println!("tangent = {:?}, difference = {:?}", tangent, difference);
+    }
+    ok
+}
+
+quickcheck! {
+    fn quadratic_aabb(c: ArbitraryQuadratic) -> bool {
+        check_aabb(c.0.to_aabb(), &samples(|t| c.0.point_at(t)))
+    }
+
+    fn cubic_aabb(c: ArbitraryCubic) -> bool {
+        check_aabb(c.0.to_aabb(), &samples(|t| c.0.point_at(t)))
+    }
+
+    fn arc_aabb(a: ArbitraryArc) -> bool {
+        check_aabb(a.0.to_aabb(), &samples(|t| a.0.point_at(t)))
+    }
+
+    fn ellipse_aabb(a: ArbitraryArc) -> bool {
+        let ellipse = a.0.ellipse();
+        let points = samples(|t| ellipse.point_at(Angle::radians(t * 2.0 * PI)));
+        check_aabb(ellipse.to_aabb(), &points)
+    }
+
+    fn quadratic_length(c: ArbitraryQuadratic) -> bool {
+        check_length(c.0.length(), &samples(|t| c.0.point_at(t)))
+    }
+
+    fn cubic_length(c: ArbitraryCubic) -> bool {
+        check_length(c.0.length(), &samples(|t| c.0.point_at(t)))
+    }
+
+    fn arc_length(a: ArbitraryArc) -> bool {
+        check_length(a.0.length(), &samples(|t| a.0.point_at(t)))
+    }
+
+    fn quadratic_tangent(c: ArbitraryQuadratic, t: u8) -> bool {
+        let t = f64::from(t) / 255.0;
+        check_tangent(c.0.tangent_at(t), |t| c.0.point_at(t), t)
+    }
+
+    fn cubic_tangent(c: ArbitraryCubic, t: u8) -> bool {
+        let t = f64::from(t) / 255.0;
+        check_tangent(c.0.tangent_at(t), |t| c.0.point_at(t), t)
+    }
+
+    fn arc_tangent(a: ArbitraryArc, t: u8) -> bool {
+        let t = f64::from(t) / 255.0;
+        check_tangent(a.0.tangent_at(t), |t| a.0.point_at(t), t)
+    }
+
+    fn arc_transform(a: ArbitraryArc, m: ArbitraryTransform, t: u8) -> bool {
+        // Transforming an arc transforms every point along it.
+        let t = f64::from(t) / 255.0;
+        let transformed = a.0.transform(&m.0);
+        close(transformed.point_at(t), m.0.transform_point(a.0.point_at(t)))
+    }
+
+    fn ellipse_transform(a: ArbitraryArc, m: ArbitraryTransform, t: u8) -> bool {
+        // The transformed ellipse goes through every transformed point.
+        let angle = Angle::radians(f64::from(t) / 255.0 * 2.0 * PI);
+        let ellipse = a.0.ellipse();
+        let transformed = ellipse.transform(&m.0);
+        let p = m.0.transform_point(ellipse.point_at(angle)) - transformed.center;
+        let (sin, cos) = transformed.rotation.radians.sin_cos();
+        let local = vec2::<f64, UnknownUnit>(
+            (cos * p.x + sin * p.y) / transformed.radii.x,
+            (-sin * p.x + cos * p.y) / transformed.radii.y,
+        );
+        (local.length() - 1.0).abs() < 1e-6
+    }
+
+    fn arc_from_endpoints(a: ArbitraryArc) -> bool {
+        let arc = a.0;
+        let sweep = arc.sweep_angle.radians;
+        if sweep.abs() < 1e-3 || (sweep.abs() - PI).abs() < 1e-3 {
+            return true;
+        }
+        match Arc::from_endpoints(
+            arc.start(),
+            arc.end(),
+            arc.radii,
+            arc.rotation,
+            sweep.abs() > PI,
+            sweep > 0.0,
+        ) {
+            None => false,
+            Some(b) => {
+                close(arc.center, b.center)
+                    && (sweep - b.sweep_angle.radians).abs() < 1e-6
+                    && (0..=4).all(|i| close(arc.point_at(i as f64 / 4.0), b.point_at(i as f64 / 4.0)))
+            }
+        }
+    }
+}
+
+#[test]
+fn circle() {
+    let c = Circle::<f64, UnknownUnit>::new(point2(1.0, 2.0), 3.0);
+    assert_eq!(c.to_aabb(), Aabb::new(point2(-2.0, -1.0), point2(4.0, 5.0)));
+    assert_eq!(c.length(), 6.0 * PI);
+
+    // A circle under a rotation and uniform scale is still a circle.
+    let transformation: Transform2D<f64, UnknownUnit, UnknownUnit> =
+        Transform2D::create_rotation(Angle::radians(1.0)).post_scale(2.0, 2.0);
+    let rotated = c.transform(&transformation);
+    assert!((rotated.radii - vec2(6.0, 6.0)).length() < 1e-12);
+    assert!((rotated.length() - 12.0 * PI).abs() < 1e-9);
+
+    let i = Circle::<i32, UnknownUnit>::new(point2(1, 2), 3);
+    assert_eq!(i.to_aabb(), Aabb::new(point2(-2, -1), point2(4, 5)));
+}
+
+#[test]
+fn full_turns() {
+    let arc = Arc::<f64, UnknownUnit>::new(
+        point2(0.0, 0.0),
+        vec2(2.0, 1.0),
+        Angle::zero(),
+        Angle::zero(),
+        Angle::radians(-2.0 * PI),
+    );
+    assert!(close(arc.start(), arc.end()));
+    assert!((arc.length() - arc.ellipse().length()).abs() < 1e-9);
+    assert_eq!(arc.to_aabb(), arc.ellipse().to_aabb());
+}
+
+#[test]
+fn degenerate_endpoints() {
+    let p = point2::<f64, UnknownUnit>(1.0, 1.0);
+    let r = vec2(1.0, 1.0);
+    assert!(Arc::from_endpoints(p, p, r, Angle::zero(), false, true).is_none());
+    assert!(Arc::from_endpoints(
+        p,
+        point2(2.0, 1.0),
+        vec2(0.0, 1.0),
+        Angle::zero(),
+        false,
+        true
+    )
+    .is_none());
+
+    // Radii that are too small are scaled up to reach.
+    let arc = Arc::from_endpoints(p, point2(5.0, 1.0), r, Angle::zero(), false, true).unwrap();
+    assert!((arc.radii - vec2(2.0, 2.0)).length() < 1e-12);
+}
+
+#[test]
+fn degenerate_bezier() {
+    let p = point2::<f64, UnknownUnit>(1.0, 1.0);
+    let dot = CubicBezier::new(p, p, p, p);
+    assert_eq!(dot.length(), 0.0);
+    assert_eq!(dot.tangent_at(0.5), vec2(0.0, 0.0));
+    assert_eq!(dot.to_aabb(), Aabb::new(p, p));
+}
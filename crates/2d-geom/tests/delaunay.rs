@@ -0,0 +1,272 @@
+use euclid::{point2, Point2D};
+use fart_2d_geom::{voronoi, DelaunayTriangulation};
+use fart_aabb::Aabb;
+use quickcheck::{quickcheck, Arbitrary, Gen};
+use rand::distributions::{Distribution, Uniform};
+use std::collections::{HashMap, HashSet};
+
+mod common;
+use common::{ArbitraryPolygon, UnknownUnit};
+
+/// A small grid of points, so that there are plenty of duplicate and
+/// collinear points.
+#[derive(Clone, Debug)]
+struct ArbitraryPoints(Vec<Point2D<f64, UnknownUnit>>);
+
+impl Arbitrary for ArbitraryPoints {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let n = Uniform::new(0, 40).sample(g);
+        let coordinate = Uniform::new(-20, 20);
+        ArbitraryPoints(
+            (0..n)
+                .map(|_| point2(coordinate.sample(g) as f64, coordinate.sample(g) as f64))
+                .collect(),
+        )
+    }
+}
+
+type Point = Point2D<f64, UnknownUnit>;
+
+fn orient(a: Point, b: Point, c: Point) -> f64 {
+    (b - a).cross(c - a)
+}
+
+fn in_circle(a: Point, b: Point, c: Point, p: Point) -> bool {
+    let (d, e, f) = (a - p, b - p, c - p);
+    let (ap, bp, cp) = (d.square_length(), e.square_length(), f.square_length());
+    d.x * (e.y * cp - bp * f.y) - d.y * (e.x * cp - bp * f.x) + ap * (e.x * f.y - e.y * f.x) > 0.0
+}
+
+fn crosses(a: Point, b: Point, c: Point, d: Point) -> bool {
+    orient(a, b, c) * orient(a, b, d) < 0.0 && orient(c, d, a) * orient(c, d, b) < 0.0
+}
+
+/// The area of the convex hull of the points, with Andrew's monotone chain.
+fn hull_area(points: &[Point]) -> f64 {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| (a.x, a.y).partial_cmp(&(b.x, b.y)).unwrap());
+    let mut hull: Vec<Point> = vec![];
+    for pass in 0..2 {
+        let start = hull.len();
+        for &p in &sorted {
+            while hull.len() >= start + 2
+                && orient(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0.0
+            {
+                hull.pop();
+            }
+            hull.push(p);
+        }
+        hull.pop();
+        if pass == 0 {
+            sorted.reverse();
+        }
+    }
+    (0..hull.len())
+        .map(|i| {
+            hull[i]
+                .to_vector()
+                .cross(hull[(i + 1) % hull.len()].to_vector())
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+/// Check that the triangles are counter-clockwise, exactly cover the convex
+/// hull of the points, and use every distinct point.
+fn check_covers_hull(t: &DelaunayTriangulation<UnknownUnit>) -> bool {
+    let points = t.points();
+    let area: f64 = t
+        .triangles()
+        .map(|[a, b, c]| orient(points[a], points[b], points[c]))
+        .inspect(|&area| assert!(area > 0.0, "triangle is not counter-clockwise"))
+        .sum::<f64>()
+        / 2.0;
+
+    let hull_area = hull_area(points);
+    if area != hull_area {
+        println!("area = {}, hull area = {}", area, hull_area);
+        return false;
+    }
+
+    if hull_area > 0.0 {
+        let used: HashSet<_> = t.triangles().flat_map(|t| t.to_vec()).collect();
+        let used: HashSet<_> = used
+            .into_iter()
+            .map(|i| (points[i].x as i64, points[i].y as i64))
+            .collect();
+        let all: HashSet<_> = points.iter().map(|p| (p.x as i64, p.y as i64)).collect();
+        if used != all {
+            println!("not every point was triangulated");
+            return false;
+        }
+    }
+    true
+}
+
+/// Check that no edge has a point inside the circumcircle of one of its
+/// triangles, on the other side of it, unless the edge is constrained.
+fn check_delaunay(t: &DelaunayTriangulation<UnknownUnit>) -> bool {
+    let points = t.points();
+    let mut opposite = HashMap::new();
+    for [a, b, c] in t.triangles() {
+        opposite.insert((a, b), c);
+        opposite.insert((b, c), a);
+        opposite.insert((c, a), b);
+    }
+    for (&(a, b), &c) in &opposite {
+        if let Some(&d) = opposite.get(&(b, a)) {
+            if !t.is_constrained(a, b) && in_circle(points[a], points[b], points[c], points[d]) {
+                println!("{:?} is inside the circumcircle of {:?}", d, (a, b, c));
+                return false;
+            }
+        }
+    }
+    true
+}
+
+quickcheck! {
+    fn delaunay_covers_hull(p: ArbitraryPoints) -> bool {
+        check_covers_hull(&DelaunayTriangulation::new(p.0))
+    }
+
+    fn delaunay_is_delaunay(p: ArbitraryPoints) -> bool {
+        check_delaunay(&DelaunayTriangulation::new(p.0))
+    }
+
+    fn constrained_delaunay(polygon: ArbitraryPolygon, p: ArbitraryPoints) -> bool {
+        if !polygon.0.is_simple() {
+            return true;
+        }
+
+        // Constrain the triangulation to the polygon's edges, among a bunch of
+        // other points.
+        let polygon = polygon.0.cast::<f64>();
+        let n = polygon.len();
+        let mut points = polygon.vertices().to_vec();
+        points.extend(p.0);
+        let constraints: Vec<_> = (0..n).map(|i| (i, (i + 1) % n)).collect();
+        let t = DelaunayTriangulation::with_constraints(points.clone(), &constraints);
+
+        for &(a, b) in &constraints {
+            for (c, d) in t.edges() {
+                if crosses(points[a], points[b], points[c], points[d]) {
+                    println!("{:?} crosses constraint {:?}", (c, d), (a, b));
+                    return false;
+                }
+            }
+        }
+        check_covers_hull(&t) && check_delaunay(&t)
+    }
+
+    fn voronoi_cells_tile_bounds(p: ArbitraryPoints) -> bool {
+        let points = p.0;
+        let bounds = Aabb::new(point2(-30.0, -30.0), point2(30.0, 30.0));
+        let cells = voronoi(&points, &bounds);
+        assert_eq!(cells.len(), points.len());
+
+        let mut area = 0.0;
+        let mut seen = HashSet::new();
+        for (p, cell) in points.iter().zip(&cells) {
+            let cell = cell.as_ref().unwrap();
+            if !cell.contains_point(*p) {
+                println!("{:?} is not in its own cell", p);
+                return false;
+            }
+            if seen.insert((p.x as i64, p.y as i64)) {
+                area += cell.area();
+            }
+        }
+        points.is_empty() || (area - 60.0 * 60.0).abs() < 1e-6
+    }
+
+    fn voronoi_cells_are_closest(p: ArbitraryPoints, x: i8, y: i8) -> bool {
+        let points = p.0;
+        let bounds = Aabb::new(point2(-30.0, -30.0), point2(30.0, 30.0));
+        let cells = voronoi(&points, &bounds);
+
+        // Any point inside a cell is at least as close to that cell's point as
+        // to any other.
+        let q = point2(f64::from(x % 30) + 0.5, f64::from(y % 30) + 0.5);
+        points.iter().zip(&cells).all(|(p, cell)| {
+            !cell.as_ref().unwrap().contains_point(q)
+                || points
+                    .iter()
+                    .all(|o| (q - *p).length() <= (q - *o).length() + 1e-9)
+        })
+    }
+}
+
+#[test]
+fn collinear_points() {
+    let points: Vec<Point> = (0..5).map(|i| point2(i as f64, 2.0 * i as f64)).collect();
+    let t = DelaunayTriangulation::new(points.clone());
+    assert_eq!(t.triangles().count(), 0);
+
+    let cells = voronoi(
+        &points,
+        &Aabb::new(point2(-10.0, -10.0), point2(20.0, 20.0)),
+    );
+    for (p, cell) in points.iter().zip(&cells) {
+        assert!(cell.as_ref().unwrap().contains_point(*p));
+    }
+}
+
+#[test]
+fn duplicate_points() {
+    let points: Vec<Point> = vec![
+        point2(0.0, 0.0),
+        point2(1.0, 0.0),
+        point2(0.0, 1.0),
+        point2(1.0, 0.0),
+        point2(0.0, 0.0),
+    ];
+    let t = DelaunayTriangulation::new(points.clone());
+    assert_eq!(t.triangles().count(), 1);
+
+    let cells = voronoi(&points, &Aabb::new(point2(-1.0, -1.0), point2(2.0, 2.0)));
+    assert_eq!(
+        cells[0].as_ref().unwrap().area(),
+        cells[4].as_ref().unwrap().area()
+    );
+    assert_eq!(
+        cells[1].as_ref().unwrap().area(),
+        cells[3].as_ref().unwrap().area()
+    );
+}
+
+#[test]
+fn cells_outside_the_bounds() {
+    let cells = voronoi::<UnknownUnit>(
+        &[point2(0.0, 0.0), point2(100.0, 0.0)],
+        &Aabb::new(point2(-1.0, -1.0), point2(1.0, 1.0)),
+    );
+    assert_eq!(cells[0].as_ref().unwrap().area(), 4.0);
+    assert!(cells[1].is_none());
+}
+
+#[test]
+fn constraint_through_points() {
+    // A constraint through the middle of a row of points is split at each of
+    // them.
+    let mut points = vec![point2(0.0, 0.0), point2(4.0, 0.0)];
+    points.extend((1..4).map(|i| point2(i as f64, 0.0)));
+    points.push(point2(2.0, 1.0));
+    points.push(point2(2.0, -1.0));
+    let t = DelaunayTriangulation::<UnknownUnit>::with_constraints(points, &[(0, 1)]);
+    assert!(t.is_constrained(0, 2));
+    assert!(t.is_constrained(2, 3));
+    assert!(t.is_constrained(3, 4));
+    assert!(t.is_constrained(4, 1));
+}
+
+#[test]
+#[should_panic]
+fn crossing_constraints() {
+    let points = vec![
+        point2(0.0, 0.0),
+        point2(1.0, 0.0),
+        point2(1.0, 1.0),
+        point2(0.0, 1.0),
+    ];
+    DelaunayTriangulation::<UnknownUnit>::with_constraints(points, &[(0, 2), (1, 3)]);
+}
@@ -0,0 +1,161 @@
+use euclid::point2;
+use fart_2d_geom::{line, ConvexPolygon, Line, Polygon, RelativeDirection};
+use quickcheck::{quickcheck, Arbitrary, Gen};
+use rand::distributions::{Distribution, Uniform};
+use rand::{rngs::SmallRng, SeedableRng};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct UnknownUnit;
+
+/// A random simple polygon, which is usually concave.
+#[derive(Clone, Debug)]
+struct SimplePolygon(Polygon<f64, UnknownUnit>);
+
+impl Arbitrary for SimplePolygon {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        loop {
+            let n = Uniform::new(3, 12).sample(g);
+            let p = Polygon::<i64, UnknownUnit>::random(
+                g,
+                &mut Uniform::new(-20, 20),
+                &mut Uniform::new(-20, 20),
+                n,
+            );
+            if p.is_simple() {
+                return SimplePolygon(p.cast());
+            }
+        }
+    }
+}
+
+/// A random line through the middle of the grid that the polygons are on.
+#[derive(Clone, Debug)]
+struct ArbitraryLine(Line<f64, UnknownUnit>);
+
+impl Arbitrary for ArbitraryLine {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let coordinate = Uniform::new(-20.0, 20.0);
+        let mut point = || point2(coordinate.sample(g), coordinate.sample(g));
+        ArbitraryLine(line(point(), point()))
+    }
+}
+
+/// A random convex polygon.
+#[derive(Clone, Debug)]
+struct ArbitraryConvexPolygon(ConvexPolygon<f64, UnknownUnit>);
+
+impl Arbitrary for ArbitraryConvexPolygon {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let coordinate = Uniform::new(-20.0, 20.0);
+        loop {
+            let points = (0..Uniform::new(3, 12).sample(g))
+                .map(|_| point2(coordinate.sample(g), coordinate.sample(g)))
+                .collect();
+            if let Some(hull) = ConvexPolygon::hull(points) {
+                return ArbitraryConvexPolygon(hull);
+            }
+        }
+    }
+}
+
+fn total_area(pieces: &[Polygon<f64, UnknownUnit>]) -> f64 {
+    pieces.iter().map(|p| p.area()).sum()
+}
+
+fn approx_eq(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-6 * a.abs().max(1.0)
+}
+
+/// Is every vertex of the polygon on the given side of the line, or within
+/// rounding of it?
+fn is_on_side(p: &Polygon<f64, UnknownUnit>, l: &Line<f64, UnknownUnit>, side: f64) -> bool {
+    let d = (l.b - l.a).normalize();
+    p.vertices()
+        .iter()
+        .all(|&v| d.cross(v - l.a) * side > -1e-9)
+}
+
+quickcheck! {
+    fn split_by_line_keeps_area(p: SimplePolygon, l: ArbitraryLine) -> bool {
+        let (p, l) = (p.0, l.0);
+        let pieces = p.split_by_line(&l);
+        approx_eq(total_area(&pieces), p.area())
+    }
+
+    fn split_by_line_separates_sides(p: SimplePolygon, l: ArbitraryLine) -> bool {
+        let (p, l) = (p.0, l.0);
+        let pieces = p.split_by_line(&l);
+
+        // The pieces on the left come first.
+        let left = pieces
+            .iter()
+            .take_while(|piece| {
+                piece
+                    .vertices()
+                    .iter()
+                    .any(|&v| l.relative_direction_of(v) == RelativeDirection::Left)
+                    && is_on_side(piece, &l, 1.0)
+            })
+            .count();
+        pieces[left..].iter().all(|piece| is_on_side(piece, &l, -1.0))
+    }
+
+    fn split_by_random_chord_keeps_area(p: SimplePolygon, seed: u64) -> bool {
+        let p = p.0;
+        let pieces = p.split_by_random_chord(&mut SmallRng::seed_from_u64(seed));
+        !pieces.is_empty() && approx_eq(total_area(&pieces), p.area())
+    }
+
+    fn split_by_random_chord_splits_convex_polygons(p: ArbitraryConvexPolygon, seed: u64) -> bool {
+        let p = p.0;
+        let pieces = p.split_by_random_chord(&mut SmallRng::seed_from_u64(seed));
+        pieces.len() == 2 && approx_eq(total_area(&pieces), p.area())
+    }
+
+    fn split_from_centroid_keeps_area(p: ArbitraryConvexPolygon, edges: Vec<usize>) -> bool {
+        let p = p.0;
+        let edges: Vec<usize> = edges.into_iter().map(|e| e % p.len()).collect();
+        let mut distinct = edges.clone();
+        distinct.sort();
+        distinct.dedup();
+
+        let pieces = p.split_from_centroid(&edges);
+        let expected = if distinct.len() < 2 { 1 } else { distinct.len() };
+        pieces.len() == expected
+            && approx_eq(total_area(&pieces), p.area())
+            && pieces.iter().all(|piece| piece.area() >= 0.0)
+    }
+}
+
+#[test]
+fn split_by_line_missing_the_polygon() {
+    let square = Polygon::<f64, UnknownUnit>::new(vec![
+        point2(0.0, 0.0),
+        point2(1.0, 0.0),
+        point2(1.0, 1.0),
+        point2(0.0, 1.0),
+    ]);
+    let pieces = square.split_by_line(&line(point2(5.0, 0.0), point2(5.0, 1.0)));
+    assert_eq!(pieces.len(), 1);
+    assert_eq!(pieces[0].area(), 1.0);
+
+    // Along one of its edges.
+    let pieces = square.split_by_line(&line(point2(0.0, 0.0), point2(1.0, 0.0)));
+    assert_eq!(pieces.len(), 1);
+    assert_eq!(pieces[0].area(), 1.0);
+}
+
+#[test]
+fn split_by_line_through_vertices() {
+    // A diamond, split through two opposite corners and touching the
+    // others.
+    let diamond = Polygon::<f64, UnknownUnit>::new(vec![
+        point2(0.0, -1.0),
+        point2(1.0, 0.0),
+        point2(0.0, 1.0),
+        point2(-1.0, 0.0),
+    ]);
+    let pieces = diamond.split_by_line(&line(point2(-1.0, 0.0), point2(1.0, 0.0)));
+    assert_eq!(pieces.len(), 2);
+    assert!(pieces.iter().all(|p| p.area() == 1.0 && p.len() == 3));
+}
@@ -0,0 +1,250 @@
+use euclid::{point2, Point2D};
+use fart_2d_geom::{stroke, JoinStyle, LineCap, Polygon, PolygonWithHoles};
+use quickcheck::{quickcheck, Arbitrary, Gen};
+use rand::distributions::{Distribution, Uniform};
+use std::f64::consts::PI;
+
+mod common;
+use common::{area, polygon, square, ArbitraryPolygon, UnknownUnit};
+
+#[derive(Clone, Copy, Debug)]
+struct ArbitraryDistance(f64);
+
+impl Arbitrary for ArbitraryDistance {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        ArbitraryDistance(Uniform::new(0.1, 5.0).sample(g))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ArbitraryPolyline(Vec<Point2D<f64, UnknownUnit>>);
+
+impl Arbitrary for ArbitraryPolyline {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let n = Uniform::new(1, 8).sample(g);
+        let coordinate = Uniform::new(-20, 20);
+        ArbitraryPolyline(
+            (0..n)
+                .map(|_| point2(coordinate.sample(g) as f64, coordinate.sample(g) as f64))
+                .collect(),
+        )
+    }
+}
+
+/// Random polygons are not always simple, and offsetting only keeps its
+/// distance from the boundary of simple polygons.
+fn is_simple(polygon: &Polygon<i64, UnknownUnit>) -> bool {
+    polygon.is_simple() && polygon.area() > 0
+}
+
+fn distance_to_segment(
+    a: Point2D<f64, UnknownUnit>,
+    b: Point2D<f64, UnknownUnit>,
+    p: Point2D<f64, UnknownUnit>,
+) -> f64 {
+    let d = b - a;
+    if d.square_length() == 0.0 {
+        return (p - a).length();
+    }
+    let t = ((p - a).dot(d) / d.square_length()).clamp(0.0, 1.0);
+    (a + d * t - p).length()
+}
+
+fn distance_to_boundary(polygon: &Polygon<f64, UnknownUnit>, p: Point2D<f64, UnknownUnit>) -> f64 {
+    polygon
+        .edges()
+        .map(|e| distance_to_segment(e.a, e.b, p))
+        .fold(f64::INFINITY, f64::min)
+}
+
+fn vertices(
+    polygons: &[PolygonWithHoles<f64, UnknownUnit>],
+) -> impl Iterator<Item = Point2D<f64, UnknownUnit>> + '_ {
+    polygons
+        .iter()
+        .flat_map(|p| Some(p.exterior()).into_iter().chain(p.holes()))
+        .flat_map(|ring| ring.vertices().iter().cloned())
+}
+
+/// Check that every vertex of offsetting the polygon with round joins is the
+/// offset distance away from its boundary.
+fn check_round_distance(polygon: Polygon<i64, UnknownUnit>, distance: f64) -> bool {
+    if !is_simple(&polygon) {
+        return true;
+    }
+    let polygon = polygon.cast::<f64>();
+
+    let result = polygon.offset(distance, JoinStyle::Round);
+    let mut ok = true;
+    for v in vertices(&result) {
+        let d = distance_to_boundary(&polygon, v);
+        if d > distance.abs() + 1e-6 || d < distance.abs() * 0.99 {
+            println!("vertex {:?} is {} from the boundary", v, d);
+            ok = false;
+        }
+    }
+    ok
+}
+
+quickcheck! {
+    fn growing_adds_area(p: ArbitraryPolygon, d: ArbitraryDistance) -> bool {
+        let p = p.0.cast::<f64>();
+        area(&p.offset(d.0, JoinStyle::Bevel)) >= p.area().abs() - 1e-6
+    }
+
+    fn shrinking_removes_area(p: ArbitraryPolygon, d: ArbitraryDistance) -> bool {
+        if !is_simple(&p.0) {
+            return true;
+        }
+        let p = p.0.cast::<f64>();
+        area(&p.offset(-d.0, JoinStyle::Miter { limit: 4.0 })) <= p.area() + 1e-6
+    }
+
+    fn growing_keeps_distance(p: ArbitraryPolygon, d: ArbitraryDistance) -> bool {
+        check_round_distance(p.0, d.0)
+    }
+
+    fn shrinking_keeps_distance(p: ArbitraryPolygon, d: ArbitraryDistance) -> bool {
+        check_round_distance(p.0, -d.0)
+    }
+
+    fn stroking_keeps_distance(p: ArbitraryPolyline, d: ArbitraryDistance) -> bool {
+        let polylines = [p.0];
+        let polyline = &polylines[0];
+        let result = stroke(&polylines, d.0 * 2.0, LineCap::Round, JoinStyle::Round);
+        let mut ok = !result.is_empty();
+        for v in vertices(&result) {
+            let distance = (0..polyline.len())
+                .map(|i| distance_to_segment(polyline[i], polyline[(i + 1).min(polyline.len() - 1)], v))
+                .fold(f64::INFINITY, f64::min);
+            if distance > d.0 + 1e-6 || distance < d.0 * 0.99 {
+                println!("vertex {:?} is {} from the polyline", v, distance);
+                ok = false;
+            }
+        }
+        ok
+    }
+}
+
+#[test]
+fn square_joins() {
+    let s = square(0.0, 0.0, 2.0);
+
+    let miter = s.offset(1.0, JoinStyle::Miter { limit: 2.0 });
+    assert_eq!(miter.len(), 1);
+    assert_eq!(miter[0].exterior().len(), 4);
+    assert_eq!(area(&miter), 16.0);
+
+    // A limit below sqrt(2) bevels right angles.
+    let beveled_miter = s.offset(1.0, JoinStyle::Miter { limit: 1.4 });
+    let bevel = s.offset(1.0, JoinStyle::Bevel);
+    assert_eq!(bevel[0].exterior().len(), 8);
+    assert_eq!(area(&bevel), 14.0);
+    assert_eq!(area(&beveled_miter), 14.0);
+
+    let round = s.offset(1.0, JoinStyle::Round);
+    assert!((area(&round) - (4.0 + 8.0 + PI)).abs() < 0.01);
+}
+
+#[test]
+fn shrink_away() {
+    let s = square(0.0, 0.0, 2.0);
+    assert_eq!(area(&s.offset(-0.5, JoinStyle::Round)), 1.0);
+    assert!(s.offset(-1.0, JoinStyle::Round).is_empty());
+    assert!(s.offset(-2.0, JoinStyle::Round).is_empty());
+}
+
+#[test]
+fn zero_distance() {
+    let s = square(0.0, 0.0, 2.0);
+    let same = s.offset(0.0, JoinStyle::Round);
+    assert_eq!(same.len(), 1);
+    assert_eq!(same[0].exterior().len(), 4);
+    assert_eq!(area(&same), 4.0);
+}
+
+#[test]
+fn sharp_spike() {
+    // A thin spike, whose miter would reach far beyond its tip.
+    let spike = polygon(&[(0.0, 0.0), (100.0, 1.0), (0.0, 2.0)]);
+    let grown = spike.offset(1.0, JoinStyle::Miter { limit: 4.0 });
+    assert_eq!(grown.len(), 1);
+    let max_x = grown[0]
+        .exterior()
+        .vertices()
+        .iter()
+        .map(|v| v.x)
+        .fold(0.0, f64::max);
+    assert!(max_x < 101.0);
+}
+
+#[test]
+fn growing_closes_concavities_into_holes() {
+    // A "C" shape, whose opening is narrower than its inside.
+    let c = polygon(&[
+        (0.0, 0.0),
+        (10.0, 0.0),
+        (10.0, 4.0),
+        (8.0, 4.0),
+        (8.0, 2.0),
+        (2.0, 2.0),
+        (2.0, 8.0),
+        (8.0, 8.0),
+        (8.0, 6.0),
+        (10.0, 6.0),
+        (10.0, 10.0),
+        (0.0, 10.0),
+    ]);
+    let grown = c.offset(1.5, JoinStyle::Miter { limit: 2.0 });
+    assert_eq!(grown.len(), 1);
+    assert_eq!(grown[0].holes().len(), 1);
+    assert_eq!(grown[0].holes()[0].area(), 3.0 * 3.0);
+}
+
+#[test]
+fn stroke_caps() {
+    let segment = [vec![point2(0.0, 0.0), point2(10.0, 0.0)]];
+    let outline = |cap| area(&stroke(&segment, 2.0, cap, JoinStyle::Bevel));
+    assert_eq!(outline(LineCap::Butt), 20.0);
+    assert_eq!(outline(LineCap::Square), 24.0);
+    assert!((outline(LineCap::Round) - (20.0 + PI)).abs() < 0.01);
+}
+
+#[test]
+fn stroke_dot() {
+    let dot = [vec![point2(5.0, 5.0), point2(5.0, 5.0)]];
+    let outline = |cap| area(&stroke(&dot, 2.0, cap, JoinStyle::Bevel));
+    assert_eq!(outline(LineCap::Butt), 0.0);
+    assert_eq!(outline(LineCap::Square), 4.0);
+    assert!((outline(LineCap::Round) - PI).abs() < 0.01);
+}
+
+#[test]
+fn stroke_doubling_back() {
+    // A polyline that goes out and comes straight back over itself is joined
+    // with a half circle at its far end.
+    let there_and_back = vec![point2(0.0, 0.0), point2(10.0, 0.0), point2(2.0, 0.0)];
+    let outline = stroke(&[there_and_back], 2.0, LineCap::Square, JoinStyle::Round);
+    assert_eq!(outline.len(), 1);
+    assert!((area(&outline) - (22.0 + PI / 2.0)).abs() < 0.01);
+
+    // Unless it comes back to where it started, and is closed.
+    let closed = vec![point2(0.0, 0.0), point2(10.0, 0.0), point2(0.0, 0.0)];
+    let outline = stroke(&[closed], 2.0, LineCap::Square, JoinStyle::Round);
+    assert!((area(&outline) - (20.0 + PI)).abs() < 0.01);
+}
+
+#[test]
+fn stroke_crossing_polylines() {
+    let horizontal = vec![point2(-5.0, 0.0), point2(5.0, 0.0)];
+    let vertical = vec![point2(0.0, -5.0), point2(0.0, 5.0)];
+    let outline = stroke(
+        &[horizontal, vertical],
+        2.0,
+        LineCap::Butt,
+        JoinStyle::Bevel,
+    );
+    assert_eq!(outline.len(), 1);
+    assert_eq!(outline[0].exterior().len(), 12);
+    assert_eq!(area(&outline), 20.0 + 20.0 - 4.0);
+}
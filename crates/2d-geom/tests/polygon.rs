@@ -0,0 +1,153 @@
+use euclid::{point2, Point2D};
+use fart_2d_geom::{ConvexPolygon, Polygon};
+use quickcheck::{quickcheck, Arbitrary, Gen};
+use rand::distributions::{Distribution, Uniform};
+use std::convert::TryFrom;
+
+mod common;
+use common::{ArbitraryEvenPolygon, UnknownUnit};
+
+type Point = Point2D<i64, UnknownUnit>;
+
+#[derive(Clone, Copy, Debug)]
+struct ArbitraryPoint(Point);
+
+impl Arbitrary for ArbitraryPoint {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let coordinate = Uniform::new(-45, 45);
+        ArbitraryPoint(point2(coordinate.sample(g), coordinate.sample(g)))
+    }
+}
+
+fn triangles(polygon: &Polygon<i64, UnknownUnit>) -> Vec<[Point; 3]> {
+    let mut triangles = vec![];
+    polygon
+        .clone()
+        .triangulate(|a, b, c| triangles.push([a, b, c]));
+    triangles
+}
+
+/// Is the point inside or on the boundary of the triangle? Triangulating
+/// collinear vertices yields degenerate triangles, which contain nothing.
+fn triangle_contains([a, b, c]: [Point; 3], p: Point) -> bool {
+    let orientation = (b - a).cross(c - a).signum();
+    orientation != 0
+        && [(a, b), (b, c), (c, a)]
+            .iter()
+            .all(|&(s, t)| (t - s).cross(p - s) * orientation >= 0)
+}
+
+fn is_on_boundary(polygon: &Polygon<i64, UnknownUnit>, p: Point) -> bool {
+    polygon.edges().any(|e| e.is_on(p))
+}
+
+quickcheck! {
+    fn contains_point_matches_triangulation(p: ArbitraryEvenPolygon, q: ArbitraryPoint) -> bool {
+        let (p, q) = (p.0, q.0);
+        if !p.is_simple() {
+            return true;
+        }
+        let in_triangle = !is_on_boundary(&p, q) && triangles(&p).into_iter().any(|t| triangle_contains(t, q));
+        p.contains_point(q) == in_triangle && p.contains_point_even_odd(q) == in_triangle
+    }
+
+    fn winding_number_is_zero_or_one_for_simple_polygons(p: ArbitraryEvenPolygon, q: ArbitraryPoint) -> bool {
+        !p.0.is_simple() || p.0.winding_number(q.0) == 0 || p.0.winding_number(q.0) == 1
+    }
+
+    fn even_odd_is_winding_number_parity(p: ArbitraryEvenPolygon, q: ArbitraryPoint) -> bool {
+        p.0.contains_point_even_odd(q.0) == (p.0.winding_number(q.0) % 2 != 0)
+    }
+
+    fn is_convex_matches_diagonals(p: ArbitraryEvenPolygon) -> bool {
+        // A simple polygon is convex if and only if all of its diagonals are
+        // inside of it.
+        let p = p.0;
+        if !p.is_simple() || p.area() == 0 {
+            return true;
+        }
+        let n = p.len();
+        let diagonals_inside = (0..n).all(|i| {
+            (0..n).all(|j| {
+                let m = point2(
+                    (p.vertices()[i].x + p.vertices()[j].x) / 2,
+                    (p.vertices()[i].y + p.vertices()[j].y) / 2,
+                );
+                p.contains_point(m) || is_on_boundary(&p, m)
+            })
+        });
+        p.is_convex() == diagonals_inside
+            && ConvexPolygon::try_from(p.clone()).is_ok() == diagonals_inside
+    }
+
+    fn centroid_matches_triangulation(p: ArbitraryEvenPolygon) -> bool {
+        let p = p.0;
+        if !p.is_simple() || p.area() == 0 {
+            return true;
+        }
+        let (mut x, mut y, mut area) = (0.0, 0.0, 0.0);
+        for [a, b, c] in triangles(&p) {
+            let w = ((b - a).cross(c - a) as f64).abs();
+            x += w * (a.x + b.x + c.x) as f64 / 3.0;
+            y += w * (a.y + b.y + c.y) as f64 / 3.0;
+            area += w;
+        }
+        let expected = point2(x / area, y / area);
+        (p.cast::<f64>().centroid() - expected).length() < 1e-9
+    }
+}
+
+#[test]
+fn degenerate_polygons_are_not_simple() {
+    // Doubles back on itself.
+    let spike: Polygon<i64, UnknownUnit> = Polygon::new(vec![
+        point2(0, 0),
+        point2(10, 0),
+        point2(5, 0),
+        point2(0, 5),
+    ]);
+    assert!(!spike.is_simple());
+    assert!(!spike.is_convex());
+
+    // Repeats a vertex.
+    let repeated: Polygon<i64, UnknownUnit> = Polygon::new(vec![
+        point2(0, 0),
+        point2(10, 0),
+        point2(10, 0),
+        point2(0, 5),
+    ]);
+    assert!(!repeated.is_simple());
+
+    // Touches itself at a vertex.
+    let pinched: Polygon<i64, UnknownUnit> = Polygon::new(vec![
+        point2(0, 0),
+        point2(10, 0),
+        point2(5, 5),
+        point2(10, 10),
+        point2(0, 10),
+        point2(5, 5),
+    ]);
+    assert!(!pinched.is_simple());
+
+    // Collinear vertices along an edge are fine.
+    let square: Polygon<i64, UnknownUnit> = Polygon::new(vec![
+        point2(0, 0),
+        point2(5, 0),
+        point2(10, 0),
+        point2(10, 10),
+        point2(0, 10),
+    ]);
+    assert!(square.is_simple());
+    assert!(square.is_convex());
+}
+
+#[test]
+fn perimeter() {
+    let square: Polygon<f64, UnknownUnit> = Polygon::new(vec![
+        point2(0.0, 0.0),
+        point2(2.0, 0.0),
+        point2(2.0, 2.0),
+        point2(0.0, 2.0),
+    ]);
+    assert_eq!(square.perimeter(), 8.0);
+}
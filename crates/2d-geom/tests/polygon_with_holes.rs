@@ -0,0 +1,137 @@
+use euclid::{point2, Point2D};
+use fart_2d_geom::{Polygon, PolygonWithHoles};
+use quickcheck::{quickcheck, Arbitrary, Gen};
+use rand::distributions::{Distribution, Uniform};
+use std::convert::TryFrom;
+use std::f64::consts::PI;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct UnknownUnit;
+
+type Point = Point2D<f64, UnknownUnit>;
+
+/// A random polygon that is star-shaped around `center`, with `n` vertices
+/// between `min` and `max` away from it.
+fn star<G: Gen>(
+    g: &mut G,
+    center: Point,
+    n: usize,
+    min: f64,
+    max: f64,
+) -> Polygon<f64, UnknownUnit> {
+    let step = 2.0 * PI / n as f64;
+    let jitter = Uniform::new(0.0, 0.8 * step);
+    let angles: Vec<f64> = (0..n).map(|i| i as f64 * step + jitter.sample(g)).collect();
+
+    let radius = Uniform::new(min, max);
+    Polygon::new(
+        angles
+            .into_iter()
+            .map(|a| {
+                let r = radius.sample(g);
+                point2(center.x + r * a.cos(), center.y + r * a.sin())
+            })
+            .collect(),
+    )
+}
+
+/// A random star-shaped polygon with up to three star-shaped holes, each around
+/// a different point, and none of which overlap.
+#[derive(Clone, Debug)]
+struct ArbitraryPolygonWithHoles(PolygonWithHoles<f64, UnknownUnit>);
+
+impl Arbitrary for ArbitraryPolygonWithHoles {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let n = Uniform::new(8, 16).sample(g);
+        let exterior = star(g, point2(0.0, 0.0), n, 15.0, 20.0);
+        let centers = [point2(-7.0, 0.0), point2(7.0, 0.0), point2(0.0, 7.0)];
+        let holes = Uniform::new(0, centers.len() + 1).sample(g);
+        let holes = centers[..holes]
+            .iter()
+            .map(|&c| {
+                let n = Uniform::new(4, 8).sample(g);
+                star(g, c, n, 1.0, 3.0)
+            })
+            .collect();
+        ArbitraryPolygonWithHoles(PolygonWithHoles::new(exterior, holes))
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct ArbitraryPoint(Point);
+
+impl Arbitrary for ArbitraryPoint {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let coordinate = Uniform::new(-22.0, 22.0);
+        ArbitraryPoint(point2(coordinate.sample(g), coordinate.sample(g)))
+    }
+}
+
+fn triangles(polygon: &PolygonWithHoles<f64, UnknownUnit>) -> Vec<[Point; 3]> {
+    let mut triangles = vec![];
+    polygon.triangulate(|a, b, c| triangles.push([a, b, c]));
+    triangles
+}
+
+fn triangle_area([a, b, c]: [Point; 3]) -> f64 {
+    (b - a).cross(c - a) / 2.0
+}
+
+fn triangle_contains([a, b, c]: [Point; 3], p: Point) -> bool {
+    (b - a).cross(p - a) > 0.0 && (c - b).cross(p - b) > 0.0 && (a - c).cross(p - c) > 0.0
+}
+
+quickcheck! {
+    fn triangulation_covers_area(p: ArbitraryPolygonWithHoles) -> bool {
+        let triangles = triangles(&p.0);
+        let area: f64 = triangles.iter().map(|&t| triangle_area(t)).sum();
+        triangles.iter().all(|&t| triangle_area(t) > 0.0) && (area - p.0.area()).abs() < 1e-9
+    }
+
+    fn triangulation_matches_contains_point(p: ArbitraryPolygonWithHoles, q: ArbitraryPoint) -> bool {
+        let in_triangle = triangles(&p.0).into_iter().any(|t| triangle_contains(t, q.0));
+        in_triangle == p.0.contains_point(q.0)
+    }
+
+    fn contains_point_matches_rings(p: ArbitraryPolygonWithHoles, q: ArbitraryPoint) -> bool {
+        let inside = |ring: &Polygon<f64, UnknownUnit>| {
+            PolygonWithHoles::from(ring.clone()).contains_point(q.0)
+        };
+        p.0.contains_point(q.0)
+            == (inside(p.0.exterior()) && !p.0.holes().iter().any(inside))
+    }
+}
+
+#[test]
+fn contains_point_on_vertices() {
+    let triangle: Polygon<i64, UnknownUnit> =
+        Polygon::new(vec![point2(0, 0), point2(10, 0), point2(0, 10)]);
+    let triangle = PolygonWithHoles::from(triangle);
+    for &v in triangle.exterior().vertices() {
+        assert!(!triangle.contains_point(v));
+    }
+    assert!(triangle.contains_point(point2(1, 1)));
+    assert!(!triangle.contains_point(point2(6, 6)));
+    assert!(!triangle.contains_point(point2(-1, 0)));
+    assert!(!triangle.contains_point(point2(11, 0)));
+}
+
+#[test]
+fn polygon_round_trip() {
+    let square = |min, max| -> Polygon<i64, UnknownUnit> {
+        Polygon::new(vec![
+            point2(min, min),
+            point2(max, min),
+            point2(max, max),
+            point2(min, max),
+        ])
+    };
+
+    let plain = PolygonWithHoles::from(square(0, 10));
+    let polygon = Polygon::try_from(plain).unwrap();
+    assert_eq!(polygon.vertices(), square(0, 10).vertices());
+
+    let frame = PolygonWithHoles::new(square(0, 10), vec![square(2, 8)]);
+    let frame = Polygon::try_from(frame).unwrap_err();
+    assert_eq!(frame.holes().len(), 1);
+}
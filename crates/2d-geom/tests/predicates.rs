@@ -0,0 +1,206 @@
+use euclid::{point2, vec2, Point2D, Vector2D};
+use fart_2d_geom::{
+    in_circle, is_counter_clockwise, orientation, ConvexPolygon, Polygon, RelativeDirection,
+};
+use quickcheck::{quickcheck, Arbitrary, Gen};
+use rand::distributions::{Distribution, Uniform};
+use std::cmp::Ordering;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct UnknownUnit;
+
+type Point = Point2D<f64, UnknownUnit>;
+
+/// Three nearly collinear points, which are all multiples of `2^-40`.
+///
+/// One point is close to the origin, and the others are far away, so that the
+/// differences between their coordinates can't be computed exactly with
+/// `f64`s, and the first point is nudged off of the line through the others by
+/// less than the rounding error of their cross product.
+#[derive(Clone, Copy, Debug)]
+struct NearlyCollinear([Point; 3]);
+
+const SCALE: f64 = (1u64 << 40) as f64;
+
+impl Arbitrary for NearlyCollinear {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let step = Uniform::new_inclusive(-(1i64 << 8), 1 << 8);
+        let multiple = Uniform::new_inclusive(-(1i64 << 12), 1 << 12);
+        let fraction = Uniform::new(0, 1i64 << 30).sample(g) as f64 / (1u64 << 30) as f64;
+        let nudge = Uniform::new_inclusive(-1, 1);
+        let d: Vector2D<f64, UnknownUnit> = vec2(step.sample(g) as f64, step.sample(g) as f64);
+        let a = (d * fraction).to_point()
+            + vec2(nudge.sample(g) as f64, nudge.sample(g) as f64) / SCALE;
+        let b = (d * multiple.sample(g) as f64).to_point();
+        let c = (d * multiple.sample(g) as f64).to_point();
+        NearlyCollinear([a, b, c])
+    }
+}
+
+/// Four nearly cocircular points, with integer coordinates too large for
+/// their in-circle determinant to be computed exactly with `f64`s.
+#[derive(Clone, Copy, Debug)]
+struct NearlyCocircular([Point; 4]);
+
+impl Arbitrary for NearlyCocircular {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let center = Uniform::new(-(1i64 << 27), 1 << 27);
+        let offset = Uniform::new(-(1i64 << 26), 1 << 26);
+        let (x, y) = (center.sample(g), center.sample(g));
+        let (p, q) = (offset.sample(g), offset.sample(g));
+
+        // The circle's reflections of one point on it are on it too.
+        let mut on_circle = || {
+            let (dx, dy) = match Uniform::new(0, 8).sample(g) {
+                0 => (p, q),
+                1 => (-p, q),
+                2 => (p, -q),
+                3 => (-p, -q),
+                4 => (q, p),
+                5 => (-q, p),
+                6 => (q, -p),
+                _ => (-q, -p),
+            };
+            point2((x + dx) as f64, (y + dy) as f64)
+        };
+        let (a, b, c, d) = (on_circle(), on_circle(), on_circle(), on_circle());
+        let nudge = Uniform::new_inclusive(-1, 1);
+        let d = d + vec2(nudge.sample(g) as f64, nudge.sample(g) as f64);
+        NearlyCocircular([a, b, c, d])
+    }
+}
+
+fn exact_orientation(a: Point, b: Point, c: Point) -> RelativeDirection {
+    // All of the points' coordinates are multiples of `2^-40`, and small
+    // enough to be integers when scaled up by `2^40`.
+    let exact = |p: Point| ((p.x * SCALE) as i128, (p.y * SCALE) as i128);
+    let ((ax, ay), (bx, by), (cx, cy)) = (exact(a), exact(b), exact(c));
+    match ((bx - ax) * (cy - ay) - (cx - ax) * (by - ay)).cmp(&0) {
+        Ordering::Greater => RelativeDirection::Left,
+        Ordering::Equal => RelativeDirection::Collinear,
+        Ordering::Less => RelativeDirection::Right,
+    }
+}
+
+fn exact_in_circle(a: Point, b: Point, c: Point, d: Point) -> Ordering {
+    // These points have integer coordinates.
+    let relative = |p: Point| (p.x as i128 - d.x as i128, p.y as i128 - d.y as i128);
+    let ((adx, ady), (bdx, bdy), (cdx, cdy)) = (relative(a), relative(b), relative(c));
+    let orientation = ((bdx - adx) * (cdy - ady) - (cdx - adx) * (bdy - ady)).signum();
+    if orientation == 0 {
+        return Ordering::Greater;
+    }
+    let lift = |x: i128, y: i128| x * x + y * y;
+    let det = lift(adx, ady) * (bdx * cdy - cdx * bdy)
+        + lift(bdx, bdy) * (cdx * ady - adx * cdy)
+        + lift(cdx, cdy) * (adx * bdy - bdx * ady);
+    // Positive determinants are inside.
+    0.cmp(&(det * orientation))
+}
+
+/// A polygon around the edges of a square, with many vertices along each
+/// side that are all nearly collinear.
+#[derive(Clone, Debug)]
+struct NearlySquare(Polygon<f64, UnknownUnit>);
+
+impl Arbitrary for NearlySquare {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let n = Uniform::new(1, 20);
+        let along = Uniform::new(0.0, 1.0);
+        let noise = Uniform::new(-1e-12, 1e-12);
+        let corners: Vec<Point> = vec![
+            point2(0.0, 0.0),
+            point2(1.0, 0.0),
+            point2(1.0, 1.0),
+            point2(0.0, 1.0),
+        ];
+        let mut vertices = vec![];
+        for i in 0..4 {
+            let (a, b) = (corners[i], corners[(i + 1) % 4]);
+            let normal = vec2(b.y - a.y, a.x - b.x);
+            let mut ts: Vec<f64> = (0..n.sample(g)).map(|_| along.sample(g)).collect();
+            ts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            vertices.push(a);
+            vertices.extend(
+                ts.into_iter()
+                    .map(|t| a.lerp(b, t) + normal * noise.sample(g)),
+            );
+        }
+        vertices.dedup();
+        NearlySquare(Polygon::new(vertices))
+    }
+}
+
+quickcheck! {
+    fn orientation_is_exact(p: NearlyCollinear) -> bool {
+        let [a, b, c] = p.0;
+        orientation(a, b, c) == exact_orientation(a, b, c)
+    }
+
+    fn orientation_is_consistent(p: NearlyCollinear) -> bool {
+        let [a, b, c] = p.0;
+        let o = orientation(a, b, c);
+        let reversed = match o {
+            RelativeDirection::Left => RelativeDirection::Right,
+            RelativeDirection::Collinear => RelativeDirection::Collinear,
+            RelativeDirection::Right => RelativeDirection::Left,
+        };
+        orientation(b, c, a) == o
+            && orientation(c, a, b) == o
+            && orientation(b, a, c) == reversed
+            && orientation(a, c, b) == reversed
+    }
+
+    fn is_counter_clockwise_is_exact(p: NearlyCollinear) -> bool {
+        let [a, b, c] = p.0;
+        let clockwise = exact_orientation(a, b, c) == RelativeDirection::Right;
+        is_counter_clockwise(&[a, b, c]) != clockwise
+            && is_counter_clockwise(&[b, c, a]) != clockwise
+    }
+
+    fn in_circle_is_exact(p: NearlyCocircular) -> bool {
+        let [a, b, c, d] = p.0;
+        in_circle(a, b, c, d) == exact_in_circle(a, b, c, d)
+            && in_circle(c, b, a, d) == exact_in_circle(a, b, c, d)
+    }
+
+    fn in_circle_of_integers_does_not_overflow(p: NearlyCocircular) -> bool {
+        let [a, b, c, d] = p.0;
+        let [ai, bi, ci, di] = [a.cast::<i32>(), b.cast(), c.cast(), d.cast()];
+        in_circle(ai, bi, ci, di) == exact_in_circle(a, b, c, d)
+            && orientation(ai, bi, ci) == orientation(a, b, c)
+    }
+
+    fn hull_of_nearly_collinear_points_is_convex(p: NearlyCollinear) -> bool {
+        let [a, b, c] = p.0;
+        let mid = a.lerp(b, 0.5);
+        let points = vec![a, b, c, point2(mid.x.round(), mid.y.round())];
+        match ConvexPolygon::hull(points.clone()) {
+            None => true,
+            Some(hull) => {
+                let vertices = hull.vertices();
+                let n = vertices.len();
+                (0..n).all(|i| {
+                    exact_orientation(vertices[i], vertices[(i + 1) % n], vertices[(i + 2) % n])
+                        == RelativeDirection::Left
+                }) && points.iter().all(|&p| {
+                    (0..n).all(|i| {
+                        exact_orientation(vertices[i], vertices[(i + 1) % n], p)
+                            != RelativeDirection::Right
+                    })
+                })
+            }
+        }
+    }
+
+    fn triangulate_nearly_collinear_polygons(p: NearlySquare) -> bool {
+        let p = p.0;
+        let mut count = 0;
+        let mut area = 0.0;
+        p.clone().triangulate(|a, b, c| {
+            count += 1;
+            area += (b - a).cross(c - a).abs() / 2.0;
+        });
+        count == p.len() - 2 && (area - 1.0).abs() < 1e-9
+    }
+}
@@ -0,0 +1,182 @@
+use euclid::{point2, Point2D};
+use fart_2d_geom::{simplify_polyline, Polygon, SimplifyAlgorithm};
+use quickcheck::{quickcheck, Arbitrary, Gen};
+use rand::distributions::{Distribution, Uniform};
+
+mod common;
+use common::{ArbitraryStar, UnknownUnit};
+
+type Point = Point2D<f64, UnknownUnit>;
+
+/// A random walk, which wanders off in random directions, with a little noise
+/// along the way.
+#[derive(Clone, Debug)]
+struct ArbitraryPolyline(Vec<Point>);
+
+impl Arbitrary for ArbitraryPolyline {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let n = Uniform::new(0, 200).sample(g);
+        let step = Uniform::new(-1.0, 1.0);
+        let noise = Uniform::new(-0.05, 0.05);
+        let mut direction = (step.sample(g), step.sample(g));
+        let mut p = point2(0.0, 0.0);
+        let mut polyline = vec![];
+        for _ in 0..n {
+            if Uniform::new(0, 10).sample(g) == 0 {
+                direction = (step.sample(g), step.sample(g));
+            }
+            p = point2(
+                p.x + direction.0 + noise.sample(g),
+                p.y + direction.1 + noise.sample(g),
+            );
+            polyline.push(p);
+        }
+        ArbitraryPolyline(polyline)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct ArbitraryTolerance(f64);
+
+impl Arbitrary for ArbitraryTolerance {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        ArbitraryTolerance(Uniform::new(0.01, 3.0).sample(g))
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct ArbitraryAlgorithm(SimplifyAlgorithm);
+
+impl Arbitrary for ArbitraryAlgorithm {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        ArbitraryAlgorithm(if bool::arbitrary(g) {
+            SimplifyAlgorithm::RamerDouglasPeucker
+        } else {
+            SimplifyAlgorithm::VisvalingamWhyatt
+        })
+    }
+}
+
+fn distance(p: Point, a: Point, b: Point) -> f64 {
+    let ab = b - a;
+    if ab.square_length() == 0.0 {
+        return (p - a).length();
+    }
+    let t = ((p - a).dot(ab) / ab.square_length()).clamp(0.0, 1.0);
+    (p - (a + ab * t)).length()
+}
+
+fn triangle_area(a: Point, b: Point, c: Point) -> f64 {
+    ((b - a).cross(c - a) / 2.0).abs()
+}
+
+/// Is `sub` made of some of `points`, in the same order?
+fn is_subsequence(sub: &[Point], points: &[Point]) -> bool {
+    let mut points = points.iter();
+    sub.iter().all(|s| points.any(|p| p == s))
+}
+
+quickcheck! {
+    fn keeps_endpoints(p: ArbitraryPolyline, t: ArbitraryTolerance, a: ArbitraryAlgorithm) -> bool {
+        let simple = simplify_polyline(&p.0, t.0, a.0);
+        simple.first() == p.0.first()
+            && simple.last() == p.0.last()
+            && simple.len() <= p.0.len()
+            && is_subsequence(&simple, &p.0)
+    }
+
+    fn ramer_douglas_peucker_is_within_tolerance(p: ArbitraryPolyline, t: ArbitraryTolerance) -> bool {
+        // Every point of the original is within tolerance of the part of the
+        // simplified polyline that replaced it.
+        let simple = simplify_polyline(&p.0, t.0, SimplifyAlgorithm::RamerDouglasPeucker);
+        let mut segment = 0;
+        p.0.iter().all(|&q| {
+            if segment + 1 < simple.len() && q == simple[segment + 1] {
+                segment += 1;
+            }
+            segment + 1 >= simple.len()
+                || distance(q, simple[segment], simple[segment + 1]) <= t.0 + 1e-9
+        })
+    }
+
+    fn visvalingam_whyatt_keeps_big_triangles(p: ArbitraryPolyline, t: ArbitraryTolerance) -> bool {
+        let simple = simplify_polyline(&p.0, t.0, SimplifyAlgorithm::VisvalingamWhyatt);
+        simple
+            .windows(3)
+            .all(|w| triangle_area(w[0], w[1], w[2]) >= t.0 * t.0)
+    }
+
+    fn simplify_polygon(p: ArbitraryStar, t: ArbitraryTolerance, a: ArbitraryAlgorithm) -> bool {
+        let simple = p.0.simplify(t.0, a.0);
+        let vertices = p.0.vertices();
+        // The simplified polygon may start at a different vertex.
+        let rotations = (0..vertices.len()).any(|i| {
+            let rotated: Vec<_> = vertices[i..].iter().chain(&vertices[..i]).cloned().collect();
+            is_subsequence(simple.vertices(), &rotated)
+        });
+        simple.len() >= 3 && simple.len() <= p.0.len() && rotations
+    }
+}
+
+#[test]
+fn short_polylines() {
+    for &algorithm in &[
+        SimplifyAlgorithm::RamerDouglasPeucker,
+        SimplifyAlgorithm::VisvalingamWhyatt,
+    ] {
+        let empty: Vec<Point> = vec![];
+        assert_eq!(simplify_polyline(&empty, 1.0, algorithm), empty);
+
+        let line: Vec<Point> = vec![point2(0.0, 0.0), point2(1.0, 0.0)];
+        assert_eq!(simplify_polyline(&line, 1.0, algorithm), line);
+
+        let collinear: Vec<Point> = vec![point2(0.0, 0.0), point2(1.0, 0.0), point2(2.0, 0.0)];
+        assert_eq!(
+            simplify_polyline(&collinear, 0.01, algorithm),
+            vec![point2(0.0, 0.0), point2(2.0, 0.0)]
+        );
+    }
+}
+
+#[test]
+fn closed_polylines_stay_closed() {
+    let ring: Vec<Point> = vec![
+        point2(0.0, 0.0),
+        point2(5.0, 0.1),
+        point2(10.0, 0.0),
+        point2(10.0, 10.0),
+        point2(0.0, 10.0),
+        point2(0.0, 0.0),
+    ];
+    for &algorithm in &[
+        SimplifyAlgorithm::RamerDouglasPeucker,
+        SimplifyAlgorithm::VisvalingamWhyatt,
+    ] {
+        let simple = simplify_polyline(&ring, 1.0, algorithm);
+        assert_eq!(
+            simple,
+            vec![
+                point2(0.0, 0.0),
+                point2(10.0, 0.0),
+                point2(10.0, 10.0),
+                point2(0.0, 10.0),
+                point2(0.0, 0.0),
+            ]
+        );
+    }
+}
+
+#[test]
+fn polygons_keep_three_vertices() {
+    let triangle = Polygon::<f64, UnknownUnit>::new(vec![
+        point2(0.0, 0.0),
+        point2(1.0, 0.0),
+        point2(0.5, 0.1),
+    ]);
+    for &algorithm in &[
+        SimplifyAlgorithm::RamerDouglasPeucker,
+        SimplifyAlgorithm::VisvalingamWhyatt,
+    ] {
+        assert_eq!(triangle.simplify(100.0, algorithm).len(), 3);
+    }
+}
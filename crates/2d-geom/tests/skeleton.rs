@@ -0,0 +1,185 @@
+use euclid::{point2, Point2D};
+use fart_2d_geom::{ConvexPolygon, Polygon, SkeletonLine};
+use quickcheck::{quickcheck, Arbitrary, Gen};
+use rand::distributions::{Distribution, Uniform};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct UnknownUnit;
+
+/// A random simple polygon on a small grid, which often has parallel edges
+/// and other degeneracies.
+#[derive(Clone, Debug)]
+struct GridPolygon(Polygon<f64, UnknownUnit>);
+
+impl Arbitrary for GridPolygon {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        loop {
+            let n = Uniform::new(3, 12).sample(g);
+            let p = Polygon::<i64, UnknownUnit>::random(
+                g,
+                &mut Uniform::new(-10, 10),
+                &mut Uniform::new(-10, 10),
+                n,
+            );
+            if p.is_simple() {
+                return GridPolygon(p.cast());
+            }
+        }
+    }
+}
+
+/// A random simple polygon anywhere, which is in general position.
+#[derive(Clone, Debug)]
+struct RandomPolygon(Polygon<f64, UnknownUnit>);
+
+impl Arbitrary for RandomPolygon {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        loop {
+            let n = Uniform::new(3, 16).sample(g);
+            let p = Polygon::<f64, UnknownUnit>::random(
+                g,
+                &mut Uniform::new(-10.0, 10.0),
+                &mut Uniform::new(-10.0, 10.0),
+                n,
+            );
+            if p.is_simple() {
+                return RandomPolygon(p);
+            }
+        }
+    }
+}
+
+/// A random convex polygon.
+#[derive(Clone, Debug)]
+struct RandomHull(ConvexPolygon<f64, UnknownUnit>);
+
+impl Arbitrary for RandomHull {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let coordinate = Uniform::new(-10.0, 10.0);
+        loop {
+            let points = (0..Uniform::new(3, 16).sample(g))
+                .map(|_| point2(coordinate.sample(g), coordinate.sample(g)))
+                .collect();
+            if let Some(hull) = ConvexPolygon::hull(points) {
+                return RandomHull(hull);
+            }
+        }
+    }
+}
+
+const TOLERANCE: f64 = 1e-6;
+
+fn distance_to_boundary(p: &Polygon<f64, UnknownUnit>, point: Point2D<f64, UnknownUnit>) -> f64 {
+    p.edges()
+        .map(|e| {
+            let d = e.b - e.a;
+            let t = ((point - e.a).dot(d) / d.square_length()).clamp(0.0, 1.0);
+            (point - e.a.lerp(e.b, t)).length()
+        })
+        .fold(f64::INFINITY, f64::min)
+}
+
+fn ends(skeleton: &[SkeletonLine<UnknownUnit>]) -> Vec<(Point2D<f64, UnknownUnit>, f64)> {
+    skeleton
+        .iter()
+        .flat_map(|s| vec![(s.line.a, s.a_offset), (s.line.b, s.b_offset)])
+        .collect()
+}
+
+fn check_straight_skeleton(p: &Polygon<f64, UnknownUnit>) -> bool {
+    let skeleton = p.straight_skeleton();
+
+    // Every vertex starts a line.
+    let starts = p
+        .vertices()
+        .iter()
+        .all(|&v| skeleton.iter().any(|s| s.line.a == v && s.a_offset == 0.0));
+
+    // The lines point inwards.
+    let inwards = skeleton
+        .iter()
+        .all(|s| s.a_offset <= s.b_offset + TOLERANCE);
+
+    // Every node is inside the polygon, no closer to its boundary than its
+    // offset, and exactly its offset from at least two edges' lines.
+    let nodes = ends(&skeleton).into_iter().all(|(point, offset)| {
+        let on_lines = p
+            .edges()
+            .filter(|e| {
+                let d = (e.b - e.a).normalize();
+                (d.cross(point - e.a) - offset).abs() < TOLERANCE
+            })
+            .count();
+        (p.contains_point(point) || distance_to_boundary(p, point) < TOLERANCE)
+            && offset >= 0.0
+            && offset <= distance_to_boundary(p, point) + TOLERANCE
+            && on_lines >= 2
+    });
+
+    starts && inwards && nodes
+}
+
+quickcheck! {
+    fn straight_skeleton_of_grid_polygons(p: GridPolygon) -> bool {
+        check_straight_skeleton(&p.0)
+    }
+
+    fn straight_skeleton_of_random_polygons(p: RandomPolygon) -> bool {
+        let p = p.0;
+        // In general position, the skeleton is a tree with a leaf at each
+        // vertex, and every other node joins three lines.
+        check_straight_skeleton(&p) && p.straight_skeleton().len() == 2 * p.len() - 3
+    }
+
+    fn straight_skeleton_of_convex_polygons_is_distance_to_boundary(p: RandomHull) -> bool {
+        let p = p.0;
+        check_straight_skeleton(&p)
+            && ends(&p.straight_skeleton())
+                .into_iter()
+                .all(|(point, offset)| (distance_to_boundary(&p, point) - offset).abs() < TOLERANCE)
+    }
+
+    fn medial_axis_is_inside_and_offsets_are_distance_to_boundary(p: GridPolygon) -> bool {
+        let p = p.0;
+        let spacing = 0.5;
+        ends(&p.medial_axis(spacing)).into_iter().all(|(point, offset)| {
+            p.contains_point(point) && (distance_to_boundary(&p, point) - offset).abs() <= spacing
+        })
+    }
+}
+
+#[test]
+fn straight_skeleton_of_an_l_shape() {
+    let l = Polygon::<f64, UnknownUnit>::new(vec![
+        point2(0.0, 0.0),
+        point2(4.0, 0.0),
+        point2(4.0, 2.0),
+        point2(2.0, 2.0),
+        point2(2.0, 4.0),
+        point2(0.0, 4.0),
+    ]);
+    let skeleton = l.straight_skeleton();
+    assert!(check_straight_skeleton(&l));
+
+    // The reflex corner's line runs diagonally in to meet the others.
+    assert!(skeleton.iter().any(|s| s.line.a == point2(2.0, 2.0)
+        && s.line.b == point2(1.0, 1.0)
+        && s.b_offset == 1.0));
+    let deepest = skeleton.iter().map(|s| s.b_offset).fold(0.0, f64::max);
+    assert_eq!(deepest, 1.0);
+}
+
+#[test]
+fn straight_skeleton_of_a_square_meets_in_the_middle() {
+    let square = Polygon::<f64, UnknownUnit>::new(vec![
+        point2(0.0, 0.0),
+        point2(2.0, 0.0),
+        point2(2.0, 2.0),
+        point2(0.0, 2.0),
+    ]);
+    let skeleton = square.straight_skeleton();
+    assert_eq!(skeleton.len(), 4);
+    assert!(skeleton
+        .iter()
+        .all(|s| s.line.b == point2(1.0, 1.0) && s.b_offset == 1.0));
+}
@@ -0,0 +1,152 @@
+use euclid::{point2, Point2D};
+use fart_2d_geom::{catmull_rom, chaikin, CatmullRom};
+use fart_aabb::ToAabb;
+use quickcheck::{quickcheck, Arbitrary, Gen};
+use rand::distributions::{Distribution, Uniform};
+
+mod common;
+use common::{ArbitraryStar, UnknownUnit};
+
+type Point = Point2D<f64, UnknownUnit>;
+
+/// A random sequence of points, without consecutive duplicates.
+#[derive(Clone, Debug)]
+struct ArbitraryPoints(Vec<Point>);
+
+impl Arbitrary for ArbitraryPoints {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let n = Uniform::new(0, 30).sample(g);
+        let coordinate = Uniform::new(-100.0, 100.0);
+        let mut points = vec![];
+        for _ in 0..n {
+            points.push(point2(coordinate.sample(g), coordinate.sample(g)));
+        }
+        points.dedup();
+        ArbitraryPoints(points)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct ArbitraryCatmullRom(CatmullRom);
+
+impl Arbitrary for ArbitraryCatmullRom {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        ArbitraryCatmullRom(match Uniform::new(0, 3).sample(g) {
+            0 => CatmullRom::Uniform,
+            1 => CatmullRom::Centripetal,
+            _ => CatmullRom::Chordal,
+        })
+    }
+}
+
+fn close(a: Point, b: Point) -> bool {
+    (a - b).length() < 1e-9
+}
+
+/// Are the tangents where consecutive curves meet parallel, and pointing the
+/// same way?
+fn is_smooth(a: Point, b: Point, c: Point) -> bool {
+    let (u, v) = (b - a, c - b);
+    u.cross(v).abs() <= 1e-6 * u.length() * v.length() && u.dot(v) >= 0.0
+}
+
+quickcheck! {
+    fn chaikin_keeps_endpoints(p: ArbitraryPoints, iterations: u8) -> bool {
+        let iterations = (iterations % 4) as usize;
+        let smooth = chaikin(&p.0, iterations);
+        let expected_len = if p.0.len() < 3 {
+            p.0.len()
+        } else {
+            (0..iterations).fold(p.0.len(), |n, _| 2 * n - 2)
+        };
+        smooth.first() == p.0.first() && smooth.last() == p.0.last() && smooth.len() == expected_len
+    }
+
+    fn chaikin_polygon_stays_in_bounds(p: ArbitraryStar, iterations: u8) -> bool {
+        // Every cut is made along an existing edge, so the smoothed polygon
+        // never strays outside of the original's bounds.
+        let iterations = (iterations % 4) as usize;
+        let smooth = p.0.chaikin(iterations);
+        let aabb = p.0.to_aabb();
+        smooth.len() == p.0.len() << iterations
+            && smooth.vertices().iter().all(|v| {
+                let e = 1e-9;
+                aabb.min().x - e <= v.x
+                    && v.x <= aabb.max().x + e
+                    && aabb.min().y - e <= v.y
+                    && v.y <= aabb.max().y + e
+            })
+    }
+
+    fn catmull_rom_passes_through_points(p: ArbitraryPoints, closed: bool, c: ArbitraryCatmullRom) -> bool {
+        let curves = catmull_rom(&p.0, closed, c.0);
+        let n = p.0.len();
+        if n < 2 {
+            return curves.is_empty();
+        }
+        curves.len() == if closed && n > 2 { n } else { n - 1 }
+            && curves.iter().enumerate().all(|(i, curve)| {
+                curve.start == p.0[i] && curve.end == p.0[(i + 1) % n]
+            })
+    }
+
+    fn catmull_rom_is_smooth(p: ArbitraryPoints, closed: bool, c: ArbitraryCatmullRom) -> bool {
+        let curves = catmull_rom(&p.0, closed, c.0);
+        let joints = curves.windows(2).map(|w| (&w[0], &w[1]));
+        let wrap = if closed && curves.len() > 1 {
+            Some((&curves[curves.len() - 1], &curves[0]))
+        } else {
+            None
+        };
+        joints
+            .chain(wrap)
+            .all(|(a, b)| is_smooth(a.control_2, a.end, b.control_1))
+    }
+}
+
+#[test]
+fn uniform_catmull_rom_control_points() {
+    // With uniform knots, the control points around each point are a sixth of
+    // the way between its neighbors away from it.
+    let points: Vec<Point> = vec![
+        point2(0.0, 0.0),
+        point2(6.0, 12.0),
+        point2(18.0, 6.0),
+        point2(30.0, 18.0),
+    ];
+    let curves = catmull_rom(&points, false, CatmullRom::Uniform);
+    assert_eq!(curves.len(), 3);
+    assert!(close(
+        curves[1].control_1,
+        points[1] + (points[2] - points[0]) / 6.0
+    ));
+    assert!(close(
+        curves[1].control_2,
+        points[2] - (points[3] - points[1]) / 6.0
+    ));
+}
+
+#[test]
+fn catmull_rom_skips_duplicates() {
+    let points: Vec<Point> = vec![
+        point2(0.0, 0.0),
+        point2(0.0, 0.0),
+        point2(10.0, 0.0),
+        point2(10.0, 10.0),
+        point2(0.0, 0.0),
+    ];
+    for &parameterization in &[
+        CatmullRom::Uniform,
+        CatmullRom::Centripetal,
+        CatmullRom::Chordal,
+    ] {
+        assert_eq!(catmull_rom(&points, false, parameterization).len(), 3);
+        // The last point is the same as the first, and so a closed spline
+        // through them is a triangle.
+        let closed = catmull_rom(&points, true, parameterization);
+        assert_eq!(closed.len(), 3);
+        assert!(closed.iter().all(|c| c.start.x.is_finite()
+            && c.control_1.x.is_finite()
+            && c.control_2.x.is_finite()));
+    }
+}
@@ -0,0 +1,237 @@
+use euclid::{point2, Point2D};
+use fart_2d_geom::{line, segment_intersections, split_segments, Line, LineIntersection};
+use quickcheck::{quickcheck, Arbitrary, Gen};
+use rand::distributions::{Distribution, Uniform};
+use std::collections::BTreeSet;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct UnknownUnit;
+
+type Point = Point2D<f64, UnknownUnit>;
+
+/// Random line segments between points on a small grid, so that many of them
+/// share endpoints, overlap, are vertical, or have zero length.
+#[derive(Clone, Debug)]
+struct GridSegments(Vec<Line<f64, UnknownUnit>>);
+
+impl Arbitrary for GridSegments {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let n = Uniform::new(0, 30).sample(g);
+        let coordinate = Uniform::new_inclusive(0, 6);
+        let mut point = || point2(coordinate.sample(g) as f64, coordinate.sample(g) as f64);
+        GridSegments((0..n).map(|_| line(point(), point())).collect())
+    }
+}
+
+/// Random horizontal and vertical line segments on a small grid, which only
+/// ever cross at points on the grid.
+#[derive(Clone, Debug)]
+struct AxisAlignedSegments(Vec<Line<f64, UnknownUnit>>);
+
+impl Arbitrary for AxisAlignedSegments {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let n = Uniform::new(0, 30).sample(g);
+        let coordinate = Uniform::new_inclusive(0, 6);
+        let mut segments = vec![];
+        for _ in 0..n {
+            let (x, y, z) = (
+                coordinate.sample(g) as f64,
+                coordinate.sample(g) as f64,
+                coordinate.sample(g) as f64,
+            );
+            segments.push(if Uniform::new(0, 2).sample(g) == 0 {
+                line(point2(x, y), point2(z, y))
+            } else {
+                line(point2(x, y), point2(x, z))
+            });
+        }
+        AxisAlignedSegments(segments)
+    }
+}
+
+/// Random line segments anywhere, which cross at points that have to be
+/// rounded.
+#[derive(Clone, Debug)]
+struct RandomSegments(Vec<Line<f64, UnknownUnit>>);
+
+impl Arbitrary for RandomSegments {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let n = Uniform::new(0, 30).sample(g);
+        let coordinate = Uniform::new(-100.0, 100.0);
+        let mut point = || point2(coordinate.sample(g), coordinate.sample(g));
+        RandomSegments((0..n).map(|_| line(point(), point())).collect())
+    }
+}
+
+/// Do the line segments `l` and `m` meet anywhere?
+fn meet(l: &Line<f64, UnknownUnit>, m: &Line<f64, UnknownUnit>) -> bool {
+    match (l.a == l.b, m.a == m.b) {
+        (true, true) => l.a == m.a,
+        (true, false) => m.is_on(l.a),
+        (false, true) => l.is_on(m.a),
+        (false, false) => l.improperly_intersects(m),
+    }
+}
+
+fn brute_force_pairs(segments: &[Line<f64, UnknownUnit>]) -> BTreeSet<(usize, usize)> {
+    let mut pairs = BTreeSet::new();
+    for i in 0..segments.len() {
+        for j in i + 1..segments.len() {
+            if meet(&segments[i], &segments[j]) {
+                pairs.insert((i, j));
+            }
+        }
+    }
+    pairs
+}
+
+fn swept_pairs(segments: &[Line<f64, UnknownUnit>]) -> BTreeSet<(usize, usize)> {
+    let mut pairs = BTreeSet::new();
+    for intersection in segment_intersections(segments) {
+        for (k, &i) in intersection.segments.iter().enumerate() {
+            for &j in &intersection.segments[k + 1..] {
+                pairs.insert((i, j));
+            }
+        }
+    }
+    pairs
+}
+
+/// Is `p` on the line segment `l`, give or take rounding?
+fn is_near(l: &Line<f64, UnknownUnit>, p: Point) -> bool {
+    let (d, e) = (l.b - l.a, p - l.a);
+    let t = if l.a == l.b {
+        0.0
+    } else {
+        (e.dot(d) / d.square_length()).clamp(0.0, 1.0)
+    };
+    (l.a + d * t - p).length() < 1e-9
+}
+
+fn intersections_are_on_segments(segments: &[Line<f64, UnknownUnit>]) -> bool {
+    segment_intersections(segments).iter().all(|intersection| {
+        intersection.segments.len() >= 2
+            && intersection
+                .segments
+                .iter()
+                .all(|&i| is_near(&segments[i], intersection.point))
+    })
+}
+
+/// Do the pieces only ever meet at their endpoints?
+fn is_planar(pieces: &[Line<f64, UnknownUnit>]) -> bool {
+    (0..pieces.len()).all(|i| {
+        (i + 1..pieces.len()).all(|j| {
+            let (l, m) = (&pieces[i], &pieces[j]);
+            !l.intersects(m)
+                && [m.a, m.b]
+                    .iter()
+                    .all(|&p| !l.is_on(p) || p == l.a || p == l.b)
+                && [l.a, l.b]
+                    .iter()
+                    .all(|&p| !m.is_on(p) || p == m.a || p == m.b)
+        })
+    })
+}
+
+quickcheck! {
+    fn grid_intersections_match_brute_force(s: GridSegments) -> bool {
+        swept_pairs(&s.0) == brute_force_pairs(&s.0) && intersections_are_on_segments(&s.0)
+    }
+
+    fn random_intersections_match_brute_force(s: RandomSegments) -> bool {
+        swept_pairs(&s.0) == brute_force_pairs(&s.0) && intersections_are_on_segments(&s.0)
+    }
+
+    fn axis_aligned_split_segments_are_planar(s: AxisAlignedSegments) -> bool {
+        let pieces = split_segments(&s.0);
+        is_planar(&pieces) && pieces.iter().all(|p| s.0.iter().any(|l| l.is_on(p.a) && l.is_on(p.b)))
+    }
+
+    fn grid_split_segments_are_planar(s: GridSegments) -> bool {
+        // Many of these cross at the same points, which have to be rounded
+        // the same way every time.
+        is_planar(&split_segments(&s.0))
+    }
+
+    fn random_split_segments_are_planar(s: RandomSegments) -> bool {
+        is_planar(&split_segments(&s.0))
+    }
+
+    fn split_segments_keep_lengths(s: RandomSegments) -> bool {
+        // Random line segments don't overlap, so splitting them neither adds
+        // nor removes any length.
+        let expected: f64 = s.0.iter().map(|l| l.length()).sum();
+        let actual: f64 = split_segments(&s.0).iter().map(|l| l.length()).sum();
+        (expected - actual).abs() < 1e-9 * expected.max(1.0)
+    }
+
+    fn integer_intersections_match_float_intersections(s: GridSegments) -> bool {
+        // Grid segments are exact either way.
+        s.0.iter().all(|l| {
+            s.0.iter().all(|m| {
+                let (l_int, m_int) = (l.cast::<i64>(), m.cast::<i64>());
+                l_int.intersection(&m_int) == l.intersection(m) && l_int.length() == l.length()
+            })
+        })
+    }
+
+    fn intersection_kinds_match_predicates(s: RandomSegments) -> bool {
+        s.0.iter().all(|l| {
+            s.0.iter().all(|m| match l.intersection(m) {
+                LineIntersection::None => !l.improperly_intersects(m),
+                LineIntersection::Proper(_) => l.intersects(m),
+                LineIntersection::Improper(_) | LineIntersection::Collinear(_) => {
+                    l.improperly_intersects(m) && !l.intersects(m)
+                }
+            })
+        })
+    }
+}
+
+#[test]
+fn intersections_of_large_integer_segments() {
+    // The products of these coordinates overflow `i32`.
+    let a = line::<i32, UnknownUnit>(point2(-50_000, -50_000), point2(50_000, 50_000));
+    let b = line(point2(-50_000, 50_000), point2(50_000, -50_000));
+    assert_eq!(
+        a.intersection(&b),
+        LineIntersection::Proper(point2(0.0, 0.0))
+    );
+
+    let c = line(point2(0, 0), point2(50_000, -50_000));
+    assert_eq!(
+        a.intersection(&c),
+        LineIntersection::Improper(point2(0.0, 0.0))
+    );
+    assert_eq!(a.length(), 100_000.0 * 2.0_f64.sqrt());
+}
+
+#[test]
+fn nearly_parallel_segments_intersect_properly() {
+    // These cross at a shallow angle, far from any of their end points.
+    let l = line::<f64, UnknownUnit>(point2(-4.7809, -8.9076), point2(8.8303, -1.5001));
+    let m = line(point2(4.7619, -3.7142), point2(11.3146, -0.1481));
+    for (l, m) in [(l, m), (m, l)].iter() {
+        match l.intersection(m) {
+            LineIntersection::Proper(p) => assert!(is_near(l, p) && is_near(m, p)),
+            i => panic!("expected a proper intersection, found {:?}", i),
+        }
+    }
+}
+
+#[test]
+fn many_segments_through_one_point() {
+    // A star of line segments all crossing at the same point.
+    let n = 12;
+    let segments: Vec<Line<f64, UnknownUnit>> = (0..n)
+        .map(|i| {
+            let angle = i as f64 * std::f64::consts::PI / n as f64;
+            let d = euclid::vec2(angle.cos(), angle.sin()) * 10.0;
+            line(point2(1.0, 1.0) - d, point2(1.0, 1.0) + d)
+        })
+        .collect();
+    let pairs = swept_pairs(&segments);
+    assert_eq!(pairs.len(), n * (n - 1) / 2);
+    assert!(intersections_are_on_segments(&segments));
+}
@@ -5,7 +5,7 @@
 //! if they precisely collide with a more expensive algorithm.
 
 use euclid::Point2D;
-use num_traits::Num;
+pub use fart_utils::Scalar;
 use partial_min_max::{max as partial_max, min as partial_min};
 use std::fmt;
 
@@ -33,7 +33,7 @@ where
 
 impl<T, U> Aabb<T, U>
 where
-    T: Copy + Num + PartialOrd,
+    T: Scalar,
 {
     /// Construct a new axis-aligned bounding box.
     ///
@@ -134,6 +134,93 @@ where
             && self.max.y > other.min.y
             && self.min.y < other.max.y
     }
+
+    /// Split `self` into four AABBs that meet at the point `p`, which must be
+    /// within `self`.
+    ///
+    /// They are returned in counter-clockwise order (if the y axis points up),
+    /// starting with the one that shares `self`'s min.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_aabb::Aabb;
+    ///
+    /// let aabb = Aabb::<i32, UnknownUnit>::new(point2(0, 0), point2(10, 10));
+    /// let [a, b, c, d] = aabb.split_at(point2(3, 6));
+    /// assert_eq!(a, Aabb::new(point2(0, 0), point2(3, 6)));
+    /// assert_eq!(b, Aabb::new(point2(3, 0), point2(10, 6)));
+    /// assert_eq!(c, Aabb::new(point2(3, 6), point2(10, 10)));
+    /// assert_eq!(d, Aabb::new(point2(0, 6), point2(3, 10)));
+    /// ```
+    pub fn split_at(&self, p: Point2D<T, U>) -> [Aabb<T, U>; 4] {
+        assert!(self.contains_point(p));
+        [
+            Aabb::new(self.min, p),
+            Aabb::new(Point2D::new(p.x, self.min.y), Point2D::new(self.max.x, p.y)),
+            Aabb::new(p, self.max),
+            Aabb::new(Point2D::new(self.min.x, p.y), Point2D::new(p.x, self.max.y)),
+        ]
+    }
+
+    /// Split `self` into four quadrants that meet at its center.
+    ///
+    /// See `split_at` for their order.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_aabb::Aabb;
+    ///
+    /// let aabb = Aabb::<f64, UnknownUnit>::new(point2(0.0, 0.0), point2(4.0, 2.0));
+    /// let quadrants = aabb.quadrants();
+    /// assert_eq!(quadrants[0], Aabb::new(point2(0.0, 0.0), point2(2.0, 1.0)));
+    /// assert!(quadrants.iter().all(|q| q.area() == 2.0));
+    /// ```
+    pub fn quadrants(&self) -> [Aabb<T, U>; 4] {
+        let two = T::one() + T::one();
+        self.split_at(Point2D::new(
+            self.min.x + self.width() / two,
+            self.min.y + self.height() / two,
+        ))
+    }
+
+    /// Recursively split `self` into quadrants, quad-tree style, for as long
+    /// as `should_split` returns `true`, and return the AABBs that aren't
+    /// split any further.
+    ///
+    /// `should_split` is given each AABB along with its depth, which is zero
+    /// for `self`, and must eventually return `false`.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart_aabb::Aabb;
+    ///
+    /// // Split more finely towards the origin.
+    /// let aabb = Aabb::<f64, UnknownUnit>::new(point2(0.0, 0.0), point2(8.0, 8.0));
+    /// let leaves = aabb.subdivide(|a, depth| depth < 3 && a.min() == point2(0.0, 0.0));
+    /// assert_eq!(leaves.len(), 10);
+    /// assert_eq!(leaves.iter().map(|a| a.area()).sum::<f64>(), 64.0);
+    /// ```
+    pub fn subdivide<F>(&self, mut should_split: F) -> Vec<Aabb<T, U>>
+    where
+        F: FnMut(&Aabb<T, U>, usize) -> bool,
+    {
+        let mut leaves = vec![];
+        let mut stack = vec![(Aabb::new(self.min, self.max), 0)];
+        while let Some((aabb, depth)) = stack.pop() {
+            if should_split(&aabb, depth) {
+                // Reversed, so that the quadrants are popped in order.
+                stack.extend(
+                    aabb.quadrants()
+                        .iter()
+                        .rev()
+                        .map(|q| (Aabb::new(q.min, q.max), depth + 1)),
+                );
+            } else {
+                leaves.push(aabb);
+            }
+        }
+        leaves
+    }
 }
 
 /// A tree mapping from axis-aligned bounding boxes to `T` values.
@@ -151,6 +238,8 @@ enum AabbTreeNode<T, U, V> {
 #[derive(Debug)]
 struct AabbTreeBranch<T, U, V> {
     aabb: Aabb<T, U>,
+    /// The length of the longest path from this branch down to a leaf.
+    height: usize,
     children: Box<(AabbTreeNode<T, U, V>, AabbTreeNode<T, U, V>)>,
 }
 
@@ -162,7 +251,7 @@ struct AabbTreeLeaf<T, U, V> {
 
 impl<T, U, V> AabbTree<T, U, V>
 where
-    T: Copy + Num + PartialOrd,
+    T: Scalar,
 {
     /// Construct a new, empty AABB tree.
     #[inline]
@@ -221,7 +310,7 @@ where
 
 impl<T, U, V> AabbTreeNode<T, U, V>
 where
-    T: Copy + Num + PartialOrd,
+    T: Scalar,
 {
     fn aabb(&self) -> &Aabb<T, U> {
         match self {
@@ -230,12 +319,58 @@ where
         }
     }
 
+    fn height(&self) -> usize {
+        match self {
+            AabbTreeNode::Leaf(_) => 0,
+            AabbTreeNode::Branch(b) => b.height,
+        }
+    }
+
+    /// Make a new branch with the given children.
+    fn branch(a: AabbTreeNode<T, U, V>, b: AabbTreeNode<T, U, V>) -> AabbTreeNode<T, U, V> {
+        AabbTreeNode::Branch(AabbTreeBranch {
+            aabb: a.aabb().join(b.aabb()),
+            height: 1 + a.height().max(b.height()),
+            children: Box::new((a, b)),
+        })
+    }
+
+    /// Make a new branch with the given children, rotating them if one child is
+    /// more than one level taller than the other, so that the tree stays
+    /// shallow even when AABBs are inserted in an unlucky order.
+    fn balanced_branch(
+        a: AabbTreeNode<T, U, V>,
+        b: AabbTreeNode<T, U, V>,
+    ) -> AabbTreeNode<T, U, V> {
+        let (tall, short) = if a.height() > b.height() + 1 {
+            (a, b)
+        } else if b.height() > a.height() + 1 {
+            (b, a)
+        } else {
+            return AabbTreeNode::branch(a, b);
+        };
+
+        // Keep the tall child's taller child where it is, and swap its other
+        // child with the short child.
+        match tall {
+            AabbTreeNode::Leaf(_) => unreachable!("leaves are never taller than anything"),
+            AabbTreeNode::Branch(tall) => {
+                let (x, y) = *tall.children;
+                let (taller, shorter) = if x.height() >= y.height() {
+                    (x, y)
+                } else {
+                    (y, x)
+                };
+                AabbTreeNode::branch(taller, AabbTreeNode::balanced_branch(short, shorter))
+            }
+        }
+    }
+
     fn insert(self, leaf: AabbTreeLeaf<T, U, V>) -> AabbTreeNode<T, U, V> {
         match self {
-            AabbTreeNode::Leaf(l) => AabbTreeNode::Branch(AabbTreeBranch {
-                aabb: l.aabb.join(&leaf.aabb),
-                children: Box::new((AabbTreeNode::Leaf(l), AabbTreeNode::Leaf(leaf))),
-            }),
+            AabbTreeNode::Leaf(l) => {
+                AabbTreeNode::branch(AabbTreeNode::Leaf(l), AabbTreeNode::Leaf(leaf))
+            }
             AabbTreeNode::Branch(branch) => {
                 let combined_aabb = branch.aabb.join(&leaf.aabb);
                 let two = T::one() + T::one();
@@ -260,18 +395,19 @@ where
                     }
                 };
 
-                AabbTreeNode::Branch(AabbTreeBranch {
-                    aabb: combined_aabb,
-                    children: Box::new(
-                        if new_parent_cost < left_cost && new_parent_cost < right_cost {
-                            (AabbTreeNode::Leaf(leaf), AabbTreeNode::Branch(branch))
-                        } else if left_cost < right_cost {
-                            (branch.children.0.insert(leaf), branch.children.1)
-                        } else {
-                            (branch.children.0, branch.children.1.insert(leaf))
-                        },
-                    ),
-                })
+                if new_parent_cost < left_cost && new_parent_cost < right_cost {
+                    AabbTreeNode::balanced_branch(
+                        AabbTreeNode::Leaf(leaf),
+                        AabbTreeNode::Branch(branch),
+                    )
+                } else {
+                    let (left, right) = *branch.children;
+                    if left_cost < right_cost {
+                        AabbTreeNode::balanced_branch(left.insert(leaf), right)
+                    } else {
+                        AabbTreeNode::balanced_branch(left, right.insert(leaf))
+                    }
+                }
             }
         }
     }
@@ -288,7 +424,7 @@ pub struct IterOverlapping<'a, T, U, V> {
 
 impl<'a, T, U, V> Iterator for IterOverlapping<'a, T, U, V>
 where
-    T: Copy + Num + PartialOrd,
+    T: Scalar,
 {
     type Item = (&'a Aabb<T, U>, &'a V);
 
@@ -192,3 +192,29 @@ fn tree() {
         assert_eq!(overlaps, expected);
     }
 }
+
+quickcheck! {
+    fn quadrants_cover_box(b: ArbitraryAabb) -> bool {
+        let b = b.0;
+        if !(b.area().is_finite()) {
+            return true;
+        }
+        let quadrants = b.quadrants();
+        let area: f64 = quadrants.iter().map(|q| q.area()).sum();
+        quadrants.iter().all(|q| b.contains(q))
+            && (area - b.area()).abs() <= 1e-9 * b.area()
+    }
+
+    fn subdivide_covers_box(b: ArbitraryAabb, depth: u8) -> bool {
+        let b = b.0;
+        if !(b.area().is_finite()) {
+            return true;
+        }
+        let depth = depth as usize % 4;
+        let leaves = b.subdivide(|_, d| d < depth);
+        let area: f64 = leaves.iter().map(|l| l.area()).sum();
+        leaves.len() == 1 << (2 * depth)
+            && leaves.iter().all(|l| b.contains(l))
+            && (area - b.area()).abs() <= 1e-9 * b.area()
+    }
+}
@@ -2,7 +2,7 @@
 
 #![deny(missing_docs)]
 
-use num_traits::{Num, NumCast};
+use num_traits::{Bounded, Num, NumAssign, NumCast, Signed};
 use std::cmp;
 use std::fmt::Debug;
 use std::ops::{Range, RangeInclusive};
@@ -90,6 +90,79 @@ where
     out_low + (slope * (value - in_low))
 }
 
+/// A coordinate type: one of the signed integer or floating point primitives.
+///
+/// Geometry throughout `fart` is generic over its coordinates, and everything
+/// that makes sense for both integer and floating point coordinates works with
+/// any `Scalar`. Operations that create new points that generally can't be
+/// represented with integers, such as offsetting polygons or computing their
+/// intersections, are only implemented for `f64` coordinates, and their results
+/// are `f64` even when their inputs are integers.
+///
+/// # Example
+///
+/// ```
+/// # use fart_utils::Scalar;
+/// fn midpoint<T: Scalar>(a: T, b: T) -> T {
+///     T::from_float((a.to_float() + b.to_float()) / 2.0)
+/// }
+///
+/// assert_eq!(midpoint(1.0, 2.0), 1.5);
+/// assert_eq!(midpoint(1, 4), 3);
+/// ```
+pub trait Scalar: Copy + Debug + NumAssign + NumCast + PartialOrd + Signed + Bounded {
+    /// Is this an integer type?
+    const IS_INTEGER: bool;
+
+    /// Convert this value to an `f64`. Integers too large to represent
+    /// exactly are rounded.
+    fn to_float(self) -> f64;
+
+    /// Convert an `f64` to this type, rounding to the nearest integer for
+    /// integer types, and saturating at their bounds.
+    fn from_float(x: f64) -> Self;
+}
+
+macro_rules! impl_scalar {
+    ( integer: $( $t:ty ),* ) => {
+        $(
+            impl Scalar for $t {
+                const IS_INTEGER: bool = true;
+
+                #[inline]
+                fn to_float(self) -> f64 {
+                    self as f64
+                }
+
+                #[inline]
+                fn from_float(x: f64) -> Self {
+                    x.round() as $t
+                }
+            }
+        )*
+    };
+    ( float: $( $t:ty ),* ) => {
+        $(
+            impl Scalar for $t {
+                const IS_INTEGER: bool = false;
+
+                #[inline]
+                fn to_float(self) -> f64 {
+                    self as f64
+                }
+
+                #[inline]
+                fn from_float(x: f64) -> Self {
+                    x as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar!(integer: i8, i16, i32, i64, isize);
+impl_scalar!(float: f32, f64);
+
 /// Turn a `T: PartialOrd` and/or `T: PartialEq` into `Ord` and/or `Eq`.
 #[derive(Copy, Clone, Debug, Default, PartialOrd, PartialEq, Hash)]
 pub struct NoMorePartial<T>(pub T);
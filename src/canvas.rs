@@ -1,8 +1,11 @@
 //! A canvas for drawing paths on.
 
-use crate::aabb::Aabb;
-use crate::path::{LineCommand, Path, ToPaths};
-use euclid::point2;
+use crate::aabb::{Aabb, AabbTree};
+use crate::clip::{clip_path_to_region, even_odd_region, ClipMode};
+use crate::optimize::{self, PenTravel};
+use crate::path::{self, Path, ToPaths};
+use fart_utils::Scalar;
+use std::iter;
 
 /// Unit for things within the canvas space.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -10,130 +13,345 @@ pub struct CanvasSpace;
 
 /// A canvas is a collection of rendered paths. To add new paths to the canvas,
 /// use the `draw` method.
+///
+/// Canvases are generic over their coordinate type, which is `i64` by default,
+/// and `Canvas::new` makes an `i64` canvas. Use `Canvas::with_view` to make an
+/// `f64` canvas, which draws fine curves without quantizing them to whole
+/// units, and `set_precision` to choose how many decimal places its paths are
+/// written to the SVG with.
+///
+/// # Example
+///
+/// ```
+/// use fart::aabb::Aabb;
+/// use fart::canvas::{Canvas, Inches};
+/// use fart::euclid::point2;
+/// use fart::geom::line;
+///
+/// let mut canvas = Canvas::with_view(Aabb::new(
+///     point2(0.0, 0.0),
+///     point2(1.0, 1.0),
+/// ));
+/// canvas.set_precision(2);
+/// canvas.draw(line(point2(0.25, 0.126), point2(1.0 / 3.0, 1.0)));
+///
+/// let svg_doc = canvas.create_svg(Inches(3.0), Inches(3.0));
+/// assert!(svg_doc.to_string().contains(r#"d="M0.25,0.13 L0.33,1""#));
+/// ```
 #[derive(Debug)]
-pub struct Canvas {
-    view: Aabb<i64, CanvasSpace>,
-    paths: Vec<Path<i64, CanvasSpace>>,
-    stroke_width: i64,
+pub struct Canvas<T = i64> {
+    view: Aabb<T, CanvasSpace>,
+    paths: Vec<Path<T, CanvasSpace>>,
+    stroke_width: T,
+    precision: usize,
+    occluding: bool,
+    /// The bounding boxes of `paths`, mapped to their indices, when
+    /// occluding. Rebuilt lazily after the paths are rearranged.
+    index: Option<AabbTree<T, CanvasSpace, usize>>,
+}
+
+impl Canvas<i64> {
+    /// Construct a new canvas with the given viewport, and `i64` coordinates.
+    ///
+    /// See `Canvas::with_view` for canvases with other coordinate types.
+    pub fn new(view: Aabb<i64, CanvasSpace>) -> Canvas<i64> {
+        Canvas::with_view(view)
+    }
 }
 
-impl Canvas {
-    /// Construct a new canvas with the given viewport.
-    pub fn new(view: Aabb<i64, CanvasSpace>) -> Canvas {
-        let stroke_width = view.width() / 500;
+impl<T> Canvas<T>
+where
+    T: Scalar,
+{
+    /// Construct a new canvas with the given viewport, with the viewport's
+    /// coordinate type.
+    pub fn with_view(view: Aabb<T, CanvasSpace>) -> Canvas<T> {
+        let stroke_width = view.width() / T::from_float(500.0);
         Canvas {
             view,
             paths: Vec::new(),
             stroke_width,
+            precision: 3,
+            occluding: false,
+            index: None,
         }
     }
 
     /// Get the stroke width for paths in this canvas.
-    pub fn stroke_width(&self) -> i64 {
+    pub fn stroke_width(&self) -> T {
         self.stroke_width
     }
 
     /// Set the stroke width for paths in this canvas.
-    pub fn set_stroke_width(&mut self, stroke_width: i64) {
+    pub fn set_stroke_width(&mut self, stroke_width: T) {
         self.stroke_width = stroke_width;
     }
 
+    /// Get how many decimal places path data is rounded to in this canvas's
+    /// SVG. Defaults to 3.
+    #[inline]
+    pub fn precision(&self) -> usize {
+        self.precision
+    }
+
+    /// Set how many decimal places path data is rounded to in this canvas's
+    /// SVG.
+    ///
+    /// This only matters for floating point canvases, since integer
+    /// coordinates are always written exactly. See `Path::to_svg_data`.
+    pub fn set_precision(&mut self, precision: usize) {
+        self.precision = precision;
+    }
+
     /// Get this canvas's view.
     #[inline]
-    pub fn view(&self) -> &Aabb<i64, CanvasSpace> {
+    pub fn view(&self) -> &Aabb<T, CanvasSpace> {
         &self.view
     }
 
     /// Set this canvas's view.
-    pub fn set_view(&mut self, view: Aabb<i64, CanvasSpace>) {
+    pub fn set_view(&mut self, view: Aabb<T, CanvasSpace>) {
         self.view = view;
     }
 
     /// Make this canvas's view the bounding box of all the paths that have been
     /// added to the canvas.
+    ///
+    /// See `Path::aabb` for how each path's bounding box is computed.
     pub fn fit_view_to_paths(&mut self) {
-        if self.paths.is_empty() {
-            return;
+        let view = self.paths.iter().filter_map(|path| path.aabb()).fold(
+            None,
+            |view: Option<Aabb<T, CanvasSpace>>, aabb| match view {
+                None => Some(aabb),
+                Some(view) => Some(view.join(&aabb)),
+            },
+        );
+        if let Some(view) = view {
+            self.set_view(view);
         }
+    }
 
-        let mut min_x = std::i64::MAX;
-        let mut min_y = std::i64::MAX;
-        let mut max_x = std::i64::MIN;
-        let mut max_y = std::i64::MIN;
-
-        let mut process_point = |p: &euclid::Point2D<i64, CanvasSpace>| {
-            min_x = std::cmp::min(min_x, p.x);
-            min_y = std::cmp::min(min_y, p.y);
-            max_x = std::cmp::max(max_x, p.x);
-            max_y = std::cmp::max(max_y, p.y);
-        };
-
-        for path in self.paths.iter() {
-            for cmd in path.commands.iter() {
-                match cmd {
-                    LineCommand::MoveTo(p)
-                    | LineCommand::LineTo(p)
-                    | LineCommand::SmoothQuadtraticCurveTo(p) => process_point(p),
-
-                    LineCommand::CubicBezierTo {
-                        control_1,
-                        control_2,
-                        end,
-                    } => {
-                        process_point(control_1);
-                        process_point(control_2);
-                        process_point(end);
-                    }
-
-                    LineCommand::SmoothCubicBezierTo { control, end }
-                    | LineCommand::QuadraticBezierTo { control, end } => {
-                        process_point(control);
-                        process_point(end);
-                    }
+    /// Is this canvas in occluding mode?
+    ///
+    /// See `set_occluding` for details.
+    #[inline]
+    pub fn occluding(&self) -> bool {
+        self.occluding
+    }
 
-                    LineCommand::Close => {}
-
-                    LineCommand::MoveBy(_)
-                    | LineCommand::LineBy(_)
-                    | LineCommand::HorizontalLineTo(_)
-                    | LineCommand::HorizontalLineBy(_)
-                    | LineCommand::VerticalLineTo(_)
-                    | LineCommand::VerticalLineBy(_)
-                    | LineCommand::CubicBezierBy { .. }
-                    | LineCommand::SmoothCubicBezierBy { .. }
-                    | LineCommand::QuadraticBezierBy { .. }
-                    | LineCommand::SmoothQuadtraticCurveBy(_)
-                    | LineCommand::ArcTo { .. }
-                    | LineCommand::ArcBy { .. } => unimplemented!(),
-                }
-            }
+    /// Turn occluding mode on or off.
+    ///
+    /// In occluding mode, drawing a closed shape hides the parts of the paths
+    /// that were drawn before it that fall inside of it, as if it were painted
+    /// on top of them. Open paths, like lines, don't hide anything. See
+    /// `Path::is_closed` for which paths are closed shapes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fart::aabb::Aabb;
+    /// use fart::canvas::Canvas;
+    /// use fart::euclid::point2;
+    /// use fart::geom::line;
+    /// use fart::path::ToPaths;
+    ///
+    /// let mut canvas = Canvas::new(Aabb::new(
+    ///     point2(0, 0),
+    ///     point2(100, 100),
+    /// ));
+    /// canvas.set_occluding(true);
+    ///
+    /// // A line, partially hidden behind a box drawn in front of it.
+    /// canvas.draw(line(point2(0, 50), point2(100, 50)));
+    /// canvas.draw(Aabb::new(point2(40, 0), point2(60, 100)));
+    ///
+    /// let paths: Vec<_> = canvas.to_paths().collect();
+    /// assert_eq!(paths.len(), 3);
+    /// assert_eq!(paths[0].end(), Some(point2(40, 50)));
+    /// assert_eq!(paths[1].start(), Some(point2(60, 50)));
+    /// ```
+    pub fn set_occluding(&mut self, occluding: bool) {
+        self.occluding = occluding;
+        if !occluding {
+            self.index = None;
         }
-
-        let view = Aabb::new(point2(min_x, min_y), point2(max_x, max_y));
-        self.set_view(view);
     }
 
     /// Add the given paths to the canvas.
     pub fn draw<P>(&mut self, paths: P)
     where
-        P: ToPaths<i64, CanvasSpace>,
+        P: ToPaths<T, CanvasSpace>,
     {
-        self.paths.extend(paths.to_paths());
+        if !self.occluding {
+            self.paths.extend(paths.to_paths());
+            return;
+        }
+
+        let mut index = self.index.take().unwrap_or_else(|| {
+            let mut index = AabbTree::new();
+            for (i, path) in self.paths.iter().enumerate() {
+                if let Some(aabb) = path.aabb() {
+                    index.insert(aabb, i);
+                }
+            }
+            index
+        });
+
+        for path in paths.to_paths() {
+            let aabb = match path.aabb() {
+                Some(aabb) => aabb,
+                None => {
+                    self.paths.push(path);
+                    continue;
+                }
+            };
+
+            if path.is_closed() {
+                let region = even_odd_region(iter::once(&path));
+                let behind: Vec<usize> = index
+                    .iter_overlapping(aabb.clone())
+                    .map(|(_, &i)| i)
+                    .collect();
+                for i in behind {
+                    let mut pieces =
+                        clip_path_to_region(&self.paths[i], &region, ClipMode::Outside).into_iter();
+                    // Reuse the hidden path's slot for its first visible piece,
+                    // or leave it empty if it was completely hidden.
+                    self.paths[i].commands = pieces.next().map_or(vec![], |p| p.commands);
+                    for piece in pieces {
+                        if let Some(aabb) = piece.aabb() {
+                            index.insert(aabb, self.paths.len());
+                        }
+                        self.paths.push(piece);
+                    }
+                }
+            }
+
+            index.insert(aabb, self.paths.len());
+            self.paths.push(path);
+        }
+
+        self.index = Some(index);
     }
 
     /// Given a collection of things that can be drawn, draw all of them.
     pub fn draw_many<I, P>(&mut self, paths: I)
     where
         I: IntoIterator<Item = P>,
-        P: ToPaths<i64, CanvasSpace>,
+        P: ToPaths<T, CanvasSpace>,
     {
         for p in paths {
             self.draw(p);
         }
     }
 
+    /// Reorder this canvas's paths, and choose which direction to draw each of
+    /// them, to minimize how far the plotter's pen travels while it is lifted.
+    ///
+    /// The pen is assumed to start at the view's minimum corner. Paths are
+    /// kept grouped by color. Returns the pen-up travel distance before and
+    /// after optimizing.
+    ///
+    /// See `fart::optimize::pen_travel` for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fart::aabb::Aabb;
+    /// use fart::canvas::{Canvas, Inches};
+    /// use fart::euclid::point2;
+    /// use fart::geom::line;
+    ///
+    /// let mut canvas = Canvas::new(Aabb::new(
+    ///     point2(0, 0),
+    ///     point2(100, 100),
+    /// ));
+    ///
+    /// for i in 0..10 {
+    ///     let y = if i % 2 == 0 { i * 5 } else { 100 - i * 5 };
+    ///     canvas.draw(line(point2(0, y), point2(100, y)));
+    /// }
+    ///
+    /// let travel = canvas.optimize_pen_travel();
+    /// assert!(travel.after < travel.before);
+    ///
+    /// let svg_doc = canvas.create_svg(Inches(3.0), Inches(3.0));
+    /// # let _ = svg_doc;
+    /// ```
+    pub fn optimize_pen_travel(&mut self) -> PenTravel {
+        self.index = None;
+        optimize::pen_travel(&mut self.paths, self.view.min())
+    }
+
+    /// Join paths of the same color whose end points are within `tolerance` of
+    /// each other into single, continuous paths, so the plotter doesn't need to
+    /// lift its pen between them.
+    ///
+    /// Returns how many paths were merged away. See
+    /// `fart::optimize::merge_paths` for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fart::aabb::Aabb;
+    /// use fart::canvas::Canvas;
+    /// use fart::euclid::point2;
+    /// use fart::geom::line;
+    ///
+    /// let mut canvas = Canvas::new(Aabb::new(
+    ///     point2(0, 0),
+    ///     point2(100, 100),
+    /// ));
+    ///
+    /// // Draw a zig-zag out of many individual lines.
+    /// for i in 0..10 {
+    ///     let y = if i % 2 == 0 { 0 } else { 100 };
+    ///     canvas.draw(line(point2(i * 10, y), point2(i * 10 + 10, 100 - y)));
+    /// }
+    ///
+    /// assert_eq!(canvas.merge_paths(0), 9);
+    /// ```
+    pub fn merge_paths(&mut self, tolerance: T) -> usize {
+        self.index = None;
+        optimize::merge_paths(&mut self.paths, tolerance)
+    }
+
+    /// Remove the parts of this canvas's line segments that would be drawn on
+    /// top of a segment of the same color that was already drawn, for example
+    /// the edges shared by neighboring triangles or boxes.
+    ///
+    /// Returns how many segments were removed or shortened. See
+    /// `fart::optimize::dedup_segments` for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fart::aabb::Aabb;
+    /// use fart::canvas::Canvas;
+    /// use fart::euclid::point2;
+    ///
+    /// let mut canvas = Canvas::new(Aabb::new(
+    ///     point2(0, 0),
+    ///     point2(100, 100),
+    /// ));
+    ///
+    /// // A row of boxes, each sharing an edge with the next.
+    /// for i in 0..10 {
+    ///     canvas.draw(Aabb::new(point2(i * 10, 0), point2(i * 10 + 10, 10)));
+    /// }
+    ///
+    /// assert_eq!(canvas.dedup_segments(), 9);
+    /// ```
+    pub fn dedup_segments(&mut self) -> usize {
+        self.index = None;
+        optimize::dedup_segments(&mut self.paths)
+    }
+
     /// Render this canvas as an SVG with the given physical width and height.
     ///
+    /// Path data is rounded to this canvas's `precision`, but the view and
+    /// stroke width are written in full.
+    ///
     /// # Example
     ///
     /// Make a 3" x 3" SVG from a canvas.
@@ -157,32 +375,37 @@ impl Canvas {
     {
         let width = width.into();
         let height = height.into();
+        let number = |x: T| path::svg_number(x, None);
         let mut doc = svg::Document::new()
             .set(
                 "viewBox",
                 format!(
                     "{} {} {} {}",
-                    self.view.min().x,
-                    self.view.min().y,
-                    self.view.width(),
-                    self.view.height(),
+                    number(self.view.min().x),
+                    number(self.view.min().y),
+                    number(self.view.width()),
+                    number(self.view.height()),
                 ),
             )
             .set("width", format!("{}{}", width, W::SUFFIX))
             .set("height", format!("{}{}", height, H::SUFFIX));
-        for path in &self.paths {
-            let path: svg::node::element::Path = path.into();
-            doc = doc.add(path.set("stroke-width", self.stroke_width));
+        for path in self.paths.iter().filter(|p| !p.commands.is_empty()) {
+            let path = path::svg_path(path, Some(self.precision));
+            doc = doc.add(path.set("stroke-width", number(self.stroke_width)));
         }
         doc
     }
 }
 
-impl ToPaths<i64, CanvasSpace> for Canvas {
-    type Paths = std::vec::IntoIter<Path<i64, CanvasSpace>>;
+impl<T> ToPaths<T, CanvasSpace> for Canvas<T>
+where
+    T: Scalar,
+{
+    type Paths = std::vec::IntoIter<Path<T, CanvasSpace>>;
 
     fn to_paths(&self) -> Self::Paths {
-        self.paths.clone().into_iter()
+        let paths = self.paths.iter().filter(|p| !p.commands.is_empty());
+        paths.cloned().collect::<Vec<_>>().into_iter()
     }
 }
 
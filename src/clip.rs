@@ -0,0 +1,430 @@
+//! Clipping paths to the inside or outside of a region.
+//!
+//! This is useful for confining open strokes, like hatching or flow field
+//! lines, to a shape, or for cutting them away where they would cross it.
+
+use crate::geom::{
+    boolean, is_counter_clockwise, BooleanOp, ConvexPolygon, Line, LineIntersection, Polygon,
+    PolygonWithHoles,
+};
+use crate::path::{Cursor, LineCommand, Path};
+use euclid::Point2D;
+use fart_utils::Scalar;
+use std::iter;
+
+/// Which parts of a path to keep when clipping it to a region.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ClipMode {
+    /// Keep the parts of the path that are inside the region, including the
+    /// parts that run along its boundary.
+    Inside,
+
+    /// Keep the parts of the path that are outside the region.
+    Outside,
+}
+
+/// Clip a path to the inside or outside of a polygon, and get the visible
+/// pieces of it.
+///
+/// If the path is entirely visible, then it is returned as-is. Otherwise, each
+/// visible piece is returned as its own path, with the same color as the
+/// original. Curves and arcs are flattened into straight lines first, to within
+/// a thousandth of the polygon's size. Closed subpaths that are entirely
+/// visible stay closed.
+///
+/// ```
+/// use euclid::{point2, UnknownUnit};
+/// use fart::clip::{clip_path_to_polygon, ClipMode};
+/// use fart::geom::Polygon;
+/// use fart::path::{LineCommand, Path};
+///
+/// // A "U" shape.
+/// let cup = Polygon::<i64, UnknownUnit>::new(vec![
+///     point2(0, 0),
+///     point2(30, 0),
+///     point2(30, 30),
+///     point2(20, 30),
+///     point2(20, 10),
+///     point2(10, 10),
+///     point2(10, 30),
+///     point2(0, 30),
+/// ]);
+///
+/// // A horizontal line across the top of the cup.
+/// let mut path = Path::with_commands(vec![
+///     LineCommand::MoveTo(point2(-10, 20)),
+///     LineCommand::LineTo(point2(40, 20)),
+/// ]);
+/// path.color = "red".into();
+///
+/// // It is inside the cup's two walls.
+/// let inside = clip_path_to_polygon(&path, &cup, ClipMode::Inside);
+/// assert_eq!(inside.len(), 2);
+/// assert_eq!(inside[0].start(), Some(point2(0, 20)));
+/// assert_eq!(inside[0].end(), Some(point2(10, 20)));
+/// assert_eq!(inside[1].start(), Some(point2(20, 20)));
+/// assert_eq!(inside[1].end(), Some(point2(30, 20)));
+/// assert!(inside.iter().all(|p| p.color == "red"));
+///
+/// // And outside the cup on either side, and in its middle.
+/// let outside = clip_path_to_polygon(&path, &cup, ClipMode::Outside);
+/// assert_eq!(outside.len(), 3);
+/// ```
+pub fn clip_path_to_polygon<T, U>(
+    path: &Path<T, U>,
+    polygon: &Polygon<T, U>,
+    mode: ClipMode,
+) -> Vec<Path<T, U>>
+where
+    T: Scalar,
+    Path<T, U>: Clone,
+{
+    let polygon = polygon.cast::<f64>();
+    clip(
+        path,
+        polygon.edges().collect(),
+        |p| polygon.contains_point_even_odd(p),
+        mode,
+    )
+}
+
+/// Clip a path to the inside or outside of a convex polygon, and get the
+/// visible pieces of it.
+///
+/// This is just like `clip_path_to_polygon`, but takes advantage of the
+/// polygon's convexity when checking which pieces are inside it.
+///
+/// ```
+/// use euclid::{point2, UnknownUnit};
+/// use fart::clip::{clip_path_to_convex_polygon, ClipMode};
+/// use fart::geom::ConvexPolygon;
+/// use fart::path::{LineCommand, Path};
+///
+/// let triangle = ConvexPolygon::<f64, UnknownUnit>::hull(vec![
+///     point2(0.0, 0.0),
+///     point2(10.0, 0.0),
+///     point2(0.0, 10.0),
+/// ])
+/// .unwrap();
+///
+/// // A zig-zag that pokes out of the triangle's diagonal edge once.
+/// let path = Path::with_commands(vec![
+///     LineCommand::MoveTo(point2(1.0, 1.0)),
+///     LineCommand::LineTo(point2(9.0, 9.0)),
+///     LineCommand::LineTo(point2(2.0, 2.0)),
+/// ]);
+///
+/// let inside = clip_path_to_convex_polygon(&path, &triangle, ClipMode::Inside);
+/// assert_eq!(inside.len(), 2);
+/// assert_eq!(inside[0].end(), Some(point2(5.0, 5.0)));
+/// assert_eq!(inside[1].start(), Some(point2(5.0, 5.0)));
+///
+/// let outside = clip_path_to_convex_polygon(&path, &triangle, ClipMode::Outside);
+/// assert_eq!(outside.len(), 1);
+/// assert_eq!(outside[0].commands.len(), 3);
+/// ```
+pub fn clip_path_to_convex_polygon<T, U>(
+    path: &Path<T, U>,
+    polygon: &ConvexPolygon<T, U>,
+    mode: ClipMode,
+) -> Vec<Path<T, U>>
+where
+    T: Scalar,
+    Path<T, U>: Clone,
+{
+    let polygon = polygon.cast::<f64>();
+    clip(
+        path,
+        polygon.edges().collect(),
+        |p| polygon.contains_point(p),
+        mode,
+    )
+}
+
+/// Clip a path to the inside or outside of a region, like
+/// `clip_path_to_polygon`.
+pub(crate) fn clip_path_to_region<T, U>(
+    path: &Path<T, U>,
+    region: &[PolygonWithHoles<f64, U>],
+    mode: ClipMode,
+) -> Vec<Path<T, U>>
+where
+    T: Scalar,
+    Path<T, U>: Clone,
+{
+    let rings = || {
+        region
+            .iter()
+            .flat_map(|shape| iter::once(shape.exterior()).chain(shape.holes()))
+    };
+    clip(
+        path,
+        rings().flat_map(|ring| ring.edges()).collect(),
+        |p| rings().filter(|r| r.contains_point_even_odd(p)).count() % 2 == 1,
+        mode,
+    )
+}
+
+/// Get the region covered by an odd number of the given paths' subpaths, with
+/// curves flattened and each subpath treated as if it were closed.
+pub(crate) fn even_odd_region<'a, I, T, U>(paths: I) -> Vec<PolygonWithHoles<f64, U>>
+where
+    I: IntoIterator<Item = &'a Path<T, U>>,
+    T: 'a + Scalar,
+    U: 'a,
+{
+    let mut rings = vec![];
+    for path in paths {
+        let tolerance = match path.aabb() {
+            Some(aabb) => aabb.width().to_float().max(aabb.height().to_float()) / 1000.0,
+            None => continue,
+        };
+        let tolerance = if tolerance > 0.0 { tolerance } else { 1.0 };
+
+        let mut cursor = Cursor::new();
+        let mut ring: Vec<Point2D<f64, U>> = vec![];
+        for cmd in &path.commands {
+            let pen = cursor.position.cast::<f64>();
+            match cursor.resolve(cmd) {
+                None => rings.push(std::mem::take(&mut ring)),
+                Some(segment) => {
+                    if ring.is_empty() {
+                        ring.push(pen);
+                    }
+                    segment.flatten(tolerance, &mut ring);
+                    if let LineCommand::Close = cmd {
+                        rings.push(std::mem::take(&mut ring));
+                    }
+                }
+            }
+        }
+        rings.push(ring);
+    }
+
+    let mut region = vec![];
+    for mut ring in rings {
+        ring.dedup();
+        if ring.len() > 1 && ring.first() == ring.last() {
+            ring.pop();
+        }
+        if ring.len() < 3 {
+            continue;
+        }
+        if !is_counter_clockwise(&ring) {
+            ring.reverse();
+        }
+        let polygon = Polygon::new(ring);
+        if polygon.area() == 0.0 {
+            continue;
+        }
+        region = boolean(&region, &[polygon.into()], BooleanOp::Xor);
+    }
+    region
+}
+
+/// The core of the clipping functions, given the region's edges and a function
+/// to check whether a point is inside of it.
+fn clip<T, U, F>(
+    path: &Path<T, U>,
+    edges: Vec<Line<f64, U>>,
+    contains: F,
+    mode: ClipMode,
+) -> Vec<Path<T, U>>
+where
+    T: Scalar,
+    Path<T, U>: Clone,
+    F: Fn(Point2D<f64, U>) -> bool,
+{
+    if edges.is_empty() {
+        return match mode {
+            ClipMode::Inside => vec![],
+            ClipMode::Outside => vec![path.clone()],
+        };
+    }
+    let (mut min, mut max) = (edges[0].a, edges[0].a);
+    for e in &edges {
+        min = min.min(e.a).min(e.b);
+        max = max.max(e.a).max(e.b);
+    }
+    let clipper = Clipper {
+        edges,
+        min,
+        max,
+        contains,
+        mode,
+    };
+    let size = (max.x - min.x).max(max.y - min.y);
+    let tolerance = if size > 0.0 { size / 1000.0 } else { 1.0 };
+
+    // Flatten each subpath into a polyline, and clip that.
+    let mut hidden = false;
+    let mut runs = vec![];
+    let mut cursor = Cursor::new();
+    let mut polyline: Vec<Point2D<f64, U>> = vec![];
+    for cmd in &path.commands {
+        let pen = cursor.position.cast::<f64>();
+        match cursor.resolve(cmd) {
+            None => {
+                hidden |= clipper.clip_polyline(&polyline, false, &mut runs);
+                polyline.clear();
+            }
+            Some(segment) => {
+                if polyline.is_empty() {
+                    polyline.push(pen);
+                }
+                segment.flatten(tolerance, &mut polyline);
+                if let LineCommand::Close = cmd {
+                    hidden |= clipper.clip_polyline(&polyline, true, &mut runs);
+                    polyline.clear();
+                }
+            }
+        }
+    }
+    hidden |= clipper.clip_polyline(&polyline, false, &mut runs);
+    if !hidden {
+        return vec![path.clone()];
+    }
+
+    runs.into_iter()
+        .filter_map(|(points, closed)| {
+            let mut commands: Vec<LineCommand<T, U>> = vec![];
+            let mut last = None;
+            for p in points {
+                let p = Point2D::new(Scalar::from_float(p.x), Scalar::from_float(p.y));
+                if last == Some(p) {
+                    continue;
+                }
+                commands.push(match last {
+                    None => LineCommand::MoveTo(p),
+                    Some(_) => LineCommand::LineTo(p),
+                });
+                last = Some(p);
+            }
+            if commands.len() < 2 {
+                return None;
+            }
+            if closed {
+                commands.pop();
+                commands.push(LineCommand::Close);
+            }
+            Some(Path {
+                color: path.color.clone(),
+                commands,
+            })
+        })
+        .collect()
+}
+
+/// The region that paths are being clipped to.
+struct Clipper<U, F> {
+    edges: Vec<Line<f64, U>>,
+    min: Point2D<f64, U>,
+    max: Point2D<f64, U>,
+    contains: F,
+    mode: ClipMode,
+}
+
+impl<U, F> Clipper<U, F>
+where
+    F: Fn(Point2D<f64, U>) -> bool,
+{
+    /// Clip the given polyline, and push its visible runs onto `runs`, along
+    /// with whether each of them is a closed ring. Returns whether any of the
+    /// polyline was hidden.
+    fn clip_polyline(
+        &self,
+        polyline: &[Point2D<f64, U>],
+        closed: bool,
+        runs: &mut Vec<(Vec<Point2D<f64, U>>, bool)>,
+    ) -> bool {
+        let first = runs.len();
+        let mut current: Option<Vec<Point2D<f64, U>>> = None;
+        let mut starts_visible = None;
+        let mut hidden = false;
+        for pair in polyline.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            if a == b {
+                continue;
+            }
+            for (s, t, visible) in self.clip_segment(a, b) {
+                starts_visible.get_or_insert(visible);
+                if !visible {
+                    hidden = true;
+                    if let Some(run) = current.take() {
+                        runs.push((run, false));
+                    }
+                    continue;
+                }
+                let p = if t == 1.0 { b } else { a.lerp(b, t) };
+                match current {
+                    Some(ref mut run) => run.push(p),
+                    None => current = Some(vec![if s == 0.0 { a } else { a.lerp(b, s) }, p]),
+                }
+            }
+        }
+
+        let mut last = match current {
+            Some(run) => run,
+            None => return hidden,
+        };
+        if closed && !hidden {
+            runs.push((last, true));
+        } else if closed && starts_visible == Some(true) && runs.len() > first {
+            // The closed ring's last visible run continues into its first.
+            let (run, _) = runs.remove(first);
+            last.extend(run.into_iter().skip(1));
+            runs.push((last, false));
+        } else {
+            runs.push((last, false));
+        }
+        hidden
+    }
+
+    /// Split the segment from `a` to `b` where it crosses the region's
+    /// boundary, and get the parameters along the segment where each piece
+    /// starts and ends, and whether it is visible.
+    fn clip_segment(&self, a: Point2D<f64, U>, b: Point2D<f64, U>) -> Vec<(f64, f64, bool)> {
+        let visible = |inside: bool| match self.mode {
+            ClipMode::Inside => inside,
+            ClipMode::Outside => !inside,
+        };
+
+        let (lo, hi) = (a.min(b), a.max(b));
+        if hi.x < self.min.x || hi.y < self.min.y || lo.x > self.max.x || lo.y > self.max.y {
+            return vec![(0.0, 1.0, visible(false))];
+        }
+
+        let segment = Line::new(a, b);
+        let ab = b - a;
+        let param = |p: Point2D<f64, U>| ((p - a).dot(ab) / ab.square_length()).clamp(0.0, 1.0);
+        let mut params = vec![0.0, 1.0];
+        let mut boundary = vec![];
+        for edge in &self.edges {
+            match segment.intersection(edge) {
+                LineIntersection::None => {}
+                LineIntersection::Proper(p) | LineIntersection::Improper(p) => {
+                    params.push(param(p));
+                }
+                LineIntersection::Collinear(_) => {
+                    let (s, t) = (param(edge.a), param(edge.b));
+                    let (s, t) = if s < t { (s, t) } else { (t, s) };
+                    params.push(s);
+                    params.push(t);
+                    boundary.push((s, t));
+                }
+            }
+        }
+        params.sort_by(|x, y| x.partial_cmp(y).unwrap());
+        params.dedup();
+
+        params
+            .windows(2)
+            .map(|w| {
+                let (s, t) = (w[0], w[1]);
+                let m = (s + t) / 2.0;
+                let on_boundary = boundary.iter().any(|&(s, t)| s <= m && m <= t);
+                (s, t, visible(on_boundary || (self.contains)(a.lerp(b, m))))
+            })
+            .collect()
+    }
+}
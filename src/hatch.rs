@@ -0,0 +1,371 @@
+//! Filling shapes with hatching.
+//!
+//! Pen plotters can't fill areas, so solid areas are drawn as many closely
+//! spaced lines instead. See `hatch` and `ToPathsExt::hatch`.
+
+use crate::clip::even_odd_region;
+use crate::geom::{JoinStyle, Polygon, PolygonWithHoles};
+use crate::path::{LineCommand, Path, ToPaths};
+use euclid::{point2, Angle, Point2D};
+use fart_utils::Scalar;
+
+/// The pattern of lines to fill a shape with. See `hatch`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HatchStyle {
+    /// Parallel lines at the hatching angle.
+    Parallel,
+
+    /// Parallel lines at the hatching angle, crossed by parallel lines
+    /// perpendicular to them.
+    Crosshatch,
+
+    /// Parallel lines at the hatching angle, where neighboring lines are
+    /// joined at alternating ends into zig-zags, so that the pen doesn't need
+    /// to be lifted between them.
+    Zigzag,
+
+    /// Outlines of the shape, inset further and further by the spacing, like
+    /// the rings of a tree. The hatching angle is ignored.
+    Concentric,
+}
+
+/// Fill a shape with hatching, and get the hatch lines as paths.
+///
+/// The lines are `spacing` apart, and run at `angle` counter-clockwise from
+/// the x axis. They are aligned to a grid through the origin, rather than to
+/// the shape, so that neighboring shapes hatched the same way line up with
+/// each other. The grid is offset by half of the spacing, so that the lines
+/// don't run right along the edges of shapes that are aligned to it. The
+/// shape's outline is not included.
+///
+/// # Panics
+///
+/// Panics if `spacing` is not positive.
+///
+/// # Example
+///
+/// ```
+/// use euclid::{point2, Angle, UnknownUnit};
+/// use fart::geom::{Polygon, PolygonWithHoles};
+/// use fart::hatch::{hatch, HatchStyle};
+///
+/// let square = |min, max| -> Polygon<i64, UnknownUnit> {
+///     Polygon::new(vec![
+///         point2(min, min),
+///         point2(max, min),
+///         point2(max, max),
+///         point2(min, max),
+///     ])
+/// };
+/// let frame = PolygonWithHoles::new(square(0, 100), vec![square(40, 60)]);
+///
+/// // Horizontal lines at y = 5, 15, ..., 95. The lines at 45 and 55 are
+/// // split in two by the hole.
+/// let lines = hatch(&frame, Angle::radians(0.0), 10, HatchStyle::Parallel);
+/// assert_eq!(lines.len(), 12);
+///
+/// // The same lines, joined up into one long zig-zag that goes around the
+/// // hole on its left, and a short one on its right.
+/// let zigzags = hatch(&frame, Angle::radians(0.0), 10, HatchStyle::Zigzag);
+/// assert_eq!(zigzags.len(), 2);
+/// ```
+pub fn hatch<T, U>(
+    shape: &PolygonWithHoles<T, U>,
+    angle: Angle<f64>,
+    spacing: T,
+    style: HatchStyle,
+) -> Vec<Path<T, U>>
+where
+    T: Scalar,
+{
+    let region = PolygonWithHoles::new(
+        shape.exterior().cast(),
+        shape.holes().iter().map(|h| h.cast()).collect(),
+    );
+    let lines = hatch_region(&[region], angle, spacing.to_float(), style);
+    polylines_to_paths(lines, "black")
+}
+
+/// A `ToPaths` wrapper type that fills the shapes drawn by the inner type with
+/// hatching. Created using `ToPathsExt::hatch`.
+#[derive(Debug, Clone)]
+pub struct Hatch<P, T> {
+    inner: P,
+    angle: Angle<f64>,
+    spacing: T,
+    style: HatchStyle,
+}
+
+impl<P, T> Hatch<P, T> {
+    pub(crate) fn new(inner: P, angle: Angle<f64>, spacing: T, style: HatchStyle) -> Hatch<P, T> {
+        Hatch {
+            inner,
+            angle,
+            spacing,
+            style,
+        }
+    }
+}
+
+impl<P, T, U> ToPaths<T, U> for Hatch<P, T>
+where
+    P: ToPaths<T, U>,
+    T: Scalar,
+{
+    type Paths = std::vec::IntoIter<Path<T, U>>;
+
+    fn to_paths(&self) -> Self::Paths {
+        let outlines: Vec<_> = self.inner.to_paths().collect();
+
+        let mut colors: Vec<&str> = vec![];
+        for path in &outlines {
+            if !colors.contains(&path.color.as_str()) {
+                colors.push(&path.color);
+            }
+        }
+
+        let mut hatching = vec![];
+        for color in colors {
+            let region = even_odd_region(outlines.iter().filter(|p| p.color == color));
+            let lines = hatch_region(&region, self.angle, self.spacing.to_float(), self.style);
+            hatching.extend(polylines_to_paths(lines, color));
+        }
+
+        let mut paths = outlines;
+        paths.extend(hatching);
+        paths.into_iter()
+    }
+}
+
+/// A polyline, and whether it is closed.
+type Polyline<U> = (Vec<Point2D<f64, U>>, bool);
+
+/// Hatch the given region, getting polylines in the region's coordinates, and
+/// whether each of them is closed.
+fn hatch_region<U>(
+    region: &[PolygonWithHoles<f64, U>],
+    angle: Angle<f64>,
+    spacing: f64,
+    style: HatchStyle,
+) -> Vec<Polyline<U>> {
+    assert!(spacing > 0.0, "hatch spacing must be positive");
+    match style {
+        HatchStyle::Parallel => scanlines(region, angle, spacing, false),
+        HatchStyle::Crosshatch => {
+            let mut lines = scanlines(region, angle, spacing, false);
+            lines.extend(scanlines(
+                region,
+                angle + Angle::frac_pi_2(),
+                spacing,
+                false,
+            ));
+            lines
+        }
+        HatchStyle::Zigzag => scanlines(region, angle, spacing, true),
+        HatchStyle::Concentric => concentric(region, spacing),
+    }
+}
+
+/// Every ring of the given region: exteriors and holes alike.
+fn rings<U>(region: &[PolygonWithHoles<f64, U>]) -> impl Iterator<Item = &Polygon<f64, U>> {
+    region
+        .iter()
+        .flat_map(|shape| std::iter::once(shape.exterior()).chain(shape.holes()))
+}
+
+/// Intersect the region with parallel lines at the given angle, and get the
+/// pieces of the lines that are inside it. If `zigzag` is true, then the
+/// pieces are joined together at their ends where possible.
+fn scanlines<U>(
+    region: &[PolygonWithHoles<f64, U>],
+    angle: Angle<f64>,
+    spacing: f64,
+    zigzag: bool,
+) -> Vec<Polyline<U>> {
+    // Rotate the region so that the lines are horizontal.
+    let (sin, cos) = angle.sin_cos();
+    let rotate = |p: Point2D<f64, U>| point2(p.x * cos + p.y * sin, p.y * cos - p.x * sin);
+    let unrotate = |p: Point2D<f64, U>| point2(p.x * cos - p.y * sin, p.x * sin + p.y * cos);
+    let edges: Vec<_> = rings(region)
+        .flat_map(|ring| ring.edges())
+        .map(|e| (rotate(e.a), rotate(e.b)))
+        .collect();
+    if edges.is_empty() {
+        return vec![];
+    }
+
+    // Find each line's pieces inside the region, with the even-odd rule.
+    let min_y = edges.iter().fold(f64::INFINITY, |y, e| y.min(e.0.y));
+    let max_y = edges.iter().fold(-f64::INFINITY, |y, e| y.max(e.0.y));
+    let mut rows = vec![];
+    let first = (min_y / spacing - 0.5).ceil() as i64;
+    let last = (max_y / spacing - 0.5).floor() as i64;
+    for k in first..=last {
+        let y = (k as f64 + 0.5) * spacing;
+        let mut xs: Vec<f64> = edges
+            .iter()
+            .filter(|(a, b)| (a.y > y) != (b.y > y))
+            .map(|(a, b)| a.x + (y - a.y) * (b.x - a.x) / (b.y - a.y))
+            .collect();
+        xs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let pieces: Vec<_> = xs
+            .chunks(2)
+            .filter(|c| c.len() == 2 && c[0] < c[1])
+            .map(|c| (c[0], c[1]))
+            .collect();
+        if !pieces.is_empty() {
+            rows.push((y, pieces));
+        }
+    }
+
+    if !zigzag {
+        // Draw every other row backwards, so that the pen zig-zags back and
+        // forth instead of always jumping back to the same side.
+        let mut lines = vec![];
+        for (i, (y, pieces)) in rows.into_iter().enumerate() {
+            for (x0, x1) in pieces {
+                let (a, b) = (unrotate(point2(x0, y)), unrotate(point2(x1, y)));
+                lines.push((if i % 2 == 0 { vec![a, b] } else { vec![b, a] }, false));
+            }
+        }
+        return lines;
+    }
+
+    // Join each row's pieces onto the end of a zig-zag from the row before,
+    // if the link between them stays inside the region.
+    let scale = edges.iter().fold(0.0_f64, |s, (a, b)| {
+        s.max(a.x.abs())
+            .max(a.y.abs())
+            .max(b.x.abs())
+            .max(b.y.abs())
+    });
+    let eps = scale * 1e-9;
+    let link_inside = |p: Point2D<f64, U>, q: Point2D<f64, U>| {
+        let pq = q - p;
+        let crosses = edges.iter().any(|&(a, b)| {
+            let ab = b - a;
+            let (o1, o2) = (pq.cross(a - p), pq.cross(b - p));
+            let (o3, o4) = (ab.cross(p - a), ab.cross(q - a));
+            let (tol_pq, tol_ab) = (eps * pq.length(), eps * ab.length());
+            ((o1 > tol_pq && o2 < -tol_pq) || (o1 < -tol_pq && o2 > tol_pq))
+                && ((o3 > tol_ab && o4 < -tol_ab) || (o3 < -tol_ab && o4 > tol_ab))
+        });
+        if crosses {
+            return false;
+        }
+        let m = p.lerp(q, 0.5);
+        let on_boundary = edges.iter().any(|&(a, b)| {
+            let ab = b - a;
+            let t = ((m - a).dot(ab) / ab.square_length()).clamp(0.0, 1.0);
+            (m - a.lerp(b, t)).length() <= eps
+        });
+        let m = unrotate(m);
+        let inside = rings(region)
+            .filter(|r| r.contains_point_even_odd(m))
+            .count()
+            % 2
+            == 1;
+        on_boundary || inside
+    };
+
+    let mut chains: Vec<Vec<Point2D<f64, U>>> = vec![];
+    // The previous row's pieces, the chain each of them is the end of, and
+    // whether that chain ends at the piece's right end.
+    let mut prev: Vec<(f64, f64, usize, bool)> = vec![];
+    let mut prev_y = 0.0;
+    for (y, pieces) in rows {
+        let mut continued = vec![false; prev.len()];
+        let mut current = vec![];
+        for (x0, x1) in pieces {
+            let link = prev
+                .iter()
+                .enumerate()
+                .find_map(|(j, &(px0, px1, chain, right))| {
+                    if continued[j] || px1 < x0 || px0 > x1 {
+                        return None;
+                    }
+                    let from = point2(if right { px1 } else { px0 }, prev_y);
+                    let to = point2(if right { x1 } else { x0 }, y);
+                    if link_inside(from, to) {
+                        Some((j, chain, right))
+                    } else {
+                        None
+                    }
+                });
+            match link {
+                Some((j, chain, right)) => {
+                    continued[j] = true;
+                    let (near, far) = if right { (x1, x0) } else { (x0, x1) };
+                    chains[chain].push(point2(near, y));
+                    chains[chain].push(point2(far, y));
+                    current.push((x0, x1, chain, !right));
+                }
+                None => {
+                    chains.push(vec![point2(x0, y), point2(x1, y)]);
+                    current.push((x0, x1, chains.len() - 1, true));
+                }
+            }
+        }
+        prev = current;
+        prev_y = y;
+    }
+
+    chains
+        .into_iter()
+        .map(|chain| (chain.into_iter().map(unrotate).collect(), false))
+        .collect()
+}
+
+/// Outline the region, inset by every multiple of `spacing` until there's
+/// nothing left of it.
+fn concentric<U>(region: &[PolygonWithHoles<f64, U>], spacing: f64) -> Vec<Polyline<U>> {
+    let mut lines = vec![];
+    for k in 1.. {
+        let distance = -(k as f64) * spacing;
+        let inset: Vec<_> = region
+            .iter()
+            .flat_map(|p| p.offset(distance, JoinStyle::Round))
+            .collect();
+        if inset.is_empty() {
+            break;
+        }
+        for ring in rings(&inset) {
+            lines.push((ring.vertices().to_vec(), true));
+        }
+    }
+    lines
+}
+
+/// Convert hatching polylines into paths of the given color.
+fn polylines_to_paths<T, U>(lines: Vec<Polyline<U>>, color: &str) -> Vec<Path<T, U>>
+where
+    T: Scalar,
+{
+    lines
+        .into_iter()
+        .filter_map(|(points, closed)| {
+            let mut commands = vec![];
+            let mut last = None;
+            for p in points {
+                let p = point2(Scalar::from_float(p.x), Scalar::from_float(p.y));
+                if last != Some(p) {
+                    commands.push(match last {
+                        None => LineCommand::MoveTo(p),
+                        Some(_) => LineCommand::LineTo(p),
+                    });
+                    last = Some(p);
+                }
+            }
+            if commands.len() < 2 {
+                return None;
+            }
+            if closed {
+                commands.push(LineCommand::Close);
+            }
+            Some(Path {
+                color: color.into(),
+                commands,
+            })
+        })
+        .collect()
+}
@@ -33,7 +33,11 @@
 #![deny(missing_docs, missing_debug_implementations)]
 
 pub mod canvas;
+pub mod clip;
+pub mod hatch;
+pub mod optimize;
 pub mod path;
+pub mod planar;
 pub mod prelude;
 pub mod process;
 
@@ -0,0 +1,771 @@
+//! Passes that rewrite a collection of paths so that it plots faster and
+//! cleaner on a pen plotter.
+//!
+//! These are usually used via their `Canvas` methods, right before creating
+//! the final SVG.
+
+use crate::aabb::{Aabb, AabbTree, ToAabb};
+use crate::geom::Line;
+use crate::path::{Cursor, LineCommand, Path};
+use euclid::{point2, Point2D};
+use fart_utils::Scalar;
+use std::collections::VecDeque;
+
+/// How far the pen travels while lifted, before and after an optimization
+/// pass. Returned by `Canvas::optimize_pen_travel` and `optimize::pen_travel`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PenTravel {
+    /// The pen-up travel distance before optimizing.
+    pub before: f64,
+    /// The pen-up travel distance after optimizing.
+    pub after: f64,
+}
+
+impl PenTravel {
+    /// How much pen-up travel distance the optimization saved.
+    #[inline]
+    pub fn saved(&self) -> f64 {
+        self.before - self.after
+    }
+}
+
+/// Reorder the given paths, and choose which direction to draw each of them,
+/// so that the pen travels as little as possible while it is lifted.
+///
+/// The pen starts at `home`. Paths are kept grouped by color, in the order
+/// that each color first appears, so that the plotter only needs one pen change
+/// per color. Within each color, paths are ordered with a greedy
+/// nearest-neighbor pass, which is then refined with 2-opt moves.
+///
+/// If the result would be worse than the original order, the original order
+/// is kept.
+///
+/// ```
+/// use euclid::{point2, UnknownUnit};
+/// use fart::geom::line;
+/// use fart::optimize;
+/// use fart::path::{Path, ToPaths};
+///
+/// let mut paths: Vec<Path<i64, UnknownUnit>> = vec![
+///     line(point2(0, 0), point2(10, 0)).to_paths().next().unwrap(),
+///     line(point2(100, 0), point2(90, 0)).to_paths().next().unwrap(),
+///     line(point2(20, 0), point2(10, 0)).to_paths().next().unwrap(),
+/// ];
+///
+/// let travel = optimize::pen_travel(&mut paths, point2(0, 0));
+/// assert_eq!(travel.before, 160.0);
+/// assert_eq!(travel.after, 70.0);
+/// assert_eq!(travel.saved(), 90.0);
+///
+/// // The second line is now drawn reversed, continuing from the first.
+/// assert_eq!(paths[1].start(), Some(point2(10, 0)));
+/// assert_eq!(paths[1].end(), Some(point2(20, 0)));
+/// ```
+pub fn pen_travel<T, U>(paths: &mut Vec<Path<T, U>>, home: Point2D<T, U>) -> PenTravel
+where
+    T: Scalar,
+    Path<T, U>: Clone,
+{
+    let home = home.cast::<f64>();
+    let before = travel_distance(paths.iter(), home);
+
+    // Group the paths by color, keeping empty paths (which don't move the pen
+    // at all) to the side.
+    let mut groups: Vec<(String, Vec<Path<T, U>>)> = vec![];
+    let mut empty = vec![];
+    for path in paths.iter() {
+        if path.commands.is_empty() {
+            empty.push(path.clone());
+            continue;
+        }
+        match groups.iter_mut().find(|(color, _)| *color == path.color) {
+            Some((_, group)) => group.push(path.clone()),
+            None => groups.push((path.color.clone(), vec![path.clone()])),
+        }
+    }
+
+    let mut optimized = Vec::with_capacity(paths.len());
+    let mut pen = home;
+    for (_, group) in groups {
+        let endpoints = group
+            .iter()
+            .map(|p| {
+                (
+                    p.start().unwrap().cast::<f64>(),
+                    p.end().unwrap().cast::<f64>(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut tour = nearest_neighbor_tour(&endpoints, pen);
+        two_opt(&mut tour, &endpoints, pen);
+
+        if let Some(&(i, reversed)) = tour.last() {
+            pen = if reversed {
+                endpoints[i].0
+            } else {
+                endpoints[i].1
+            };
+        }
+        optimized.extend(tour.into_iter().map(|(i, reversed)| {
+            if reversed {
+                group[i].reversed()
+            } else {
+                group[i].clone()
+            }
+        }));
+    }
+    optimized.extend(empty);
+
+    let after = travel_distance(optimized.iter(), home);
+    if after < before {
+        *paths = optimized;
+        PenTravel { before, after }
+    } else {
+        PenTravel {
+            before,
+            after: before,
+        }
+    }
+}
+
+/// Join paths of the same color whose end points are within `tolerance` of
+/// each other into single, continuous paths, reversing paths as needed.
+///
+/// Each merged path takes the place of the first of its pieces. Where the
+/// pieces' end points don't exactly coincide, the gap between them is bridged
+/// with a straight line. Returns how many paths were merged away.
+///
+/// ```
+/// use euclid::{point2, UnknownUnit};
+/// use fart::geom::line;
+/// use fart::optimize;
+/// use fart::path::{Path, ToPaths};
+///
+/// let mut paths: Vec<Path<i64, UnknownUnit>> = vec![
+///     line(point2(0, 0), point2(10, 0)).to_paths().next().unwrap(),
+///     line(point2(20, 0), point2(10, 0)).to_paths().next().unwrap(),
+///     line(point2(21, 1), point2(20, 10)).to_paths().next().unwrap(),
+///     line(point2(50, 50), point2(60, 60)).to_paths().next().unwrap(),
+/// ];
+///
+/// let merged = optimize::merge_paths(&mut paths, 2);
+/// assert_eq!(merged, 2);
+///
+/// assert_eq!(paths.len(), 2);
+/// assert_eq!(paths[0].start(), Some(point2(0, 0)));
+/// assert_eq!(paths[0].end(), Some(point2(20, 10)));
+/// ```
+pub fn merge_paths<T, U>(paths: &mut Vec<Path<T, U>>, tolerance: T) -> usize
+where
+    T: Scalar,
+{
+    let tolerance = tolerance.to_float();
+
+    let endpoints = paths
+        .iter()
+        .map(|p| Some((p.start()?.cast::<f64>(), p.end()?.cast::<f64>())))
+        .collect::<Vec<_>>();
+    let colors = paths.iter().map(|p| p.color.clone()).collect::<Vec<_>>();
+
+    let mut tree = AabbTree::new();
+    for (i, e) in endpoints.iter().enumerate() {
+        if let Some((start, end)) = *e {
+            tree.insert(Aabb::new(start, start), 2 * i);
+            tree.insert(Aabb::new(end, end), 2 * i + 1);
+        }
+    }
+
+    // Find the closest end point, within tolerance of `p`, of an unused path
+    // of the given color. As with `pen_travel`, the tree's values are `2 * i`
+    // for the start of the `i`th path and `2 * i + 1` for its end.
+    let find = |used: &[bool], color: &str, p: Point2D<f64, U>| {
+        let mut best: Option<(usize, f64)> = None;
+        for (_, &e) in tree.iter_overlapping(square_around(p, tolerance)) {
+            let i = e / 2;
+            if used[i] || colors[i] != color {
+                continue;
+            }
+            let (start, end) = endpoints[i].unwrap();
+            let d = (if e % 2 == 0 { start } else { end } - p).length();
+            match best {
+                _ if d > tolerance => {}
+                Some((_, best_d)) if best_d <= d => {}
+                _ => best = Some((e, d)),
+            }
+        }
+        best.map(|(e, _)| e)
+    };
+
+    let mut used = vec![false; paths.len()];
+    let mut slots = paths.drain(..).map(Some).collect::<Vec<_>>();
+    let mut merged = 0;
+
+    for i in 0..slots.len() {
+        if used[i] {
+            continue;
+        }
+        used[i] = true;
+
+        let (start, end) = match endpoints[i] {
+            Some(e) => e,
+            None => {
+                paths.push(slots[i].take().unwrap());
+                continue;
+            }
+        };
+
+        // Grow a chain of `(path index, reversed)` pieces forwards from this
+        // path's end, and then backwards from its start.
+        let mut chain = VecDeque::new();
+        chain.push_back((i, false));
+
+        let mut tail = end;
+        while let Some(e) = find(&used, &colors[i], tail) {
+            let (j, reversed) = (e / 2, e % 2 == 1);
+            used[j] = true;
+            chain.push_back((j, reversed));
+            let (start, end) = endpoints[j].unwrap();
+            tail = if reversed { start } else { end };
+        }
+
+        let mut head = start;
+        while let Some(e) = find(&used, &colors[i], head) {
+            let (j, reversed) = (e / 2, e % 2 == 0);
+            used[j] = true;
+            chain.push_front((j, reversed));
+            let (start, end) = endpoints[j].unwrap();
+            head = if reversed { end } else { start };
+        }
+
+        merged += chain.len() - 1;
+
+        let mut pieces = chain.into_iter().map(|(j, reversed)| {
+            let piece = slots[j].take().unwrap();
+            if reversed {
+                piece.reversed()
+            } else {
+                piece
+            }
+        });
+        let mut path = pieces.next().unwrap();
+        for piece in pieces {
+            continue_path(&mut path, piece);
+        }
+        paths.push(path);
+    }
+
+    merged
+}
+
+/// Append `next` onto `path`, continuing from where `path` leaves the pen
+/// rather than lifting the pen and moving to `next`'s start.
+fn continue_path<T, U>(path: &mut Path<T, U>, next: Path<T, U>)
+where
+    T: Scalar,
+{
+    let pen = path.end().unwrap();
+    let start = next.start().unwrap();
+    if start != pen {
+        path.commands.push(LineCommand::LineTo(start));
+    }
+
+    // `next`'s leading moves are dropped, so its first subpath becomes part of
+    // `path`'s last subpath. Any `Close` commands in it would then return to
+    // the wrong point, so they are replaced with explicit lines back to
+    // `next`'s start.
+    let mut first_subpath = true;
+    for cmd in next
+        .commands
+        .into_iter()
+        .skip_while(|c| matches!(c, LineCommand::MoveTo(_) | LineCommand::MoveBy(_)))
+    {
+        match cmd {
+            LineCommand::MoveTo(_) | LineCommand::MoveBy(_) => {
+                first_subpath = false;
+                path.commands.push(cmd);
+            }
+            LineCommand::Close if first_subpath => {
+                path.commands.push(LineCommand::LineTo(start));
+            }
+            cmd => path.commands.push(cmd),
+        }
+    }
+}
+
+/// Remove the parts of line segments that would be drawn on top of a segment
+/// of the same color that was already drawn.
+///
+/// Straight segments (`LineTo`, `LineBy`, horizontal and vertical lines, and
+/// `Close`) are checked against all of the same color's segments that come
+/// before them. Wherever a segment is collinear with, and overlaps, an earlier
+/// segment, as reported by `Line::intersection`, the overlapping part is
+/// removed from the later segment and the pen is lifted over it instead.
+/// Curves and arcs are left as they are. Paths that no longer draw anything are
+/// removed.
+///
+/// Returns how many segments were removed or shortened.
+///
+/// ```
+/// use euclid::{point2, UnknownUnit};
+/// use fart::aabb::Aabb;
+/// use fart::optimize;
+/// use fart::path::{LineCommand, Path, ToPaths};
+///
+/// // Two squares that share the edge along `x = 10`.
+/// let mut paths: Vec<Path<i64, UnknownUnit>> = vec![
+///     Aabb::new(point2(0, 0), point2(10, 10)).to_paths().next().unwrap(),
+///     Aabb::new(point2(10, 5), point2(20, 15)).to_paths().next().unwrap(),
+/// ];
+///
+/// let deduped = optimize::dedup_segments(&mut paths);
+/// assert_eq!(deduped, 1);
+///
+/// // The second square's left edge now stops where the first square's right
+/// // edge begins.
+/// assert_eq!(paths[1].end(), Some(point2(10, 10)));
+///
+/// // A segment that starts on top of an earlier one just starts where that one
+/// // ends instead.
+/// let mut paths: Vec<Path<i64, UnknownUnit>> = vec![
+///     Path::with_commands(vec![
+///         LineCommand::MoveTo(point2(0, 0)),
+///         LineCommand::LineTo(point2(10, 0)),
+///     ]),
+///     Path::with_commands(vec![
+///         LineCommand::MoveTo(point2(5, 0)),
+///         LineCommand::LineTo(point2(20, 0)),
+///     ]),
+/// ];
+///
+/// optimize::dedup_segments(&mut paths);
+/// assert_eq!(paths[1].commands.len(), 2);
+/// assert_eq!(paths[1].start(), Some(point2(10, 0)));
+/// assert_eq!(paths[1].end(), Some(point2(20, 0)));
+/// ```
+pub fn dedup_segments<T, U>(paths: &mut Vec<Path<T, U>>) -> usize
+where
+    T: Scalar,
+{
+    let mut colors: Vec<String> = vec![];
+    let mut drawn: Vec<(usize, Line<f64, U>)> = vec![];
+    let mut tree = AabbTree::new();
+    let mut deduped = 0;
+
+    for path in paths.iter_mut() {
+        let color = match colors.iter().position(|c| *c == path.color) {
+            Some(c) => c,
+            None => {
+                colors.push(path.color.clone());
+                colors.len() - 1
+            }
+        };
+
+        let mut cursor = Cursor::new();
+        let mut commands = Vec::with_capacity(path.commands.len());
+
+        // Where a `Close` in `commands` would return the pen to. This differs
+        // from `cursor.subpath_start` once the pen has been lifted over a
+        // duplicate segment in the middle of a subpath.
+        let mut close_to = cursor.subpath_start;
+
+        // Whether the last command in `commands` is a move that lifts the pen
+        // over a duplicate segment.
+        let mut lifted = false;
+
+        for cmd in std::mem::take(&mut path.commands) {
+            let from = cursor.position;
+            let subpath_start = cursor.subpath_start;
+            cursor.advance(&cmd);
+            let to = cursor.position;
+
+            match cmd {
+                LineCommand::MoveTo(_) | LineCommand::MoveBy(_) => {
+                    if lifted {
+                        commands.pop();
+                        commands.push(LineCommand::MoveTo(to));
+                    } else {
+                        commands.push(cmd);
+                    }
+                    close_to = to;
+                    lifted = false;
+                    continue;
+                }
+                LineCommand::LineTo(_)
+                | LineCommand::LineBy(_)
+                | LineCommand::HorizontalLineTo(_)
+                | LineCommand::HorizontalLineBy(_)
+                | LineCommand::VerticalLineTo(_)
+                | LineCommand::VerticalLineBy(_)
+                | LineCommand::Close
+                    if from != to => {}
+                _ => {
+                    commands.push(match cmd {
+                        LineCommand::Close if close_to != subpath_start => LineCommand::LineTo(to),
+                        cmd => cmd,
+                    });
+                    lifted = false;
+                    continue;
+                }
+            }
+
+            let segment = Line::new(from.cast::<f64>(), to.cast::<f64>());
+            let pieces = uncovered(
+                &segment,
+                tree.iter_overlapping(segment_aabb(&segment))
+                    .map(|(_, &i)| &drawn[i])
+                    .filter(|(c, _)| *c == color)
+                    .map(|(_, l)| l),
+            );
+            tree.insert(segment_aabb(&segment), drawn.len());
+            drawn.push((color, Line::new(segment.a, segment.b)));
+
+            if pieces == [(0.0, 1.0)] {
+                commands.push(match cmd {
+                    LineCommand::Close if close_to != subpath_start => LineCommand::LineTo(to),
+                    cmd => cmd,
+                });
+                lifted = false;
+                continue;
+            }
+
+            deduped += 1;
+            let point_at = |t: f64| {
+                if t == 0.0 {
+                    from
+                } else if t == 1.0 {
+                    to
+                } else {
+                    let p = segment.a.lerp(segment.b, t);
+                    point2(Scalar::from_float(p.x), Scalar::from_float(p.y))
+                }
+            };
+            let mut pen = from;
+            for (t0, t1) in pieces {
+                let (a, b) = (point_at(t0), point_at(t1));
+                if a == b {
+                    continue;
+                }
+                if a != pen {
+                    // Move the pen straight there, rather than moving twice.
+                    if matches!(
+                        commands.last(),
+                        Some(LineCommand::MoveTo(_)) | Some(LineCommand::MoveBy(_))
+                    ) {
+                        commands.pop();
+                    }
+                    commands.push(LineCommand::MoveTo(a));
+                    close_to = a;
+                }
+                commands.push(LineCommand::LineTo(b));
+                pen = b;
+            }
+            lifted = pen != to;
+            if lifted {
+                commands.push(LineCommand::MoveTo(to));
+                close_to = to;
+            }
+        }
+
+        if lifted {
+            commands.pop();
+        }
+        path.commands = commands;
+    }
+
+    paths.retain(|path| {
+        path.commands
+            .iter()
+            .any(|c| !matches!(c, LineCommand::MoveTo(_) | LineCommand::MoveBy(_)))
+    });
+
+    deduped
+}
+
+/// The parts of `segment`, as ranges of its parameter from `0.0` at `segment.a`
+/// to `1.0` at `segment.b`, that are not covered by any of the given segments.
+fn uncovered<'a, U: 'a>(
+    segment: &Line<f64, U>,
+    others: impl Iterator<Item = &'a Line<f64, U>>,
+) -> Vec<(f64, f64)> {
+    let d = segment.b - segment.a;
+    let t = |p: Point2D<f64, U>| (p - segment.a).dot(d) / d.square_length();
+
+    let mut pieces: Vec<(f64, f64)> = vec![(0.0, 1.0)];
+    for other in others {
+        if !segment.intersection(other).is_collinear() {
+            continue;
+        }
+        let (lo, hi) = match (t(other.a), t(other.b)) {
+            (t0, t1) if t0 <= t1 => (t0, t1),
+            (t0, t1) => (t1, t0),
+        };
+        pieces = pieces
+            .into_iter()
+            .flat_map(|(a, b)| vec![(a, b.min(lo)), (a.max(hi), b)])
+            .filter(|(a, b)| b - a > 1e-9)
+            .collect();
+    }
+    pieces
+}
+
+/// Get the bounding box of the given segment, inflated a little so that the
+/// boxes of horizontal or vertical segments along the same line overlap.
+fn segment_aabb<U>(segment: &Line<f64, U>) -> Aabb<f64, U> {
+    let aabb = segment.to_aabb();
+    let (min, max) = (aabb.min(), aabb.max());
+    let slop = (min.x.abs() + min.y.abs() + max.x.abs() + max.y.abs()) * 1e-9 + f64::EPSILON;
+    Aabb::new(
+        point2(min.x - slop, min.y - slop),
+        point2(max.x + slop, max.y + slop),
+    )
+}
+
+/// The total pen-up distance traveled when drawing the given paths in order,
+/// starting from `home`.
+fn travel_distance<'a, T, U>(
+    paths: impl Iterator<Item = &'a Path<T, U>>,
+    home: Point2D<f64, U>,
+) -> f64
+where
+    T: 'a + Scalar,
+    U: 'a,
+{
+    let mut pen = home;
+    let mut distance = 0.0;
+    for path in paths {
+        if let (Some(start), Some(end)) = (path.start(), path.end()) {
+            distance += (start.cast::<f64>() - pen).length();
+            pen = end.cast();
+        }
+    }
+    distance
+}
+
+/// Get the square centered on `p` that contains the circle of radius `r`
+/// around `p`.
+fn square_around<U>(p: Point2D<f64, U>, r: f64) -> Aabb<f64, U> {
+    // Inflate the square a little, since `Aabb::intersects` does not consider
+    // boxes that merely touch as overlapping.
+    let r = r + (r + p.x.abs() + p.y.abs()) * 1e-9 + f64::EPSILON;
+    Aabb::new(point2(p.x - r, p.y - r), point2(p.x + r, p.y + r))
+}
+
+/// A tour visits each path once, as an index and whether it is drawn reversed.
+type Tour = Vec<(usize, bool)>;
+
+/// Each path's start and end points, indexed like the paths.
+type Endpoints<U> = [(Point2D<f64, U>, Point2D<f64, U>)];
+
+/// Where the pen enters and exits the given tour stop.
+#[inline]
+fn entry_exit<U>(
+    endpoints: &Endpoints<U>,
+    stop: (usize, bool),
+) -> (Point2D<f64, U>, Point2D<f64, U>) {
+    let (start, end) = endpoints[stop.0];
+    if stop.1 {
+        (end, start)
+    } else {
+        (start, end)
+    }
+}
+
+/// Build a tour by repeatedly drawing whichever remaining path has an end point
+/// closest to the pen.
+fn nearest_neighbor_tour<U>(endpoints: &Endpoints<U>, home: Point2D<f64, U>) -> Tour {
+    let n = endpoints.len();
+    if n == 0 {
+        return vec![];
+    }
+
+    let bounds = Aabb::for_vertices(
+        endpoints
+            .iter()
+            .flat_map(|&(a, b)| vec![a, b])
+            .chain(Some(home)),
+    );
+    let diagonal = (bounds.max() - bounds.min()).length();
+    let initial_radius = f64::max(diagonal / (n as f64).sqrt(), f64::EPSILON);
+
+    // The tree's values are `2 * i` for the start of the `i`th path and
+    // `2 * i + 1` for its end. The `AabbTree` doesn't support removal, so
+    // visited paths are filtered out, and the tree is rebuilt once it is
+    // mostly full of visited paths.
+    let build_tree = |visited: &[bool]| {
+        let mut tree = AabbTree::new();
+        let mut len = 0;
+        for (i, &(start, end)) in endpoints.iter().enumerate() {
+            if !visited[i] {
+                tree.insert(Aabb::new(start, start), 2 * i);
+                tree.insert(Aabb::new(end, end), 2 * i + 1);
+                len += 2;
+            }
+        }
+        (tree, len)
+    };
+
+    let mut visited = vec![false; n];
+    let (mut tree, mut tree_len) = build_tree(&visited);
+    let mut stale = 0;
+
+    let mut tour = Vec::with_capacity(n);
+    let mut pen = home;
+    while tour.len() < n {
+        let mut radius = initial_radius;
+        let entry = loop {
+            let mut best: Option<(usize, f64)> = None;
+            for (_, &e) in tree.iter_overlapping(square_around(pen, radius)) {
+                if visited[e / 2] {
+                    continue;
+                }
+                let (start, end) = endpoints[e / 2];
+                let p = if e % 2 == 0 { start } else { end };
+                let d = (p - pen).length();
+                match best {
+                    Some((_, best_d)) if best_d <= d => {}
+                    _ => best = Some((e, d)),
+                }
+            }
+
+            match best {
+                // Anything closer than the best point would be inside the
+                // query's inscribed circle, so we found the nearest point.
+                Some((e, d)) if d <= radius => break e,
+                // The best point is in a corner of the query box, and there
+                // may be closer points just outside the box. Query again,
+                // making sure that the best point is within the circle.
+                Some((_, d)) => radius = d,
+                None => radius *= 2.0,
+            }
+        };
+
+        let i = entry / 2;
+        let reversed = entry % 2 == 1;
+        visited[i] = true;
+        stale += 2;
+        tour.push((i, reversed));
+        pen = entry_exit(endpoints, (i, reversed)).1;
+
+        if stale * 2 > tree_len {
+            let rebuilt = build_tree(&visited);
+            tree = rebuilt.0;
+            tree_len = rebuilt.1;
+            stale = 0;
+        }
+    }
+
+    tour
+}
+
+/// Refine a tour with 2-opt moves.
+///
+/// A 2-opt move reverses a run of stops in the tour (drawing each of them in
+/// the opposite direction as well), which replaces the two connections at the
+/// run's ends with two new connections. Only moves where one of the new
+/// connections is shorter than the connection it replaces can be improvements,
+/// so candidates are found by looking for nearby end points in an `AabbTree`.
+fn two_opt<U>(tour: &mut Tour, endpoints: &Endpoints<U>, home: Point2D<f64, U>) {
+    let n = tour.len();
+    if n < 2 {
+        return;
+    }
+
+    let mut tree = AabbTree::new();
+    for (i, &(start, end)) in endpoints.iter().enumerate() {
+        tree.insert(Aabb::new(start, start), 2 * i);
+        tree.insert(Aabb::new(end, end), 2 * i + 1);
+    }
+
+    // `position[i]` is the index of the `i`th path within the tour.
+    let mut position = vec![0; n];
+    for (k, &(i, _)) in tour.iter().enumerate() {
+        position[i] = k;
+    }
+
+    let dist = |a: Point2D<f64, U>, b: Point2D<f64, U>| (a - b).length();
+
+    let mut improved = true;
+    while improved {
+        improved = false;
+
+        for k in 0..n {
+            // Reversing the run `i..=j` replaces the connections
+            // `exit(i - 1) -> entry(i)` and `exit(j) -> entry(j + 1)` with
+            // `exit(i - 1) -> exit(j)` and `entry(i) -> entry(j + 1)`.
+            let delta = |tour: &Tour, i: usize, j: usize| {
+                let prev_exit = if i == 0 {
+                    home
+                } else {
+                    entry_exit(endpoints, tour[i - 1]).1
+                };
+                let (entry_i, _) = entry_exit(endpoints, tour[i]);
+                let (_, exit_j) = entry_exit(endpoints, tour[j]);
+                let mut delta = dist(prev_exit, exit_j) - dist(prev_exit, entry_i);
+                if j + 1 < n {
+                    let (next_entry, _) = entry_exit(endpoints, tour[j + 1]);
+                    delta += dist(entry_i, next_entry) - dist(exit_j, next_entry);
+                }
+                delta
+            };
+
+            // Candidates where the new `exit(i - 1) -> exit(j)` connection is
+            // shorter, with `i = k`.
+            let mut candidates = vec![];
+            {
+                let i = k;
+                let prev_exit = if i == 0 {
+                    home
+                } else {
+                    entry_exit(endpoints, tour[i - 1]).1
+                };
+                let (entry_i, _) = entry_exit(endpoints, tour[i]);
+                for (_, &e) in
+                    tree.iter_overlapping(square_around(prev_exit, dist(prev_exit, entry_i)))
+                {
+                    let j = position[e / 2];
+                    let is_exit = (e % 2 == 1) != tour[j].1;
+                    if j > i && is_exit {
+                        candidates.push((i, j));
+                    }
+                }
+            }
+
+            // Candidates where the new `entry(i) -> entry(j + 1)` connection
+            // is shorter, with `j = k`.
+            if k + 1 < n {
+                let j = k;
+                let (_, exit_j) = entry_exit(endpoints, tour[j]);
+                let (next_entry, _) = entry_exit(endpoints, tour[j + 1]);
+                for (_, &e) in
+                    tree.iter_overlapping(square_around(next_entry, dist(exit_j, next_entry)))
+                {
+                    let i = position[e / 2];
+                    let is_entry = (e % 2 == 0) != tour[i].1;
+                    if i < j && is_entry {
+                        candidates.push((i, j));
+                    }
+                }
+            }
+
+            let best = candidates
+                .into_iter()
+                .map(|(i, j)| (i, j, delta(tour, i, j)))
+                .fold(None, |best: Option<(usize, usize, f64)>, c| match best {
+                    Some(b) if b.2 <= c.2 => Some(b),
+                    _ => Some(c),
+                });
+
+            if let Some((i, j, d)) = best {
+                if d < -1e-9 {
+                    tour[i..=j].reverse();
+                    for k in i..=j {
+                        tour[k].1 = !tour[k].1;
+                        position[tour[k].0] = k;
+                    }
+                    improved = true;
+                }
+            }
+        }
+    }
+}
@@ -3,13 +3,22 @@
 //! Everything in a scene is eventually lowered into a series of paths, which
 //! are finally compiled to SVG.
 
+use crate::aabb::{Aabb, ToAabb};
 use crate::canvas::CanvasSpace;
-use euclid::{point2, vec2, Point2D, Vector2D};
-use num_traits::{Num, NumAssign, NumCast, Signed};
+use crate::hatch::{Hatch, HatchStyle};
+use euclid::{point2, vec2, Angle, Point2D, Vector2D};
+use fart_2d_geom::{
+    catmull_rom, is_counter_clockwise, simplify_polyline, stroke, Arc, CatmullRom, Circle,
+    CubicBezier, Ellipse, JoinStyle, LineCap, Polygon, PolygonWithHoles, QuadraticBezier,
+    SimplifyAlgorithm,
+};
+use fart_utils::Scalar;
 use std::borrow::Cow;
+use std::f64::consts::PI;
 use std::fmt::Debug;
 use std::iter;
 use std::marker::PhantomData;
+use std::mem;
 
 /// A series of line commands that describe a path.
 #[derive(Clone, Debug)]
@@ -158,7 +167,7 @@ pub enum LineCommand<T, U> {
         /// The y radius of the arc's ellipse.
         y_radius: T,
         /// The rotation of the arc.
-        x_axis_rotation: T,
+        x_axis_rotation: euclid::Angle<T>,
         /// Should the arc be greater than or less than 180 degrees?
         large_arc_flag: bool,
         /// Determines whether to begin moving at positive or negative angles.
@@ -170,7 +179,7 @@ pub enum LineCommand<T, U> {
 
 impl<T, U> LineCommand<T, U>
 where
-    T: Copy + Num + PartialOrd,
+    T: Scalar,
 {
     /// Transform this line command with the given linear transformation and
     /// return the new, transformed line command.
@@ -280,13 +289,13 @@ where
 
 impl<T, U> LineCommand<T, U>
 where
-    T: Copy + NumCast,
+    T: Scalar,
 {
     /// Cast from number representation `T` to number representation `V`.
     #[inline]
     pub fn cast<V>(&self) -> LineCommand<V, U>
     where
-        V: NumCast + Copy,
+        V: Scalar,
     {
         match *self {
             LineCommand::MoveTo(p) => LineCommand::MoveTo(p.cast()),
@@ -370,7 +379,9 @@ where
                 LineCommand::ArcBy {
                     x_radius: radius.x,
                     y_radius: radius.y,
-                    x_axis_rotation: V::from(x_axis_rotation).unwrap(),
+                    x_axis_rotation: euclid::Angle::radians(
+                        V::from(x_axis_rotation.get()).unwrap(),
+                    ),
                     large_arc_flag,
                     sweep_flag,
                     end: end.cast(),
@@ -400,13 +411,13 @@ impl<T, U> Path<T, U> {
 
 impl<T, U> Path<T, U>
 where
-    T: Copy + NumCast,
+    T: Scalar,
 {
     /// Cast from number representation `T` to number representation `V`.
     #[inline]
     pub fn cast<V>(&self) -> Path<V, U>
     where
-        V: NumCast + Copy,
+        V: Scalar,
     {
         Path {
             color: self.color.clone(),
@@ -417,7 +428,7 @@ where
 
 impl<T, U> Path<T, U>
 where
-    T: Copy + Num + PartialOrd,
+    T: Scalar,
 {
     /// Transform this path with the given linear transformation and return
     /// the new, transformed path.
@@ -438,50 +449,1301 @@ where
             c.transform_in_place(transformation);
         }
     }
+
+    /// Get the point where this path starts drawing, after any leading move
+    /// commands.
+    ///
+    /// Returns `None` if this path has no commands.
+    ///
+    /// ```
+    /// use euclid::{point2, vec2, UnknownUnit};
+    /// use fart::path::{LineCommand, Path};
+    ///
+    /// let path = Path::<i32, UnknownUnit>::with_commands(vec![
+    ///     LineCommand::MoveTo(point2(1, 1)),
+    ///     LineCommand::MoveBy(vec2(2, 0)),
+    ///     LineCommand::LineTo(point2(5, 5)),
+    /// ]);
+    ///
+    /// assert_eq!(path.start(), Some(point2(3, 1)));
+    /// ```
+    pub fn start(&self) -> Option<Point2D<T, U>> {
+        if self.commands.is_empty() {
+            return None;
+        }
+
+        let mut cursor = Cursor::new();
+        for cmd in &self.commands {
+            match cmd {
+                LineCommand::MoveTo(_) | LineCommand::MoveBy(_) => cursor.advance(cmd),
+                _ => break,
+            }
+        }
+        Some(cursor.position)
+    }
+
+    /// Get the point where this path leaves the pen once it is done drawing.
+    ///
+    /// Returns `None` if this path has no commands.
+    ///
+    /// ```
+    /// use euclid::{point2, vec2, UnknownUnit};
+    /// use fart::path::{LineCommand, Path};
+    ///
+    /// let path = Path::<i32, UnknownUnit>::with_commands(vec![
+    ///     LineCommand::MoveTo(point2(1, 1)),
+    ///     LineCommand::LineBy(vec2(2, 0)),
+    ///     LineCommand::VerticalLineTo(5),
+    /// ]);
+    ///
+    /// assert_eq!(path.end(), Some(point2(3, 5)));
+    ///
+    /// // Closing a path returns the pen to where the path started.
+    /// let closed = Path::<i32, UnknownUnit>::with_commands(vec![
+    ///     LineCommand::MoveTo(point2(1, 1)),
+    ///     LineCommand::LineTo(point2(3, 1)),
+    ///     LineCommand::LineTo(point2(3, 3)),
+    ///     LineCommand::Close,
+    /// ]);
+    ///
+    /// assert_eq!(closed.end(), Some(point2(1, 1)));
+    /// ```
+    pub fn end(&self) -> Option<Point2D<T, U>> {
+        if self.commands.is_empty() {
+            return None;
+        }
+
+        let mut cursor = Cursor::new();
+        for cmd in &self.commands {
+            cursor.advance(cmd);
+        }
+        Some(cursor.position)
+    }
+
+    /// Does this path outline a closed shape?
+    ///
+    /// That is, does every one of its subpaths end back where it started,
+    /// either with a `Close` command or otherwise? Paths that don't draw
+    /// anything are not closed.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart::path::{LineCommand, Path};
+    ///
+    /// let triangle = Path::<i32, UnknownUnit>::with_commands(vec![
+    ///     LineCommand::MoveTo(point2(0, 0)),
+    ///     LineCommand::LineTo(point2(5, 0)),
+    ///     LineCommand::LineTo(point2(0, 5)),
+    ///     LineCommand::Close,
+    /// ]);
+    /// assert!(triangle.is_closed());
+    ///
+    /// let open = Path::<i32, UnknownUnit>::with_commands(vec![
+    ///     LineCommand::MoveTo(point2(0, 0)),
+    ///     LineCommand::LineTo(point2(5, 0)),
+    /// ]);
+    /// assert!(!open.is_closed());
+    /// ```
+    pub fn is_closed(&self) -> bool {
+        let mut cursor = Cursor::new();
+        let mut drawn = false;
+        let mut drawing = false;
+        for cmd in &self.commands {
+            match cmd {
+                LineCommand::MoveTo(_) | LineCommand::MoveBy(_) => {
+                    if drawing && cursor.position != cursor.subpath_start {
+                        return false;
+                    }
+                    drawing = false;
+                }
+                _ => {
+                    drawn = true;
+                    drawing = true;
+                }
+            }
+            cursor.advance(cmd);
+        }
+        drawn && !(drawing && cursor.position != cursor.subpath_start)
+    }
+
+    /// Get a new path that draws the same shape as this one, but in the
+    /// opposite direction.
+    ///
+    /// Relative commands stay relative, and absolute commands stay absolute,
+    /// except for smooth curves, whose implicit control points depend on the
+    /// commands before them. Those are turned into their explicit, absolute
+    /// counterparts.
+    ///
+    /// ```
+    /// use euclid::{point2, vec2, UnknownUnit};
+    /// use fart::path::{LineCommand, Path};
+    ///
+    /// let path = Path::<i32, UnknownUnit>::with_commands(vec![
+    ///     LineCommand::MoveTo(point2(0, 0)),
+    ///     LineCommand::LineTo(point2(5, 0)),
+    ///     LineCommand::LineBy(vec2(0, 5)),
+    /// ]);
+    ///
+    /// let reversed = path.reversed();
+    /// assert_eq!(reversed.start(), Some(point2(5, 5)));
+    /// assert_eq!(reversed.end(), Some(point2(0, 0)));
+    /// ```
+    pub fn reversed(&self) -> Path<T, U> {
+        struct Subpath<T, U> {
+            start: Point2D<T, U>,
+            end: Point2D<T, U>,
+            reversed: Vec<LineCommand<T, U>>,
+            closed: bool,
+        }
+
+        let mut subpaths: Vec<Subpath<T, U>> = vec![];
+        let mut cursor = Cursor::new();
+
+        for cmd in &self.commands {
+            let from = cursor.position;
+
+            let reversed = match *cmd {
+                LineCommand::MoveTo(_) | LineCommand::MoveBy(_) => {
+                    cursor.advance(cmd);
+                    subpaths.push(Subpath {
+                        start: cursor.position,
+                        end: cursor.position,
+                        reversed: vec![],
+                        closed: false,
+                    });
+                    continue;
+                }
+                LineCommand::Close => {
+                    cursor.advance(cmd);
+                    if let Some(s) = subpaths.last_mut() {
+                        s.closed = true;
+                    }
+                    continue;
+                }
+                LineCommand::LineTo(_) => LineCommand::LineTo(from),
+                LineCommand::LineBy(v) => LineCommand::LineBy(Vector2D::zero() - v),
+                LineCommand::HorizontalLineTo(_) => LineCommand::HorizontalLineTo(from.x),
+                LineCommand::HorizontalLineBy(dx) => LineCommand::HorizontalLineBy(T::zero() - dx),
+                LineCommand::VerticalLineTo(_) => LineCommand::VerticalLineTo(from.y),
+                LineCommand::VerticalLineBy(dy) => LineCommand::VerticalLineBy(T::zero() - dy),
+                LineCommand::CubicBezierTo {
+                    control_1,
+                    control_2,
+                    ..
+                } => LineCommand::CubicBezierTo {
+                    control_1: control_2,
+                    control_2: control_1,
+                    end: from,
+                },
+                LineCommand::CubicBezierBy {
+                    control_1,
+                    control_2,
+                    end,
+                } => LineCommand::CubicBezierBy {
+                    control_1: control_2 - end,
+                    control_2: control_1 - end,
+                    end: Vector2D::zero() - end,
+                },
+                LineCommand::SmoothCubicBezierTo { control, .. } => LineCommand::CubicBezierTo {
+                    control_1: control,
+                    control_2: cursor.smooth_cubic_control(),
+                    end: from,
+                },
+                LineCommand::SmoothCubicBezierBy { control, .. } => LineCommand::CubicBezierTo {
+                    control_1: from + control,
+                    control_2: cursor.smooth_cubic_control(),
+                    end: from,
+                },
+                LineCommand::QuadraticBezierTo { control, .. } => {
+                    LineCommand::QuadraticBezierTo { control, end: from }
+                }
+                LineCommand::QuadraticBezierBy { control, end } => LineCommand::QuadraticBezierBy {
+                    control: control - end,
+                    end: Vector2D::zero() - end,
+                },
+                LineCommand::SmoothQuadtraticCurveTo(_)
+                | LineCommand::SmoothQuadtraticCurveBy(_) => LineCommand::QuadraticBezierTo {
+                    control: cursor.smooth_quadratic_control(),
+                    end: from,
+                },
+                LineCommand::ArcTo {
+                    x_radius,
+                    y_radius,
+                    x_axis_rotation,
+                    large_arc_flag,
+                    sweep_flag,
+                    ..
+                } => LineCommand::ArcTo {
+                    x_radius,
+                    y_radius,
+                    x_axis_rotation,
+                    large_arc_flag,
+                    sweep_flag: !sweep_flag,
+                    end: from,
+                },
+                LineCommand::ArcBy {
+                    x_radius,
+                    y_radius,
+                    x_axis_rotation,
+                    large_arc_flag,
+                    sweep_flag,
+                    end,
+                } => LineCommand::ArcBy {
+                    x_radius,
+                    y_radius,
+                    x_axis_rotation,
+                    large_arc_flag,
+                    sweep_flag: !sweep_flag,
+                    end: Vector2D::zero() - end,
+                },
+            };
+
+            // Drawing after a `Close` (or without any initial move) implicitly
+            // starts a new subpath from the current position.
+            if !matches!(subpaths.last(), Some(s) if !s.closed) {
+                subpaths.push(Subpath {
+                    start: from,
+                    end: from,
+                    reversed: vec![],
+                    closed: false,
+                });
+            }
+
+            cursor.advance(cmd);
+            let subpath = subpaths.last_mut().unwrap();
+            subpath.reversed.push(reversed);
+            subpath.end = cursor.position;
+        }
+
+        let mut commands = vec![];
+        for subpath in subpaths.into_iter().rev() {
+            if subpath.reversed.is_empty() {
+                continue;
+            }
+            if subpath.closed {
+                // The closing line is drawn first, and then we come back
+                // around to the start, where we close the path again.
+                commands.push(LineCommand::MoveTo(subpath.start));
+                if subpath.end != subpath.start {
+                    commands.push(LineCommand::LineTo(subpath.end));
+                }
+                commands.extend(subpath.reversed.into_iter().rev());
+                commands.push(LineCommand::Close);
+            } else {
+                commands.push(LineCommand::MoveTo(subpath.end));
+                commands.extend(subpath.reversed.into_iter().rev());
+            }
+        }
+
+        Path {
+            color: self.color.clone(),
+            commands,
+        }
+    }
 }
 
-impl<'a, T> From<&'a Path<T, CanvasSpace>> for svg::node::element::Path
+impl<T, U> Path<T, U>
 where
-    T: Copy + Debug + Into<svg::node::element::path::Parameters>,
+    T: Scalar,
 {
-    fn from(path: &'a Path<T, CanvasSpace>) -> svg::node::element::Path {
-        let mut data = svg::node::element::path::Data::new();
-        for cmd in &path.commands {
-            data = match cmd {
-                LineCommand::MoveTo(p) => data.move_to((p.x, p.y)),
-                LineCommand::MoveBy(v) => data.move_by((v.x, v.y)),
-                LineCommand::LineTo(p) => data.line_to((p.x, p.y)),
-                LineCommand::LineBy(v) => data.line_by((v.x, v.y)),
-                LineCommand::HorizontalLineTo(x) => data.horizontal_line_to(*x),
-                LineCommand::HorizontalLineBy(dx) => data.horizontal_line_by(*dx),
-                LineCommand::VerticalLineTo(y) => data.vertical_line_to(*y),
-                LineCommand::VerticalLineBy(dy) => data.vertical_line_by(*dy),
-                LineCommand::Close => data.close(),
-                LineCommand::QuadraticBezierTo { control, end } => {
-                    data.quadratic_curve_to((control.x, control.y, end.x, end.y))
+    /// Get the bounding box of everything this path draws.
+    ///
+    /// Relative commands are resolved against the pen's position, and the
+    /// bounds of curves and arcs are computed from their exact extrema rather
+    /// than their control points. Move commands don't draw anything, and do
+    /// not contribute to the bounds. For integer coordinates, the bounding box
+    /// is rounded outwards.
+    ///
+    /// Returns `None` if this path doesn't draw anything.
+    ///
+    /// ```
+    /// use euclid::{point2, vec2, Angle, UnknownUnit};
+    /// use fart::path::{LineCommand, Path};
+    ///
+    /// let path = Path::<i64, UnknownUnit>::with_commands(vec![
+    ///     LineCommand::MoveTo(point2(0, 0)),
+    ///     LineCommand::CubicBezierBy {
+    ///         control_1: vec2(0, 10),
+    ///         control_2: vec2(10, 10),
+    ///         end: vec2(10, 0),
+    ///     },
+    ///     LineCommand::ArcTo {
+    ///         x_radius: 10,
+    ///         y_radius: 10,
+    ///         x_axis_rotation: Angle::radians(0),
+    ///         large_arc_flag: false,
+    ///         sweep_flag: true,
+    ///         end: point2(30, 0),
+    ///     },
+    /// ]);
+    ///
+    /// let aabb = path.aabb().unwrap();
+    ///
+    /// // The curve only reaches `y = 7.5`, even though its control points are
+    /// // at `y = 10`.
+    /// assert_eq!(aabb.max(), point2(30, 8));
+    ///
+    /// // The arc is a half circle, reaching up to `y = -10`.
+    /// assert_eq!(aabb.min(), point2(0, -10));
+    /// ```
+    pub fn aabb(&self) -> Option<Aabb<T, U>> {
+        let aabb = self.segments().map(|s| s.aabb()).fold(
+            None,
+            |acc: Option<Aabb<f64, U>>, b| match acc {
+                None => Some(b),
+                Some(a) => Some(a.join(&b)),
+            },
+        )?;
+
+        let (min, max) = if T::IS_INTEGER {
+            // Allow a little floating point error before rounding outwards, so
+            // that, e.g., the top of a circle of radius 10 doesn't get rounded
+            // out to 11.
+            (
+                point2((aabb.min().x + 1e-6).floor(), (aabb.min().y + 1e-6).floor()),
+                point2((aabb.max().x - 1e-6).ceil(), (aabb.max().y - 1e-6).ceil()),
+            )
+        } else {
+            (aabb.min(), aabb.max())
+        };
+        Some(Aabb::new(
+            point2(T::from_float(min.x), T::from_float(min.y)),
+            point2(T::from_float(max.x), T::from_float(max.y)),
+        ))
+    }
+
+    /// Convert this path into straight lines, approximating every curve and arc
+    /// with a polyline that deviates from it by no more than `tolerance`.
+    ///
+    /// The resulting path consists of only absolute `MoveTo` and `LineTo`
+    /// commands. Relative commands and smooth curves' implicit control points
+    /// are resolved against the pen's position, and `Close` becomes a line back
+    /// to the start of its subpath. Bezier curves are adaptively subdivided
+    /// until they are flat enough, and arcs are split into segments whose
+    /// sagitta is within `tolerance`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tolerance` is not positive.
+    ///
+    /// ```
+    /// use euclid::{point2, Angle, UnknownUnit};
+    /// use fart::path::{LineCommand, Path};
+    ///
+    /// // A circle of radius 10 around the origin, made from two arcs.
+    /// let arc = |end| LineCommand::ArcTo {
+    ///     x_radius: 10.0,
+    ///     y_radius: 10.0,
+    ///     x_axis_rotation: Angle::radians(0.0),
+    ///     large_arc_flag: false,
+    ///     sweep_flag: true,
+    ///     end,
+    /// };
+    /// let circle = Path::<f64, UnknownUnit>::with_commands(vec![
+    ///     LineCommand::MoveTo(point2(10.0, 0.0)),
+    ///     arc(point2(-10.0, 0.0)),
+    ///     arc(point2(10.0, 0.0)),
+    /// ]);
+    ///
+    /// let flat = circle.flatten(0.1);
+    /// assert!(flat.commands.len() > 10);
+    ///
+    /// for cmd in &flat.commands {
+    ///     match cmd {
+    ///         LineCommand::MoveTo(p) | LineCommand::LineTo(p) => {
+    ///             assert!((p.to_vector().length() - 10.0).abs() < 1e-9);
+    ///         }
+    ///         _ => panic!("flattened paths are only moves and lines"),
+    ///     }
+    /// }
+    /// ```
+    pub fn flatten(&self, tolerance: T) -> Path<T, U> {
+        let tolerance = tolerance.to_float();
+        assert!(tolerance > 0.0, "flattening tolerance must be positive");
+
+        let mut cursor = Cursor::new();
+        let mut commands = vec![];
+        let mut points = vec![];
+        for cmd in &self.commands {
+            let pen = cursor.position;
+            let segment = match cursor.resolve(cmd) {
+                Some(segment) => segment,
+                None => {
+                    commands.push(LineCommand::MoveTo(cursor.position));
+                    continue;
                 }
-                LineCommand::QuadraticBezierBy { control, end } => {
-                    data.quadratic_curve_by((control.x, control.y, end.x, end.y))
+            };
+
+            points.clear();
+            segment.flatten(tolerance, &mut points);
+
+            // Rounding to integer coordinates can make neighboring points
+            // land on top of each other, so skip any repeats.
+            let mut last = pen;
+            for p in points.drain(..) {
+                let p = point2(Scalar::from_float(p.x), Scalar::from_float(p.y));
+                if p != last {
+                    commands.push(LineCommand::LineTo(p));
+                    last = p;
                 }
-                LineCommand::SmoothCubicBezierTo { control, end } => {
-                    data.smooth_cubic_curve_to((control.x, control.y, end.x, end.y))
+            }
+            if last != cursor.position {
+                commands.push(LineCommand::LineTo(cursor.position));
+            }
+        }
+
+        Path {
+            color: self.color.clone(),
+            commands,
+        }
+    }
+
+    /// Simplify this path's straight lines by removing points that contribute
+    /// little to its shape, using the given algorithm. How much is "little" is
+    /// determined by `tolerance`: see `fart::geom::SimplifyAlgorithm` for
+    /// details.
+    ///
+    /// Each run of consecutive straight lines is simplified as a polyline,
+    /// and replaced with absolute `LineTo` commands. The points where each run
+    /// starts and ends are kept, as is everything else: moves, curves, and
+    /// arcs are left as they are. A run that ends by closing its subpath is
+    /// simplified together with the line back to the subpath's start, and
+    /// stays closed.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart::geom::SimplifyAlgorithm;
+    /// use fart::path::{LineCommand, Path};
+    ///
+    /// // A noisy square, with a slight wobble every unit along its sides.
+    /// let mut commands = vec![LineCommand::MoveTo(point2(0.0, 0.0))];
+    /// let wobble = |i: i32| if i % 2 == 0 { 0.0 } else { 0.01 };
+    /// for i in 1..=10 {
+    ///     commands.push(LineCommand::LineTo(point2(i as f64, wobble(i))));
+    /// }
+    /// for i in 1..=10 {
+    ///     commands.push(LineCommand::LineTo(point2(10.0 + wobble(i), i as f64)));
+    /// }
+    /// for i in 1..=10 {
+    ///     commands.push(LineCommand::LineTo(point2(10.0 - i as f64, 10.0 + wobble(i))));
+    /// }
+    /// commands.push(LineCommand::Close);
+    /// let square = Path::<f64, UnknownUnit>::with_commands(commands);
+    ///
+    /// let simple = square.simplify(0.1, SimplifyAlgorithm::RamerDouglasPeucker);
+    /// assert_eq!(simple.commands.len(), 5);
+    /// assert!(simple.is_closed());
+    /// assert_eq!(simple.start(), Some(point2(0.0, 0.0)));
+    /// ```
+    pub fn simplify(&self, tolerance: T, algorithm: SimplifyAlgorithm) -> Path<T, U>
+    where
+        LineCommand<T, U>: Clone,
+    {
+        // Simplify the run of straight lines in `polyline`, which starts at
+        // the pen's position before the run.
+        let flush = |polyline: &mut Vec<Point2D<T, U>>, commands: &mut Vec<LineCommand<T, U>>| {
+            let simple = simplify_polyline(polyline, tolerance, algorithm);
+            commands.extend(simple.into_iter().skip(1).map(LineCommand::LineTo));
+            polyline.clear();
+        };
+
+        let mut cursor = Cursor::new();
+        let mut commands = vec![];
+        let mut polyline = vec![];
+        for cmd in &self.commands {
+            let pen = cursor.position;
+            cursor.advance(cmd);
+            match cmd {
+                LineCommand::LineTo(_)
+                | LineCommand::LineBy(_)
+                | LineCommand::HorizontalLineTo(_)
+                | LineCommand::HorizontalLineBy(_)
+                | LineCommand::VerticalLineTo(_)
+                | LineCommand::VerticalLineBy(_) => {
+                    if polyline.is_empty() {
+                        polyline.push(pen);
+                    }
+                    polyline.push(cursor.position);
                 }
-                LineCommand::SmoothCubicBezierBy { control, end } => {
-                    data.smooth_cubic_curve_by((control.x, control.y, end.x, end.y))
+                LineCommand::Close if !polyline.is_empty() => {
+                    // Simplify the line back to the start along with the rest,
+                    // and then let `Close` draw it.
+                    polyline.push(cursor.position);
+                    flush(&mut polyline, &mut commands);
+                    commands.pop();
+                    commands.push(LineCommand::Close);
                 }
-                LineCommand::SmoothQuadtraticCurveTo(p) => {
-                    data.smooth_quadratic_curve_to((p.x, p.y))
+                _ => {
+                    flush(&mut polyline, &mut commands);
+                    commands.push(cmd.clone());
+                }
+            }
+        }
+        flush(&mut polyline, &mut commands);
+
+        Path {
+            color: self.color.clone(),
+            commands,
+        }
+    }
+
+    /// Create a path that smoothly passes through each of the given points,
+    /// following a Catmull-Rom spline made of cubic Bézier curves.
+    ///
+    /// The path starts at the first point, heading towards the second, and
+    /// ends at the last point, heading away from the second to last. See
+    /// `fart::geom::catmull_rom` for details.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart::geom::CatmullRom;
+    /// use fart::path::{LineCommand, Path};
+    ///
+    /// let points = vec![
+    ///     point2::<f64, UnknownUnit>(0.0, 0.0),
+    ///     point2(10.0, 10.0),
+    ///     point2(20.0, 0.0),
+    /// ];
+    ///
+    /// let path = Path::catmull_rom(&points, CatmullRom::Centripetal);
+    /// assert_eq!(path.commands.len(), 3);
+    /// assert!(matches!(path.commands[1], LineCommand::CubicBezierTo { .. }));
+    /// assert_eq!(path.start(), Some(points[0]));
+    /// assert_eq!(path.end(), Some(points[2]));
+    /// assert!(!path.is_closed());
+    /// ```
+    pub fn catmull_rom(points: &[Point2D<T, U>], parameterization: CatmullRom) -> Path<T, U> {
+        Path::from_catmull_rom(points, false, parameterization)
+    }
+
+    /// Create a closed path that smoothly passes through each of the given
+    /// points, and then back to the first, following a Catmull-Rom spline made
+    /// of cubic Bézier curves.
+    ///
+    /// See `fart::geom::catmull_rom` for details.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart::geom::CatmullRom;
+    /// use fart::path::{LineCommand, Path};
+    ///
+    /// let points = vec![
+    ///     point2::<f64, UnknownUnit>(0.0, 0.0),
+    ///     point2(10.0, 0.0),
+    ///     point2(10.0, 10.0),
+    ///     point2(0.0, 10.0),
+    /// ];
+    ///
+    /// let path = Path::closed_catmull_rom(&points, CatmullRom::Uniform);
+    /// assert_eq!(path.commands.len(), 6);
+    /// assert!(path.is_closed());
+    /// assert!(matches!(path.commands.last(), Some(LineCommand::Close)));
+    /// ```
+    pub fn closed_catmull_rom(
+        points: &[Point2D<T, U>],
+        parameterization: CatmullRom,
+    ) -> Path<T, U> {
+        Path::from_catmull_rom(points, true, parameterization)
+    }
+
+    fn from_catmull_rom(
+        points: &[Point2D<T, U>],
+        closed: bool,
+        parameterization: CatmullRom,
+    ) -> Path<T, U> {
+        let mut path = Path::new();
+        let start = match points.first() {
+            None => return path,
+            Some(p) => *p,
+        };
+        path.commands.push(LineCommand::MoveTo(start));
+
+        let to_point =
+            |p: Point2D<f64, U>| point2(Scalar::from_float(p.x), Scalar::from_float(p.y));
+        let points: Vec<Point2D<f64, U>> = points.iter().map(|p| p.cast()).collect();
+        let curves = catmull_rom(&points, closed, parameterization);
+        let n = curves.len();
+        path.commands
+            .extend(curves.into_iter().map(|curve| LineCommand::CubicBezierTo {
+                control_1: to_point(curve.control_1),
+                control_2: to_point(curve.control_2),
+                end: to_point(curve.end),
+            }));
+        if closed && n > 0 {
+            path.commands.push(LineCommand::Close);
+        }
+        path
+    }
+
+    /// Get the outline of drawing this path with a pen of the given `width`.
+    ///
+    /// Curves and arcs are flattened first, and then each subpath is stroked
+    /// with its corners joined according to the `join` style, and its ends
+    /// capped according to the `cap` style. Subpaths that end where they
+    /// started are joined there instead of capped. See `fart::geom::stroke`
+    /// for details.
+    ///
+    /// The outline is made of closed polygons, which may have holes, and which
+    /// can be hatched, or used for clipping other paths, just like any other
+    /// shape. For integer coordinates, the outline's vertices are rounded, and
+    /// any pieces too small to survive rounding are dropped.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart::geom::{JoinStyle, LineCap};
+    /// use fart::path::{LineCommand, Path};
+    ///
+    /// // A "V" shape.
+    /// let path = Path::<i64, UnknownUnit>::with_commands(vec![
+    ///     LineCommand::MoveTo(point2(0, 100)),
+    ///     LineCommand::LineTo(point2(50, 0)),
+    ///     LineCommand::LineTo(point2(100, 100)),
+    /// ]);
+    ///
+    /// let outline = path.stroke_outline(10, LineCap::Round, JoinStyle::Round);
+    /// assert_eq!(outline.len(), 1);
+    /// assert!(outline[0].holes().is_empty());
+    ///
+    /// // A square, closed back at its start, has a hole in its outline.
+    /// let square = Path::<i64, UnknownUnit>::with_commands(vec![
+    ///     LineCommand::MoveTo(point2(0, 0)),
+    ///     LineCommand::LineTo(point2(100, 0)),
+    ///     LineCommand::LineTo(point2(100, 100)),
+    ///     LineCommand::LineTo(point2(0, 100)),
+    ///     LineCommand::Close,
+    /// ]);
+    ///
+    /// let outline = square.stroke_outline(10, LineCap::Butt, JoinStyle::Miter { limit: 2.0 });
+    /// assert_eq!(outline.len(), 1);
+    /// assert_eq!(outline[0].exterior().area(), 110 * 110);
+    /// assert_eq!(outline[0].holes()[0].area(), 90 * 90);
+    /// ```
+    pub fn stroke_outline(
+        &self,
+        width: T,
+        cap: LineCap,
+        join: JoinStyle,
+    ) -> Vec<PolygonWithHoles<T, U>> {
+        let width = width.to_float();
+        if width <= 0.0 {
+            return vec![];
+        }
+
+        // Flatten each subpath into a polyline. Closing a subpath draws a line
+        // back to its start, which makes the polyline closed, too.
+        let tolerance = width / 100.0;
+        let mut polylines = vec![];
+        let mut polyline: Vec<Point2D<f64, U>> = vec![];
+        let mut cursor = Cursor::new();
+        for cmd in &self.commands {
+            let pen = cursor.position.cast::<f64>();
+            match cursor.resolve(cmd) {
+                None => {
+                    if !polyline.is_empty() {
+                        polylines.push(mem::take(&mut polyline));
+                    }
                 }
-                LineCommand::SmoothQuadtraticCurveBy(v) => {
-                    data.smooth_quadratic_curve_by((v.x, v.y))
+                Some(segment) => {
+                    if polyline.is_empty() {
+                        polyline.push(pen);
+                    }
+                    segment.flatten(tolerance, &mut polyline);
                 }
-                cmd => unimplemented!("Have not implemented support for command yet: {:?}", cmd),
-            };
+            }
+        }
+        if !polyline.is_empty() {
+            polylines.push(polyline);
+        }
+
+        stroke(&polylines, width, cap, join)
+            .iter()
+            .filter_map(round_polygon_with_holes)
+            .collect()
+    }
+
+    /// Resolve this path's drawing commands into segments with absolute
+    /// coordinates.
+    pub(crate) fn segments<'a>(&'a self) -> impl Iterator<Item = Segment<U>> + 'a {
+        let mut cursor = Cursor::new();
+        self.commands
+            .iter()
+            .filter_map(move |cmd| cursor.resolve(cmd))
+    }
+}
+
+/// Tracks the pen's position while walking over a path's commands, so that
+/// relative commands and smooth curves' implicit control points can be
+/// resolved into absolute points.
+#[derive(Clone, Debug)]
+pub(crate) struct Cursor<T, U> {
+    pub(crate) position: Point2D<T, U>,
+    pub(crate) subpath_start: Point2D<T, U>,
+    last_control: LastControl<T, U>,
+}
+
+/// The control point of the previous command, if it was a curve, which smooth
+/// curves reflect to get their first control point.
+#[derive(Clone, Copy, Debug)]
+enum LastControl<T, U> {
+    None,
+    Cubic(Point2D<T, U>),
+    Quadratic(Point2D<T, U>),
+}
+
+impl<T, U> Cursor<T, U>
+where
+    T: Scalar,
+{
+    pub(crate) fn new() -> Cursor<T, U> {
+        Cursor {
+            position: Point2D::origin(),
+            subpath_start: Point2D::origin(),
+            last_control: LastControl::None,
+        }
+    }
+
+    /// The implicit first control point of a smooth cubic bezier drawn from the
+    /// current position.
+    fn smooth_cubic_control(&self) -> Point2D<T, U> {
+        match self.last_control {
+            LastControl::Cubic(c) => self.position + (self.position - c),
+            _ => self.position,
+        }
+    }
+
+    /// The implicit control point of a smooth quadratic bezier drawn from the
+    /// current position.
+    fn smooth_quadratic_control(&self) -> Point2D<T, U> {
+        match self.last_control {
+            LastControl::Quadratic(c) => self.position + (self.position - c),
+            _ => self.position,
+        }
+    }
+
+    /// Move the cursor past the given command.
+    pub(crate) fn advance(&mut self, cmd: &LineCommand<T, U>) {
+        let p = self.position;
+        let (position, last_control) = match *cmd {
+            LineCommand::MoveTo(to) => {
+                self.subpath_start = to;
+                (to, LastControl::None)
+            }
+            LineCommand::MoveBy(v) => {
+                self.subpath_start = p + v;
+                (p + v, LastControl::None)
+            }
+            LineCommand::LineTo(to) => (to, LastControl::None),
+            LineCommand::LineBy(v) => (p + v, LastControl::None),
+            LineCommand::HorizontalLineTo(x) => (point2(x, p.y), LastControl::None),
+            LineCommand::HorizontalLineBy(dx) => (point2(p.x + dx, p.y), LastControl::None),
+            LineCommand::VerticalLineTo(y) => (point2(p.x, y), LastControl::None),
+            LineCommand::VerticalLineBy(dy) => (point2(p.x, p.y + dy), LastControl::None),
+            LineCommand::Close => (self.subpath_start, LastControl::None),
+            LineCommand::CubicBezierTo { control_2, end, .. } => {
+                (end, LastControl::Cubic(control_2))
+            }
+            LineCommand::CubicBezierBy { control_2, end, .. } => {
+                (p + end, LastControl::Cubic(p + control_2))
+            }
+            LineCommand::SmoothCubicBezierTo { control, end } => (end, LastControl::Cubic(control)),
+            LineCommand::SmoothCubicBezierBy { control, end } => {
+                (p + end, LastControl::Cubic(p + control))
+            }
+            LineCommand::QuadraticBezierTo { control, end } => {
+                (end, LastControl::Quadratic(control))
+            }
+            LineCommand::QuadraticBezierBy { control, end } => {
+                (p + end, LastControl::Quadratic(p + control))
+            }
+            LineCommand::SmoothQuadtraticCurveTo(end) => {
+                (end, LastControl::Quadratic(self.smooth_quadratic_control()))
+            }
+            LineCommand::SmoothQuadtraticCurveBy(end) => (
+                p + end,
+                LastControl::Quadratic(self.smooth_quadratic_control()),
+            ),
+            LineCommand::ArcTo { end, .. } => (end, LastControl::None),
+            LineCommand::ArcBy { end, .. } => (p + end, LastControl::None),
+        };
+        self.position = position;
+        self.last_control = last_control;
+    }
+
+    /// Move the cursor past the given command, and return the segment that it
+    /// draws, if any.
+    pub(crate) fn resolve(&mut self, cmd: &LineCommand<T, U>) -> Option<Segment<U>> {
+        let from = self.position;
+        let smooth_cubic_control = self.smooth_cubic_control();
+        let smooth_quadratic_control = self.smooth_quadratic_control();
+        self.advance(cmd);
+        let to = self.position.cast::<f64>();
+
+        let f = |p: Point2D<T, U>| p.cast::<f64>();
+        let f64 = |x: T| x.to_float();
+        Some(match *cmd {
+            LineCommand::MoveTo(_) | LineCommand::MoveBy(_) => return None,
+            LineCommand::LineTo(_)
+            | LineCommand::LineBy(_)
+            | LineCommand::HorizontalLineTo(_)
+            | LineCommand::HorizontalLineBy(_)
+            | LineCommand::VerticalLineTo(_)
+            | LineCommand::VerticalLineBy(_)
+            | LineCommand::Close => Segment::Line(f(from), to),
+            LineCommand::CubicBezierTo {
+                control_1,
+                control_2,
+                ..
+            } => Segment::Cubic(f(from), f(control_1), f(control_2), to),
+            LineCommand::CubicBezierBy {
+                control_1,
+                control_2,
+                ..
+            } => Segment::Cubic(f(from), f(from + control_1), f(from + control_2), to),
+            LineCommand::SmoothCubicBezierTo { control, .. } => {
+                Segment::Cubic(f(from), f(smooth_cubic_control), f(control), to)
+            }
+            LineCommand::SmoothCubicBezierBy { control, .. } => {
+                Segment::Cubic(f(from), f(smooth_cubic_control), f(from + control), to)
+            }
+            LineCommand::QuadraticBezierTo { control, .. } => {
+                Segment::Quadratic(f(from), f(control), to)
+            }
+            LineCommand::QuadraticBezierBy { control, .. } => {
+                Segment::Quadratic(f(from), f(from + control), to)
+            }
+            LineCommand::SmoothQuadtraticCurveTo(_) | LineCommand::SmoothQuadtraticCurveBy(_) => {
+                Segment::Quadratic(f(from), f(smooth_quadratic_control), to)
+            }
+            LineCommand::ArcTo {
+                x_radius,
+                y_radius,
+                x_axis_rotation,
+                large_arc_flag,
+                sweep_flag,
+                ..
+            } => Segment::Arc {
+                from: f(from),
+                to,
+                radii: vec2(f64(x_radius), f64(y_radius)),
+                x_axis_rotation: f64(x_axis_rotation.radians),
+                large_arc_flag,
+                sweep_flag,
+            },
+            LineCommand::ArcBy {
+                x_radius,
+                y_radius,
+                x_axis_rotation,
+                large_arc_flag,
+                sweep_flag,
+                ..
+            } => Segment::Arc {
+                from: f(from),
+                to,
+                radii: vec2(f64(x_radius), f64(y_radius)),
+                x_axis_rotation: f64(x_axis_rotation.radians),
+                large_arc_flag,
+                sweep_flag,
+            },
+        })
+    }
+}
+
+/// A single drawing command, resolved into absolute `f64` coordinates. See
+/// `Path::segments`.
+#[derive(Clone, Copy)]
+pub(crate) enum Segment<U> {
+    /// A straight line between two points.
+    Line(Point2D<f64, U>, Point2D<f64, U>),
+
+    /// A quadratic bezier curve: start point, control point, and end point.
+    Quadratic(Point2D<f64, U>, Point2D<f64, U>, Point2D<f64, U>),
+
+    /// A cubic bezier curve: start point, two control points, and end point.
+    Cubic(
+        Point2D<f64, U>,
+        Point2D<f64, U>,
+        Point2D<f64, U>,
+        Point2D<f64, U>,
+    ),
+
+    /// An elliptical arc, as described by SVG's endpoint parameterization. The
+    /// x axis rotation is in radians.
+    Arc {
+        from: Point2D<f64, U>,
+        to: Point2D<f64, U>,
+        radii: Vector2D<f64, U>,
+        x_axis_rotation: f64,
+        large_arc_flag: bool,
+        sweep_flag: bool,
+    },
+}
+
+impl<U> Segment<U> {
+    /// Get this segment's exact bounding box.
+    pub(crate) fn aabb(&self) -> Aabb<f64, U> {
+        match *self {
+            Segment::Line(a, b) => Aabb::for_vertices(vec![a, b]),
+            Segment::Quadratic(p0, p1, p2) => QuadraticBezier::new(p0, p1, p2).to_aabb(),
+            Segment::Cubic(p0, p1, p2, p3) => CubicBezier::new(p0, p1, p2, p3).to_aabb(),
+            Segment::Arc { from, to, .. } => match self.center_arc() {
+                Some(arc) => arc.to_aabb(),
+                None => Aabb::for_vertices(vec![from, to]),
+            },
+        }
+    }
+
+    /// Approximate this segment with straight lines that deviate from it by no
+    /// more than `tolerance`, pushing the points after its start onto `points`.
+    pub(crate) fn flatten(&self, tolerance: f64, points: &mut Vec<Point2D<f64, U>>) {
+        // Bound the recursion, in case of extreme coordinates or tolerances.
+        const MAX_DEPTH: u32 = 16;
+
+        // How far `p` is from the line through `a` and `b`.
+        fn distance<U>(p: Point2D<f64, U>, a: Point2D<f64, U>, b: Point2D<f64, U>) -> f64 {
+            let ab = b - a;
+            let length = ab.length();
+            if length == 0.0 {
+                (p - a).length()
+            } else {
+                ab.cross(p - a).abs() / length
+            }
+        }
+
+        fn quadratic<U>(
+            p0: Point2D<f64, U>,
+            p1: Point2D<f64, U>,
+            p2: Point2D<f64, U>,
+            tolerance: f64,
+            depth: u32,
+            points: &mut Vec<Point2D<f64, U>>,
+        ) {
+            // A quadratic curve's furthest point from its chord is half as far
+            // as its control point.
+            if depth == MAX_DEPTH || distance(p1, p0, p2) / 2.0 <= tolerance {
+                points.push(p2);
+                return;
+            }
+            let (p01, p12) = (p0.lerp(p1, 0.5), p1.lerp(p2, 0.5));
+            let mid = p01.lerp(p12, 0.5);
+            quadratic(p0, p01, mid, tolerance, depth + 1, points);
+            quadratic(mid, p12, p2, tolerance, depth + 1, points);
+        }
+
+        fn cubic<U>(
+            p0: Point2D<f64, U>,
+            p1: Point2D<f64, U>,
+            p2: Point2D<f64, U>,
+            p3: Point2D<f64, U>,
+            tolerance: f64,
+            depth: u32,
+            points: &mut Vec<Point2D<f64, U>>,
+        ) {
+            // A cubic curve's furthest point from its chord is within three
+            // quarters of its furthest control point's distance.
+            let flatness = distance(p1, p0, p3).max(distance(p2, p0, p3)) * 0.75;
+            if depth == MAX_DEPTH || flatness <= tolerance {
+                points.push(p3);
+                return;
+            }
+            let (p01, p12, p23) = (p0.lerp(p1, 0.5), p1.lerp(p2, 0.5), p2.lerp(p3, 0.5));
+            let (p012, p123) = (p01.lerp(p12, 0.5), p12.lerp(p23, 0.5));
+            let mid = p012.lerp(p123, 0.5);
+            cubic(p0, p01, p012, mid, tolerance, depth + 1, points);
+            cubic(mid, p123, p23, p3, tolerance, depth + 1, points);
+        }
+
+        match *self {
+            Segment::Line(_, b) => points.push(b),
+            Segment::Quadratic(p0, p1, p2) => quadratic(p0, p1, p2, tolerance, 0, points),
+            Segment::Cubic(p0, p1, p2, p3) => cubic(p0, p1, p2, p3, tolerance, 0, points),
+            Segment::Arc { from, to, .. } => {
+                let arc = match self.center_arc() {
+                    Some(arc) => arc,
+                    None => {
+                        if from != to {
+                            points.push(to);
+                        }
+                        return;
+                    }
+                };
+                // A chord spanning `step` radians of a circle of radius `r`
+                // deviates from it by `r * (1 - cos(step / 2))`.
+                let r = arc.radii.x.max(arc.radii.y);
+                let step = if tolerance < r {
+                    2.0 * (1.0 - tolerance / r).acos()
+                } else {
+                    PI
+                };
+                let n = (arc.sweep_angle.radians.abs() / step).ceil().max(1.0) as usize;
+                for i in 1..n {
+                    points.push(arc.point_at((i as f64) / (n as f64)));
+                }
+                points.push(to);
+            }
         }
-        svg::node::element::Path::new()
-            .set("stroke", path.color.as_str())
-            .set("fill", "none")
-            .set("d", data)
+    }
+
+    /// Convert an arc segment into center parameterization.
+    ///
+    /// Returns `None` if this is not an arc, or if the arc is degenerate. See
+    /// `Arc::from_endpoints`.
+    pub(crate) fn center_arc(&self) -> Option<Arc<f64, U>> {
+        match *self {
+            Segment::Arc {
+                from,
+                to,
+                radii,
+                x_axis_rotation,
+                large_arc_flag,
+                sweep_flag,
+            } => Arc::from_endpoints(
+                from,
+                to,
+                radii,
+                Angle::radians(x_axis_rotation),
+                large_arc_flag,
+                sweep_flag,
+            ),
+            _ => None,
+        }
+    }
+}
+
+/// Convert a polygon with holes into `T`s with `Scalar::from_float`, or `None` if
+/// rounding collapses its exterior. Holes that collapse are dropped.
+fn round_polygon_with_holes<T, U>(
+    polygon: &PolygonWithHoles<f64, U>,
+) -> Option<PolygonWithHoles<T, U>>
+where
+    T: Scalar,
+{
+    let round = |ring: &Polygon<f64, U>| -> Option<Polygon<T, U>> {
+        let mut vertices: Vec<Point2D<T, U>> = ring
+            .vertices()
+            .iter()
+            .map(|v| point2(Scalar::from_float(v.x), Scalar::from_float(v.y)))
+            .collect();
+        vertices.dedup();
+        if vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+        if vertices.len() < 3 || !is_counter_clockwise(&vertices) {
+            return None;
+        }
+        let polygon = Polygon::new(vertices);
+        if polygon.area() == T::zero() {
+            None
+        } else {
+            Some(polygon)
+        }
+    };
+
+    let exterior = round(polygon.exterior())?;
+    let holes = polygon.holes().iter().filter_map(round).collect();
+    Some(PolygonWithHoles::new(exterior, holes))
+}
+
+impl<T, U> Path<T, U>
+where
+    T: Scalar,
+{
+    /// Render this path's commands as SVG path data, for a `<path>` element's
+    /// `d` attribute, with every number rounded to at most `precision` decimal
+    /// places.
+    ///
+    /// Coordinates are written from `f64`s, so floating point paths keep their
+    /// precision all the way to the SVG, and trailing zeros are dropped.
+    ///
+    /// ```
+    /// use euclid::{point2, vec2, Angle, UnknownUnit};
+    /// use fart::path::{LineCommand, Path};
+    ///
+    /// let path = Path::<f64, UnknownUnit>::with_commands(vec![
+    ///     LineCommand::MoveTo(point2(0.126, 1.0 / 3.0)),
+    ///     LineCommand::LineTo(point2(10.0, -0.0001)),
+    ///     LineCommand::Close,
+    /// ]);
+    /// assert_eq!(path.to_svg_data(2), "M0.13,0.33 L10,0 z");
+    /// assert_eq!(path.to_svg_data(4), "M0.126,0.3333 L10,-0.0001 z");
+    ///
+    /// // Arcs' rotations are written in degrees.
+    /// let arc = Path::<f64, UnknownUnit>::with_commands(vec![
+    ///     LineCommand::MoveTo(point2(0.0, 0.0)),
+    ///     LineCommand::ArcBy {
+    ///         x_radius: 5.0,
+    ///         y_radius: 10.0,
+    ///         x_axis_rotation: Angle::degrees(90.0),
+    ///         large_arc_flag: false,
+    ///         sweep_flag: true,
+    ///         end: vec2(10.0, 0.0),
+    ///     },
+    /// ]);
+    /// assert_eq!(arc.to_svg_data(2), "M0,0 a5,10,90,0,1,10,0");
+    /// ```
+    pub fn to_svg_data(&self, precision: usize) -> String {
+        svg_data(&self.commands, Some(precision))
+    }
+}
+
+/// Format a number for SVG, rounded to `precision` decimal places if given,
+/// and otherwise in full.
+pub(crate) fn svg_number<T: Scalar>(x: T, precision: Option<usize>) -> String {
+    let x = x.to_float();
+    let s = match precision {
+        None => x.to_string(),
+        Some(precision) => {
+            let s = format!("{:.*}", precision, x);
+            if s.contains('.') {
+                s.trim_end_matches('0').trim_end_matches('.').to_string()
+            } else {
+                s
+            }
+        }
+    };
+    // Rounding can leave a negative zero behind, like `-0.0001` to `-0`.
+    if s == "-0" {
+        "0".to_string()
+    } else {
+        s
+    }
+}
+
+fn svg_data<T, U>(commands: &[LineCommand<T, U>], precision: Option<usize>) -> String
+where
+    T: Scalar,
+{
+    let command = |letter: &str, numbers: &[T]| {
+        let numbers: Vec<_> = numbers.iter().map(|&x| svg_number(x, precision)).collect();
+        format!("{}{}", letter, numbers.join(","))
+    };
+    let flag = |f: bool| if f { T::one() } else { T::zero() };
+
+    commands
+        .iter()
+        .map(|cmd| match *cmd {
+            LineCommand::MoveTo(p) => command("M", &[p.x, p.y]),
+            LineCommand::MoveBy(v) => command("m", &[v.x, v.y]),
+            LineCommand::LineTo(p) => command("L", &[p.x, p.y]),
+            LineCommand::LineBy(v) => command("l", &[v.x, v.y]),
+            LineCommand::HorizontalLineTo(x) => command("H", &[x]),
+            LineCommand::HorizontalLineBy(dx) => command("h", &[dx]),
+            LineCommand::VerticalLineTo(y) => command("V", &[y]),
+            LineCommand::VerticalLineBy(dy) => command("v", &[dy]),
+            LineCommand::Close => "z".to_string(),
+            LineCommand::QuadraticBezierTo { control, end } => {
+                command("Q", &[control.x, control.y, end.x, end.y])
+            }
+            LineCommand::QuadraticBezierBy { control, end } => {
+                command("q", &[control.x, control.y, end.x, end.y])
+            }
+            LineCommand::SmoothCubicBezierTo { control, end } => {
+                command("S", &[control.x, control.y, end.x, end.y])
+            }
+            LineCommand::SmoothCubicBezierBy { control, end } => {
+                command("s", &[control.x, control.y, end.x, end.y])
+            }
+            LineCommand::SmoothQuadtraticCurveTo(p) => command("T", &[p.x, p.y]),
+            LineCommand::SmoothQuadtraticCurveBy(v) => command("t", &[v.x, v.y]),
+            LineCommand::CubicBezierTo {
+                control_1,
+                control_2,
+                end,
+            } => command(
+                "C",
+                &[
+                    control_1.x,
+                    control_1.y,
+                    control_2.x,
+                    control_2.y,
+                    end.x,
+                    end.y,
+                ],
+            ),
+            LineCommand::CubicBezierBy {
+                control_1,
+                control_2,
+                end,
+            } => command(
+                "c",
+                &[
+                    control_1.x,
+                    control_1.y,
+                    control_2.x,
+                    control_2.y,
+                    end.x,
+                    end.y,
+                ],
+            ),
+            LineCommand::ArcTo {
+                x_radius,
+                y_radius,
+                x_axis_rotation,
+                large_arc_flag,
+                sweep_flag,
+                end,
+            } => {
+                // The rotation is in radians, but SVG wants degrees, which may
+                // not be whole even when the radians are.
+                let rotation =
+                    svg_number(x_axis_rotation.radians.to_float().to_degrees(), precision);
+                format!(
+                    "A{},{},{},{},{},{},{}",
+                    svg_number(x_radius, precision),
+                    svg_number(y_radius, precision),
+                    rotation,
+                    svg_number(flag(large_arc_flag), None),
+                    svg_number(flag(sweep_flag), None),
+                    svg_number(end.x, precision),
+                    svg_number(end.y, precision),
+                )
+            }
+            LineCommand::ArcBy {
+                x_radius,
+                y_radius,
+                x_axis_rotation,
+                large_arc_flag,
+                sweep_flag,
+                end,
+            } => {
+                let rotation =
+                    svg_number(x_axis_rotation.radians.to_float().to_degrees(), precision);
+                format!(
+                    "a{},{},{},{},{},{},{}",
+                    svg_number(x_radius, precision),
+                    svg_number(y_radius, precision),
+                    rotation,
+                    svg_number(flag(large_arc_flag), None),
+                    svg_number(flag(sweep_flag), None),
+                    svg_number(end.x, precision),
+                    svg_number(end.y, precision),
+                )
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Build an SVG `<path>` element for the given path, rounding its data to
+/// `precision` decimal places if given.
+pub(crate) fn svg_path<T, U>(
+    path: &Path<T, U>,
+    precision: Option<usize>,
+) -> svg::node::element::Path
+where
+    T: Scalar,
+{
+    svg::node::element::Path::new()
+        .set("stroke", path.color.as_str())
+        .set("fill", "none")
+        .set("d", svg_data(&path.commands, precision))
+}
+
+impl<'a, T> From<&'a Path<T, CanvasSpace>> for svg::node::element::Path
+where
+    T: Scalar,
+{
+    fn from(path: &'a Path<T, CanvasSpace>) -> svg::node::element::Path {
+        svg_path(path, None)
     }
 }
 
@@ -489,7 +1751,9 @@ where
 ///
 /// This is implemented for `fart::geom::Polygon` and
 /// `fart::geom::ConvexPolygon`, and draws the outline of the polygon. It is
-/// also implemented for `fart::geom::Line`, and draws just the line.
+/// also implemented for `fart::geom::Line`, and draws just the line, and for
+/// the curves in `fart::geom`, which are drawn with SVG's arc and Bézier curve
+/// commands.
 pub trait ToPaths<T, U> {
     /// An iterator over this thing's paths.
     type Paths: Iterator<Item = Path<T, U>>;
@@ -511,7 +1775,7 @@ where
 
 impl<T, U> ToPaths<T, U> for fart_2d_geom::Polygon<T, U>
 where
-    T: Copy + NumAssign + PartialOrd + Signed + Debug,
+    T: Scalar,
 {
     type Paths = iter::Once<Path<T, U>>;
 
@@ -535,7 +1799,7 @@ where
 
 impl<T, U> ToPaths<T, U> for fart_2d_geom::ConvexPolygon<T, U>
 where
-    T: Copy + NumAssign + PartialOrd + Signed + Debug,
+    T: Scalar,
 {
     type Paths = <fart_2d_geom::Polygon<T, U> as ToPaths<T, U>>::Paths;
 
@@ -545,6 +1809,62 @@ where
     }
 }
 
+impl<T, U> ToPaths<T, U> for PolygonWithHoles<T, U>
+where
+    T: Scalar,
+{
+    type Paths = iter::Once<Path<T, U>>;
+
+    /// Render this polygon as a single path with a sub-path for its exterior
+    /// and one for each of its holes.
+    ///
+    /// The holes are wound in the opposite direction from the exterior, so
+    /// that they are left unfilled under both the non-zero and even-odd fill
+    /// rules.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart::geom::{Polygon, PolygonWithHoles};
+    /// use fart::path::{LineCommand, ToPaths};
+    ///
+    /// let square = |min, max| -> Polygon<i32, UnknownUnit> {
+    ///     Polygon::new(vec![
+    ///         point2(min, min),
+    ///         point2(max, min),
+    ///         point2(max, max),
+    ///         point2(min, max),
+    ///     ])
+    /// };
+    ///
+    /// let frame = PolygonWithHoles::new(square(0, 10), vec![square(2, 8)]);
+    /// let path = frame.to_paths().next().unwrap();
+    ///
+    /// let move_tos = path
+    ///     .commands
+    ///     .iter()
+    ///     .filter(|c| matches!(c, LineCommand::MoveTo(_)))
+    ///     .count();
+    /// assert_eq!(move_tos, 2);
+    /// ```
+    fn to_paths(&self) -> Self::Paths {
+        let rings = iter::once(self.exterior().vertices().to_vec()).chain(
+            self.holes()
+                .iter()
+                .map(|h| h.vertices().iter().rev().cloned().collect()),
+        );
+
+        let mut commands = vec![];
+        for ring in rings {
+            let mut vertices = ring.into_iter();
+            commands.extend(vertices.next().map(LineCommand::MoveTo));
+            commands.extend(vertices.map(LineCommand::LineTo));
+            commands.push(LineCommand::Close);
+        }
+
+        iter::once(Path::with_commands(commands))
+    }
+}
+
 impl<T, U> ToPaths<T, U> for fart_2d_geom::Line<T, U>
 where
     T: Clone,
@@ -561,7 +1881,7 @@ where
 
 impl<T, U> ToPaths<T, U> for fart_aabb::Aabb<T, U>
 where
-    T: Copy + Num + PartialOrd,
+    T: Scalar,
 {
     type Paths = iter::Once<Path<T, U>>;
 
@@ -576,6 +1896,129 @@ where
     }
 }
 
+impl<T, U> ToPaths<T, U> for Circle<T, U>
+where
+    T: Scalar,
+{
+    type Paths = iter::Once<Path<T, U>>;
+
+    fn to_paths(&self) -> Self::Paths {
+        // Two half circles, since a single arc can't end where it starts.
+        let (center, r) = (self.center, self.radius);
+        let arc = |end| LineCommand::ArcTo {
+            x_radius: r,
+            y_radius: r,
+            x_axis_rotation: Angle::radians(T::zero()),
+            large_arc_flag: false,
+            sweep_flag: true,
+            end,
+        };
+        iter::once(Path::with_commands(vec![
+            LineCommand::MoveTo(point2(center.x + r, center.y)),
+            arc(point2(center.x - r, center.y)),
+            arc(point2(center.x + r, center.y)),
+            LineCommand::Close,
+        ]))
+    }
+}
+
+impl<T, U> ToPaths<T, U> for Ellipse<T, U>
+where
+    T: Scalar,
+{
+    type Paths = iter::Once<Path<T, U>>;
+
+    fn to_paths(&self) -> Self::Paths {
+        // Two halves, split at the ends of the ellipse's x axis.
+        let ellipse = self.cast::<f64>();
+        let point = |theta| {
+            let p = ellipse.point_at(Angle::radians(theta));
+            point2(Scalar::from_float(p.x), Scalar::from_float(p.y))
+        };
+        let start = point(0.0);
+        let arc = |end| LineCommand::ArcTo {
+            x_radius: self.radii.x,
+            y_radius: self.radii.y,
+            x_axis_rotation: self.rotation,
+            large_arc_flag: false,
+            sweep_flag: true,
+            end,
+        };
+        iter::once(Path::with_commands(vec![
+            LineCommand::MoveTo(start),
+            arc(point(PI)),
+            arc(start),
+            LineCommand::Close,
+        ]))
+    }
+}
+
+impl<T, U> ToPaths<T, U> for Arc<T, U>
+where
+    T: Scalar,
+{
+    type Paths = iter::Once<Path<T, U>>;
+
+    fn to_paths(&self) -> Self::Paths {
+        // Split the arc into pieces of no more than half a turn, so that none
+        // of them end where they start.
+        let arc = self.cast::<f64>();
+        let point = |t| {
+            let p = arc.point_at(t);
+            point2(Scalar::from_float(p.x), Scalar::from_float(p.y))
+        };
+        let n = (arc.sweep_angle.radians.abs() / PI).ceil().max(1.0) as usize;
+        let mut commands = Vec::with_capacity(n + 1);
+        commands.push(LineCommand::MoveTo(point(0.0)));
+        for i in 1..=n {
+            commands.push(LineCommand::ArcTo {
+                x_radius: self.radii.x,
+                y_radius: self.radii.y,
+                x_axis_rotation: self.rotation,
+                large_arc_flag: false,
+                sweep_flag: arc.sweep_angle.radians > 0.0,
+                end: point(i as f64 / n as f64),
+            });
+        }
+        iter::once(Path::with_commands(commands))
+    }
+}
+
+impl<T, U> ToPaths<T, U> for QuadraticBezier<T, U>
+where
+    T: Copy,
+{
+    type Paths = iter::Once<Path<T, U>>;
+
+    fn to_paths(&self) -> Self::Paths {
+        iter::once(Path::with_commands(vec![
+            LineCommand::MoveTo(self.start),
+            LineCommand::QuadraticBezierTo {
+                control: self.control,
+                end: self.end,
+            },
+        ]))
+    }
+}
+
+impl<T, U> ToPaths<T, U> for CubicBezier<T, U>
+where
+    T: Copy,
+{
+    type Paths = iter::Once<Path<T, U>>;
+
+    fn to_paths(&self) -> Self::Paths {
+        iter::once(Path::with_commands(vec![
+            LineCommand::MoveTo(self.start),
+            LineCommand::CubicBezierTo {
+                control_1: self.control_1,
+                control_2: self.control_2,
+                end: self.end,
+            },
+        ]))
+    }
+}
+
 /// A `ToPaths` wrapper type that forces all of the paths produced by the inner
 /// type to be of a certain color. Created using `ToPathsExt::color`.
 #[derive(Debug, Clone)]
@@ -636,6 +2079,42 @@ pub trait ToPathsExt<T, U>: ToPaths<T, U> {
             color: color.into(),
         }
     }
+
+    /// Fill the shapes drawn by this thing with hatching, in addition to
+    /// drawing their outlines.
+    ///
+    /// Each subpath is treated as if it were closed, and the areas covered by
+    /// an odd number of the subpaths of a color are hatched with that color.
+    /// See `fart::hatch::hatch` for how the hatch lines are laid out.
+    ///
+    /// ```
+    /// use euclid::{point2, Angle, UnknownUnit};
+    /// use fart::geom::Polygon;
+    /// use fart::hatch::HatchStyle;
+    /// use fart::path::{ToPaths, ToPathsExt};
+    ///
+    /// let triangle = Polygon::<i64, UnknownUnit>::new(vec![
+    ///     point2(0, 0),
+    ///     point2(100, 0),
+    ///     point2(50, 100),
+    /// ]);
+    ///
+    /// let paths: Vec<_> = triangle
+    ///     .hatch(Angle::degrees(45.0), 10, HatchStyle::Crosshatch)
+    ///     .color("blue")
+    ///     .to_paths()
+    ///     .collect();
+    ///
+    /// // The triangle's outline, and then its hatching.
+    /// assert!(paths.len() > 10);
+    /// assert!(paths.iter().all(|p| p.color == "blue"));
+    /// ```
+    fn hatch(self, angle: Angle<f64>, spacing: T, style: HatchStyle) -> Hatch<Self, T>
+    where
+        Self: Sized,
+    {
+        Hatch::new(self, angle, spacing, style)
+    }
 }
 
 impl<S, T, U> ToPathsExt<T, U> for S where S: ToPaths<T, U> {}
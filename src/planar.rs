@@ -0,0 +1,615 @@
+//! Planar subdivisions of the plane into vertices, edges, and faces.
+//!
+//! Draw a bunch of lines, and they carve the plane up into faces, which can
+//! then be colored, hatched, or subdivided further. A `PlanarGraph` finds
+//! those faces, and how they are connected, as a doubly connected edge list:
+//! every edge is split into two half-edges pointing in opposite directions,
+//! and each half-edge knows the face on its left, and the next half-edge
+//! around that face.
+//!
+//! ```
+//! use euclid::{point2, UnknownUnit};
+//! use fart::geom::{line, Line};
+//! use fart::planar::PlanarGraph;
+//!
+//! // A square, cut in two by a line across its middle.
+//! let lines: Vec<Line<i32, UnknownUnit>> = vec![
+//!     line(point2(0, 0), point2(10, 0)),
+//!     line(point2(10, 0), point2(10, 10)),
+//!     line(point2(10, 10), point2(0, 10)),
+//!     line(point2(0, 10), point2(0, 0)),
+//!     line(point2(-5, 5), point2(15, 5)),
+//! ];
+//! let graph = PlanarGraph::from_lines(&lines);
+//!
+//! // The line across the middle is split where it crosses the square.
+//! assert_eq!(graph.vertices().count(), 8);
+//! assert_eq!(graph.half_edges().count(), 2 * 9);
+//!
+//! // The two halves of the square, and the unbounded face around them.
+//! assert_eq!(graph.faces().count(), 3);
+//! let halves: Vec<_> = graph.bounded_faces().collect();
+//! assert_eq!(halves.len(), 2);
+//! for &half in &halves {
+//!     assert_eq!(graph.polygon(half).unwrap().area(), 50.0);
+//!
+//!     // Each half is next to the other, and to the unbounded face.
+//!     let mut adjacent = graph.adjacent_faces(half);
+//!     adjacent.sort();
+//!     let mut expected: Vec<_> = halves.iter().cloned().filter(|&f| f != half).collect();
+//!     expected.push(graph.unbounded_face());
+//!     expected.sort();
+//!     assert_eq!(adjacent, expected);
+//! }
+//! ```
+
+use crate::aabb::Aabb;
+use crate::geom::{
+    is_counter_clockwise, orientation, split_segments, Line, Polygon, PolygonWithHoles,
+    RelativeDirection,
+};
+use crate::path::{Cursor, Path};
+use euclid::Point2D;
+use fart_utils::{NoMorePartial, Scalar};
+use id_arena::{Arena, Id};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::iter;
+
+/// The id of a vertex in a `PlanarGraph`.
+pub type VertexId<U> = Id<Vertex<U>>;
+
+/// The id of a half-edge in a `PlanarGraph`.
+pub type HalfEdgeId<U> = Id<HalfEdge<U>>;
+
+/// The id of a face in a `PlanarGraph`.
+pub type FaceId<U> = Id<Face<U>>;
+
+/// A subdivision of the plane by line segments, as a doubly connected edge
+/// list.
+///
+/// See the module documentation for details.
+#[derive(Debug)]
+pub struct PlanarGraph<U> {
+    vertices: Arena<Vertex<U>>,
+    half_edges: Arena<HalfEdge<U>>,
+    faces: Arena<Face<U>>,
+    unbounded_face: FaceId<U>,
+}
+
+/// A point where edges of a `PlanarGraph` meet.
+#[derive(Debug)]
+pub struct Vertex<U> {
+    point: Point2D<f64, U>,
+    edge: HalfEdgeId<U>,
+}
+
+impl<U> Vertex<U> {
+    /// Get this vertex's location.
+    pub fn point(&self) -> Point2D<f64, U> {
+        self.point
+    }
+
+    /// Get one of the half-edges that starts at this vertex. See
+    /// `PlanarGraph::outgoing` for all of them.
+    pub fn edge(&self) -> HalfEdgeId<U> {
+        self.edge
+    }
+}
+
+/// One direction of an edge in a `PlanarGraph`, with its face on its left.
+#[derive(Debug)]
+pub struct HalfEdge<U> {
+    origin: VertexId<U>,
+    twin: HalfEdgeId<U>,
+    next: HalfEdgeId<U>,
+    prev: HalfEdgeId<U>,
+    face: FaceId<U>,
+}
+
+impl<U> HalfEdge<U> {
+    /// Get the vertex that this half-edge starts at.
+    pub fn origin(&self) -> VertexId<U> {
+        self.origin
+    }
+
+    /// Get the half-edge going the other way along the same edge. It ends at
+    /// this half-edge's origin.
+    pub fn twin(&self) -> HalfEdgeId<U> {
+        self.twin
+    }
+
+    /// Get the half-edge after this one around its face.
+    pub fn next(&self) -> HalfEdgeId<U> {
+        self.next
+    }
+
+    /// Get the half-edge before this one around its face.
+    pub fn prev(&self) -> HalfEdgeId<U> {
+        self.prev
+    }
+
+    /// Get the face on this half-edge's left.
+    pub fn face(&self) -> FaceId<U> {
+        self.face
+    }
+}
+
+/// A region of the plane enclosed by the edges of a `PlanarGraph`.
+#[derive(Debug)]
+pub struct Face<U> {
+    outer: Option<HalfEdgeId<U>>,
+    inner: Vec<HalfEdgeId<U>>,
+}
+
+impl<U> Face<U> {
+    /// Get a half-edge on this face's outer boundary, which runs
+    /// counter-clockwise around it. The unbounded face doesn't have one.
+    pub fn outer_boundary(&self) -> Option<HalfEdgeId<U>> {
+        self.outer
+    }
+
+    /// Get a half-edge on each of this face's inner boundaries, which run
+    /// clockwise around the parts of the graph that are inside of it, but not
+    /// connected to its outer boundary.
+    pub fn inner_boundaries(&self) -> &[HalfEdgeId<U>] {
+        &self.inner
+    }
+}
+
+impl<U> PlanarGraph<U> {
+    /// Construct the planar graph for the given line segments.
+    ///
+    /// The line segments are split wherever they meet (see
+    /// `fart::geom::split_segments`), and converted to `f64`s so that the
+    /// points where they cross aren't rounded off.
+    pub fn from_lines<T>(lines: &[Line<T, U>]) -> PlanarGraph<U>
+    where
+        T: Scalar,
+    {
+        let lines: Vec<Line<f64, U>> = lines.iter().map(|l| l.cast()).collect();
+        PlanarGraph::new(&lines)
+    }
+
+    /// Construct the planar graph for the lines drawn by the given paths.
+    ///
+    /// Curves and arcs are flattened into straight lines first, to within a
+    /// thousandth of the paths' size. Paths' colors are ignored.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart::path::{LineCommand, Path};
+    /// use fart::planar::PlanarGraph;
+    ///
+    /// // Two overlapping triangles, which make a star of David.
+    /// let triangle = |y, dy| -> Path<f64, UnknownUnit> {
+    ///     Path::with_commands(vec![
+    ///         LineCommand::MoveTo(point2(0.0, y)),
+    ///         LineCommand::LineBy(euclid::vec2(4.0, dy)),
+    ///         LineCommand::LineBy(euclid::vec2(-8.0, 0.0)),
+    ///         LineCommand::Close,
+    ///     ])
+    /// };
+    /// let graph = PlanarGraph::from_paths(&[triangle(-4.0, 6.0), triangle(4.0, -6.0)]);
+    ///
+    /// // The hexagon in the middle, and the six points around it.
+    /// assert_eq!(graph.bounded_faces().count(), 7);
+    /// let hexagon = graph
+    ///     .bounded_faces()
+    ///     .find(|&f| graph.polygon(f).unwrap().len() == 6)
+    ///     .unwrap();
+    /// assert_eq!(graph.adjacent_faces(hexagon).len(), 6);
+    /// ```
+    pub fn from_paths<T>(paths: &[Path<T, U>]) -> PlanarGraph<U>
+    where
+        T: Scalar,
+    {
+        let aabb =
+            paths
+                .iter()
+                .filter_map(|p| p.aabb())
+                .fold(None, |joined: Option<Aabb<T, U>>, aabb| match joined {
+                    Some(joined) => Some(joined.join(&aabb)),
+                    None => Some(aabb),
+                });
+        let size = aabb.map_or(0.0, |aabb| {
+            aabb.width().to_float().max(aabb.height().to_float())
+        });
+        let tolerance = if size > 0.0 { size / 1000.0 } else { 1.0 };
+
+        let mut lines = vec![];
+        let mut points: Vec<Point2D<f64, U>> = vec![];
+        for path in paths {
+            let mut cursor = Cursor::new();
+            for cmd in &path.commands {
+                let pen = cursor.position.cast::<f64>();
+                if let Some(segment) = cursor.resolve(cmd) {
+                    points.clear();
+                    points.push(pen);
+                    segment.flatten(tolerance, &mut points);
+                    lines.extend(points.windows(2).map(|w| Line::new(w[0], w[1])));
+                }
+            }
+        }
+        PlanarGraph::new(&lines)
+    }
+
+    fn new(lines: &[Line<f64, U>]) -> PlanarGraph<U> {
+        let mut vertices: Arena<Vertex<U>> = Arena::new();
+        let mut half_edges: Arena<HalfEdge<U>> = Arena::new();
+        let mut faces: Arena<Face<U>> = Arena::new();
+        let unbounded_face = faces.alloc(Face {
+            outer: None,
+            inner: vec![],
+        });
+
+        // Once the line segments are split, they only meet at their endpoints,
+        // and those are exactly the same where they meet. Every vertex's edge
+        // is a placeholder until its outgoing half-edges are sorted below.
+        let mut ids: BTreeMap<NoMorePartial<(f64, f64)>, VertexId<U>> = BTreeMap::new();
+        let mut outgoing: Vec<Vec<HalfEdgeId<U>>> = vec![];
+        for l in split_segments(lines) {
+            let mut vertex = |p: Point2D<f64, U>| {
+                *ids.entry(NoMorePartial((p.x, p.y))).or_insert_with(|| {
+                    outgoing.push(vec![]);
+                    vertices.alloc(Vertex {
+                        point: p,
+                        edge: half_edges.next_id(),
+                    })
+                })
+            };
+            let (a, b) = (vertex(l.a), vertex(l.b));
+
+            let mut half_edge = |origin| {
+                half_edges.alloc_with_id(|id| HalfEdge {
+                    origin,
+                    twin: id,
+                    next: id,
+                    prev: id,
+                    face: unbounded_face,
+                })
+            };
+            let (ab, ba) = (half_edge(a), half_edge(b));
+            half_edges[ab].twin = ba;
+            half_edges[ba].twin = ab;
+            outgoing[a.index()].push(ab);
+            outgoing[b.index()].push(ba);
+        }
+
+        // Sort each vertex's outgoing half-edges counter-clockwise, and link
+        // the half-edge coming in along each of them to the next one
+        // clockwise, which keeps the same face on their left.
+        for edges in &mut outgoing {
+            let pivot = vertices[half_edges[edges[0]].origin].point;
+            let end = |e: HalfEdgeId<U>| vertices[half_edges[half_edges[e].twin].origin].point;
+            let half = |p: Point2D<f64, U>| p.y < pivot.y || (p.y == pivot.y && p.x < pivot.x);
+            edges.sort_by(|&e, &f| {
+                let (p, q) = (end(e), end(f));
+                half(p)
+                    .cmp(&half(q))
+                    .then_with(|| match orientation(pivot, p, q) {
+                        RelativeDirection::Left => Ordering::Less,
+                        RelativeDirection::Collinear => Ordering::Equal,
+                        RelativeDirection::Right => Ordering::Greater,
+                    })
+            });
+        }
+        for (i, edges) in outgoing.iter().enumerate() {
+            let k = edges.len();
+            for j in 0..k {
+                let incoming = half_edges[edges[j]].twin;
+                let next = edges[(j + k - 1) % k];
+                half_edges[incoming].next = next;
+                half_edges[next].prev = incoming;
+            }
+            let v = half_edges[edges[0]].origin;
+            debug_assert_eq!(v.index(), i);
+            vertices[v].edge = edges[0];
+        }
+
+        let mut graph = PlanarGraph {
+            vertices,
+            half_edges,
+            faces,
+            unbounded_face,
+        };
+        graph.find_faces();
+        graph
+    }
+
+    /// Find the cycles of half-edges around each face, and which faces the
+    /// cycles that don't enclose anything are inside of.
+    fn find_faces(&mut self) {
+        let mut cycles = vec![];
+        let mut seen = vec![false; self.half_edges.len()];
+        for (e, _) in self.half_edges.iter() {
+            if !seen[e.index()] {
+                let cycle: Vec<_> = self.boundary(e).collect();
+                for e in &cycle {
+                    seen[e.index()] = true;
+                }
+                cycles.push(cycle);
+            }
+        }
+
+        // Vertices in different connected components of the graph never lie on
+        // each others' cycles.
+        let mut components: Vec<usize> = (0..self.vertices.len()).collect();
+        fn find(components: &mut [usize], i: usize) -> usize {
+            let mut root = i;
+            while components[root] != root {
+                root = components[root];
+            }
+            components[i] = root;
+            root
+        }
+        for (_, e) in self.half_edges.iter() {
+            let (a, b) = (e.origin.index(), self.half_edges[e.twin].origin.index());
+            let (a, b) = (find(&mut components, a), find(&mut components, b));
+            components[a] = b;
+        }
+
+        // Counter-clockwise cycles are the outer boundaries of bounded faces.
+        let mut bounded = vec![];
+        let mut others = vec![];
+        for cycle in cycles {
+            let ring = self.ring(cycle[0]);
+            if ring.len() >= 3 && is_counter_clockwise(&ring) {
+                let face = self.faces.alloc(Face {
+                    outer: Some(cycle[0]),
+                    inner: vec![],
+                });
+                let component = find(&mut components, self.half_edges[cycle[0]].origin.index());
+                let polygon = Polygon::new(ring);
+                bounded.push((face, component, polygon.area(), polygon));
+                for e in cycle {
+                    self.half_edges[e].face = face;
+                }
+            } else {
+                others.push(cycle);
+            }
+        }
+
+        // The rest are inner boundaries of the smallest face around them.
+        for cycle in others {
+            let v = self.half_edges[cycle[0]].origin;
+            let component = find(&mut components, v.index());
+            let point = self.vertices[v].point;
+            let face = bounded
+                .iter()
+                .filter(|b| b.1 != component && b.3.contains_point(point))
+                .min_by(|a, b| a.2.partial_cmp(&b.2).unwrap())
+                .map_or(self.unbounded_face, |b| b.0);
+            self.faces[face].inner.push(cycle[0]);
+            for e in cycle {
+                self.half_edges[e].face = face;
+            }
+        }
+    }
+
+    /// Get the points around the cycle of half-edges that `edge` is on, with
+    /// any edges that are walked up and then straight back down removed.
+    fn ring(&self, edge: HalfEdgeId<U>) -> Vec<Point2D<f64, U>> {
+        let mut ring: Vec<Point2D<f64, U>> = vec![];
+        for e in self.boundary(edge) {
+            let p = self.vertices[self.half_edges[e].origin].point;
+            if ring.len() >= 2 && ring[ring.len() - 2] == p {
+                ring.pop();
+            } else {
+                ring.push(p);
+            }
+        }
+        // The same again where the cycle wraps around.
+        while ring.len() >= 3 {
+            let n = ring.len();
+            if ring[n - 1] == ring[1] {
+                ring.drain(..2);
+            } else if ring[n - 2] == ring[0] {
+                ring.truncate(n - 2);
+            } else {
+                break;
+            }
+        }
+        ring
+    }
+
+    /// Iterate over this graph's vertices.
+    pub fn vertices<'a>(&'a self) -> impl 'a + Iterator<Item = VertexId<U>> {
+        self.vertices.iter().map(|(id, _)| id)
+    }
+
+    /// Iterate over this graph's half-edges.
+    pub fn half_edges<'a>(&'a self) -> impl 'a + Iterator<Item = HalfEdgeId<U>> {
+        self.half_edges.iter().map(|(id, _)| id)
+    }
+
+    /// Iterate over this graph's faces, including the unbounded face.
+    pub fn faces<'a>(&'a self) -> impl 'a + Iterator<Item = FaceId<U>> {
+        self.faces.iter().map(|(id, _)| id)
+    }
+
+    /// Iterate over this graph's bounded faces, which is all of them except
+    /// for the unbounded face.
+    pub fn bounded_faces<'a>(&'a self) -> impl 'a + Iterator<Item = FaceId<U>> {
+        let unbounded_face = self.unbounded_face;
+        self.faces().filter(move |&f| f != unbounded_face)
+    }
+
+    /// Get the unbounded face, which is outside of everything else.
+    pub fn unbounded_face(&self) -> FaceId<U> {
+        self.unbounded_face
+    }
+
+    /// Get the vertex with the given id.
+    pub fn vertex(&self, id: VertexId<U>) -> &Vertex<U> {
+        &self.vertices[id]
+    }
+
+    /// Get the half-edge with the given id.
+    pub fn half_edge(&self, id: HalfEdgeId<U>) -> &HalfEdge<U> {
+        &self.half_edges[id]
+    }
+
+    /// Get the face with the given id.
+    pub fn face(&self, id: FaceId<U>) -> &Face<U> {
+        &self.faces[id]
+    }
+
+    /// Get the line segment that the given half-edge runs along, from its
+    /// origin to its twin's origin.
+    pub fn line(&self, edge: HalfEdgeId<U>) -> Line<f64, U> {
+        let e = &self.half_edges[edge];
+        Line::new(
+            self.vertices[e.origin].point,
+            self.vertices[self.half_edges[e.twin].origin].point,
+        )
+    }
+
+    /// Walk around a face's boundary, starting at the given half-edge, and
+    /// following each half-edge's `next` until getting back to it.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart::geom::{line, Line};
+    /// use fart::planar::PlanarGraph;
+    ///
+    /// let lines: Vec<Line<f64, UnknownUnit>> = vec![
+    ///     line(point2(0.0, 0.0), point2(4.0, 0.0)),
+    ///     line(point2(4.0, 0.0), point2(0.0, 3.0)),
+    ///     line(point2(0.0, 3.0), point2(0.0, 0.0)),
+    /// ];
+    /// let graph = PlanarGraph::from_lines(&lines);
+    ///
+    /// let triangle = graph.bounded_faces().next().unwrap();
+    /// let edge = graph.face(triangle).outer_boundary().unwrap();
+    /// let perimeter: f64 = graph.boundary(edge).map(|e| graph.line(e).length()).sum();
+    /// assert_eq!(perimeter, 12.0);
+    /// ```
+    pub fn boundary<'a>(&'a self, edge: HalfEdgeId<U>) -> impl 'a + Iterator<Item = HalfEdgeId<U>> {
+        let mut current = Some(edge);
+        iter::from_fn(move || {
+            let e = current?;
+            let next = self.half_edges[e].next;
+            current = if next == edge { None } else { Some(next) };
+            Some(e)
+        })
+    }
+
+    /// Iterate over the half-edges that start at the given vertex, in
+    /// counter-clockwise order.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart::geom::{line, Line};
+    /// use fart::planar::PlanarGraph;
+    ///
+    /// // A plus sign.
+    /// let lines: Vec<Line<f64, UnknownUnit>> = vec![
+    ///     line(point2(-1.0, 0.0), point2(1.0, 0.0)),
+    ///     line(point2(0.0, -1.0), point2(0.0, 1.0)),
+    /// ];
+    /// let graph = PlanarGraph::from_lines(&lines);
+    ///
+    /// let center = graph
+    ///     .vertices()
+    ///     .find(|&v| graph.vertex(v).point() == point2(0.0, 0.0))
+    ///     .unwrap();
+    /// let mut ends: Vec<_> = graph.outgoing(center).map(|e| graph.line(e).b).collect();
+    ///
+    /// // Rotate the ends to start from the right, for comparison.
+    /// let right = ends.iter().position(|&p| p == point2(1.0, 0.0)).unwrap();
+    /// ends.rotate_left(right);
+    /// assert_eq!(ends, vec![
+    ///     point2(1.0, 0.0),
+    ///     point2(0.0, 1.0),
+    ///     point2(-1.0, 0.0),
+    ///     point2(0.0, -1.0),
+    /// ]);
+    /// ```
+    pub fn outgoing<'a>(&'a self, vertex: VertexId<U>) -> impl 'a + Iterator<Item = HalfEdgeId<U>> {
+        let first = self.vertices[vertex].edge;
+        let mut current = Some(first);
+        iter::from_fn(move || {
+            let e = current?;
+            let next = self.half_edges[self.half_edges[e].prev].twin;
+            current = if next == first { None } else { Some(next) };
+            Some(e)
+        })
+    }
+
+    /// Get the given face's outer boundary as a polygon, or `None` for the
+    /// unbounded face.
+    ///
+    /// Any edges that stick into the face, with the face on both of their
+    /// sides, are left out of the polygon. Its holes are too: see
+    /// `PlanarGraph::polygon_with_holes`.
+    pub fn polygon(&self, face: FaceId<U>) -> Option<Polygon<f64, U>> {
+        let edge = self.faces[face].outer?;
+        Some(Polygon::new(self.ring(edge)))
+    }
+
+    /// Get the given face as a polygon with holes, or `None` for the unbounded
+    /// face.
+    ///
+    /// The holes are the areas enclosed by the parts of the graph inside of
+    /// the face, but not connected to its outer boundary.
+    ///
+    /// ```
+    /// use euclid::{point2, UnknownUnit};
+    /// use fart::geom::{line, Line};
+    /// use fart::planar::PlanarGraph;
+    ///
+    /// let square = |min: f64, max: f64| -> Vec<Line<f64, UnknownUnit>> {
+    ///     vec![
+    ///         line(point2(min, min), point2(max, min)),
+    ///         line(point2(max, min), point2(max, max)),
+    ///         line(point2(max, max), point2(min, max)),
+    ///         line(point2(min, max), point2(min, min)),
+    ///     ]
+    /// };
+    /// let mut lines = square(0.0, 10.0);
+    /// lines.extend(square(2.0, 8.0));
+    /// let graph = PlanarGraph::from_lines(&lines);
+    ///
+    /// // The frame between the squares, and the inner square.
+    /// let mut areas: Vec<f64> = graph
+    ///     .bounded_faces()
+    ///     .map(|f| graph.polygon_with_holes(f).unwrap().area())
+    ///     .collect();
+    /// areas.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    /// assert_eq!(areas, vec![36.0, 64.0]);
+    /// ```
+    pub fn polygon_with_holes(&self, face: FaceId<U>) -> Option<PolygonWithHoles<f64, U>> {
+        let exterior = self.polygon(face)?;
+        let holes = self.faces[face]
+            .inner
+            .iter()
+            .filter_map(|&e| {
+                let mut ring = self.ring(e);
+                ring.reverse();
+                if ring.len() >= 3 {
+                    Some(Polygon::new(ring))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        Some(PolygonWithHoles::new(exterior, holes))
+    }
+
+    /// Get the faces that share an edge with the given face.
+    pub fn adjacent_faces(&self, face: FaceId<U>) -> Vec<FaceId<U>> {
+        let f = &self.faces[face];
+        let mut adjacent: Vec<_> = f
+            .outer
+            .iter()
+            .chain(&f.inner)
+            .flat_map(|&e| self.boundary(e))
+            .map(|e| self.half_edges[self.half_edges[e].twin].face)
+            .filter(|&g| g != face)
+            .collect();
+        adjacent.sort();
+        adjacent.dedup();
+        adjacent
+    }
+}
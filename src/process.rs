@@ -1,6 +1,7 @@
 //! Incrementally computed processs for drawing onto a canvas.
 
 use crate::canvas::Canvas;
+use fart_utils::Scalar;
 
 /// A process is something that is incrementally computed and drawn.
 ///
@@ -11,6 +12,9 @@ use crate::canvas::Canvas;
 /// This is a useful framework for particle systems, recursive subdivision,
 /// simulations, etc...
 ///
+/// Processes draw onto `i64` canvases by default. Implement `Process<f64>` to
+/// draw onto an `f64` canvas instead.
+///
 /// ## Example
 ///
 /// This example implements a process that does stochastic rectangle packing.
@@ -66,7 +70,7 @@ use crate::canvas::Canvas;
 ///     }
 /// }
 /// ```
-pub trait Process {
+pub trait Process<T = i64> {
     /// Update the process's state.
     ///
     /// If the process is complete, return `true`. Then there will be a final
@@ -74,19 +78,20 @@ pub trait Process {
     ///
     /// If the process is not finished, return `false` and `update` will be
     /// called again in the future.
-    fn update(&mut self, canvas: &Canvas) -> bool;
+    fn update(&mut self, canvas: &Canvas<T>) -> bool;
 
     /// Draw the current state of the process to the given canvas.
     ///
     /// If `last_frame` is true, then this is the last time that `draw` will be
     /// called.
-    fn draw(&self, canvas: &mut Canvas, last_frame: bool);
+    fn draw(&self, canvas: &mut Canvas<T>, last_frame: bool);
 }
 
 /// Run a process to completion, drawing it to the given canvas.
-pub fn run<P>(canvas: &mut Canvas, process: &mut P)
+pub fn run<T, P>(canvas: &mut Canvas<T>, process: &mut P)
 where
-    P: Process,
+    T: Scalar,
+    P: Process<T>,
 {
     loop {
         let last_frame = process.update(canvas);
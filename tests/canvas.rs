@@ -0,0 +1,61 @@
+use fart::aabb::Aabb;
+use fart::canvas::{Canvas, CanvasSpace, Inches};
+use fart::euclid::point2;
+use fart::geom::line;
+use fart::path::ToPaths;
+use fart::process::{self, Process};
+
+/// Draws a line a third of the way further along on each frame.
+#[derive(Default)]
+struct Thirds {
+    frame: usize,
+}
+
+impl Process<f64> for Thirds {
+    fn update(&mut self, _canvas: &Canvas<f64>) -> bool {
+        self.frame += 1;
+        self.frame == 3
+    }
+
+    fn draw(&self, canvas: &mut Canvas<f64>, _last_frame: bool) {
+        let x = (self.frame - 1) as f64 / 3.0;
+        canvas.draw(line(point2(x, 0.125), point2(x + 0.05, 0.7)));
+    }
+}
+
+#[test]
+fn process_on_a_float_canvas() {
+    let mut canvas = Canvas::with_view(Aabb::new(point2(0.0, 0.0), point2(1.5, 0.75)));
+    canvas.set_precision(4);
+    process::run(&mut canvas, &mut Thirds::default());
+
+    // The paths themselves aren't rounded.
+    let paths: Vec<_> = canvas.to_paths().collect();
+    assert_eq!(paths.len(), 3);
+    assert_eq!(paths[1].start(), Some(point2(1.0 / 3.0, 0.125)));
+
+    let svg = canvas.create_svg(Inches(3.0), Inches(1.5)).to_string();
+    assert!(svg.contains(r#"viewBox="0 0 1.5 0.75""#));
+    assert_eq!(svg.matches(r#"stroke-width="0.003""#).count(), 3);
+    assert!(svg.contains(r#"d="M0,0.125 L0.05,0.7""#));
+    assert!(svg.contains(r#"d="M0.3333,0.125 L0.3833,0.7""#));
+    assert!(svg.contains(r#"d="M0.6667,0.125 L0.7167,0.7""#));
+
+    // Fewer decimal places, which only round the path data.
+    canvas.set_precision(1);
+    let svg = canvas.create_svg(Inches(3.0), Inches(1.5)).to_string();
+    assert!(svg.contains(r#"viewBox="0 0 1.5 0.75""#));
+    assert_eq!(svg.matches(r#"stroke-width="0.003""#).count(), 3);
+    assert!(svg.contains(r#"d="M0.3,0.1 L0.4,0.7""#));
+}
+
+#[test]
+fn new_canvases_have_i64_coordinates() {
+    // Too large for `i32`.
+    let canvas = Canvas::new(Aabb::new(point2(0, 0), point2(5_000_000_000, 1)));
+    assert_eq!(canvas.stroke_width(), 10_000_000);
+
+    let canvas: Canvas<i64> = canvas;
+    let view: &Aabb<i64, CanvasSpace> = canvas.view();
+    assert_eq!(view.width(), 5_000_000_000);
+}
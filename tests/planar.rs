@@ -0,0 +1,363 @@
+use fart::euclid::{point2, vec2, Angle};
+use fart::geom::{line, Circle, Line};
+use fart::path::{LineCommand, Path, ToPaths};
+use fart::planar::{FaceId, PlanarGraph};
+use quickcheck::{quickcheck, Arbitrary, Gen};
+use rand::distributions::{Distribution, Uniform};
+use std::f64::consts::PI;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct UnknownUnit;
+
+/// Random line segments between points on a small grid, so that many of them
+/// share endpoints, overlap, are vertical, or have zero length.
+#[derive(Clone, Debug)]
+struct GridSegments(Vec<Line<i64, UnknownUnit>>);
+
+impl Arbitrary for GridSegments {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let n = Uniform::new(0, 20).sample(g);
+        let coordinate = Uniform::new_inclusive(0, 6);
+        let mut point = || point2(coordinate.sample(g), coordinate.sample(g));
+        GridSegments((0..n).map(|_| line(point(), point())).collect())
+    }
+}
+
+/// Random line segments anywhere, which cross at points that have to be
+/// rounded.
+#[derive(Clone, Debug)]
+struct RandomSegments(Vec<Line<f64, UnknownUnit>>);
+
+impl Arbitrary for RandomSegments {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let n = Uniform::new(0, 20).sample(g);
+        let coordinate = Uniform::new(-100.0, 100.0);
+        let mut point = || point2(coordinate.sample(g), coordinate.sample(g));
+        RandomSegments((0..n).map(|_| line(point(), point())).collect())
+    }
+}
+
+fn square(min: f64, max: f64) -> Vec<Line<f64, UnknownUnit>> {
+    vec![
+        line(point2(min, min), point2(max, min)),
+        line(point2(max, min), point2(max, max)),
+        line(point2(max, max), point2(min, max)),
+        line(point2(min, max), point2(min, min)),
+    ]
+}
+
+fn areas(graph: &PlanarGraph<UnknownUnit>) -> Vec<f64> {
+    let mut areas: Vec<f64> = graph
+        .bounded_faces()
+        .map(|f| graph.polygon_with_holes(f).unwrap().area())
+        .collect();
+    areas.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    areas
+}
+
+fn face_with_area(graph: &PlanarGraph<UnknownUnit>, area: f64) -> FaceId<UnknownUnit> {
+    graph
+        .bounded_faces()
+        .find(|&f| graph.polygon_with_holes(f).unwrap().area() == area)
+        .unwrap()
+}
+
+/// Check that the half-edges are linked up consistently, that each face's
+/// boundaries are exactly the cycles with that face on their left, and that
+/// the numbers of vertices, edges, faces, and connected components agree with
+/// Euler's formula.
+fn check_invariants(graph: &PlanarGraph<UnknownUnit>) -> bool {
+    let linked = graph.half_edges().all(|e| {
+        let h = graph.half_edge(e);
+        let twin = graph.half_edge(h.twin());
+        h.twin() != e
+            && twin.twin() == e
+            && graph.half_edge(h.next()).prev() == e
+            && graph.half_edge(h.prev()).next() == e
+            && graph.half_edge(h.next()).origin() == twin.origin()
+            && graph.half_edge(h.next()).face() == h.face()
+            && graph.line(e).a != graph.line(e).b
+    });
+
+    let vertices = graph.vertices().all(|v| {
+        let outgoing: Vec<_> = graph.outgoing(v).collect();
+        !outgoing.is_empty() && outgoing.iter().all(|&e| graph.half_edge(e).origin() == v)
+    });
+
+    // Every half-edge is on exactly one of the faces' boundaries.
+    let mut seen = vec![0; graph.half_edges().count()];
+    let mut cycles = 0;
+    for f in graph.faces() {
+        let face = graph.face(f);
+        if (f == graph.unbounded_face()) != face.outer_boundary().is_none() {
+            return false;
+        }
+        for &start in face.outer_boundary().iter().chain(face.inner_boundaries()) {
+            cycles += 1;
+            for e in graph.boundary(start) {
+                if graph.half_edge(e).face() != f {
+                    return false;
+                }
+                seen[e.index()] += 1;
+            }
+        }
+    }
+    let covered = seen.iter().all(|&n| n == 1);
+
+    // Each connected component has one cycle around the outside of it, and
+    // the rest are the outer boundaries of the bounded faces.
+    let mut roots: Vec<usize> = (0..graph.vertices().count()).collect();
+    fn root(roots: &[usize], mut i: usize) -> usize {
+        while roots[i] != i {
+            i = roots[i];
+        }
+        i
+    }
+    for e in graph.half_edges() {
+        let twin = graph.half_edge(e).twin();
+        let a = root(&roots, graph.half_edge(e).origin().index());
+        let b = root(&roots, graph.half_edge(twin).origin().index());
+        roots[a] = b;
+    }
+    let components = (0..roots.len()).filter(|&i| roots[i] == i).count();
+    let faces = graph.faces().count();
+    let outer_cycles = cycles + 1 == faces + components;
+
+    // Euler's formula.
+    let (v, e) = (graph.vertices().count(), graph.half_edges().count() / 2);
+    let euler = v + faces == e + 1 + components;
+
+    linked && vertices && covered && outer_cycles && euler
+}
+
+quickcheck! {
+    fn invariants_of_grid_segments(s: GridSegments) -> bool {
+        check_invariants(&PlanarGraph::from_lines(&s.0))
+    }
+
+    fn invariants_of_random_segments(s: RandomSegments) -> bool {
+        check_invariants(&PlanarGraph::from_lines(&s.0))
+    }
+
+    fn bounded_faces_tile_the_squares(s: GridSegments) -> bool {
+        // With a frame around all of the segments, the bounded faces tile it.
+        let mut lines: Vec<Line<f64, UnknownUnit>> = s.0.iter().map(|l| l.cast()).collect();
+        lines.extend(square(-1.0, 7.0));
+        let graph = PlanarGraph::from_lines(&lines);
+        let total: f64 = areas(&graph).iter().sum();
+        check_invariants(&graph) && (total - 64.0).abs() < 1e-9
+    }
+}
+
+#[test]
+fn nested_squares_are_holes() {
+    let mut lines = square(0.0, 10.0);
+    lines.extend(square(2.0, 8.0));
+    lines.extend(square(4.0, 6.0));
+    let graph = PlanarGraph::from_lines(&lines);
+    assert!(check_invariants(&graph));
+
+    // Each square's face has the next square in as its hole.
+    assert_eq!(areas(&graph), vec![4.0, 32.0, 64.0]);
+    let outer = face_with_area(&graph, 64.0);
+    let middle = face_with_area(&graph, 32.0);
+    let inner = face_with_area(&graph, 4.0);
+    assert_eq!(graph.face(outer).inner_boundaries().len(), 1);
+    assert_eq!(graph.face(middle).inner_boundaries().len(), 1);
+    assert!(graph.face(inner).inner_boundaries().is_empty());
+    assert_eq!(
+        graph.face(graph.unbounded_face()).inner_boundaries().len(),
+        1
+    );
+
+    // The hole's edges face the square around it.
+    let hole = graph.face(outer).inner_boundaries()[0];
+    let twin = graph.half_edge(hole).twin();
+    assert_eq!(graph.half_edge(twin).face(), middle);
+    assert_eq!(graph.adjacent_faces(middle), {
+        let mut faces = vec![outer, inner];
+        faces.sort();
+        faces
+    });
+}
+
+#[test]
+fn side_by_side_holes() {
+    let mut lines = square(0.0, 10.0);
+    lines.extend(square(1.0, 3.0));
+    lines.extend(square(5.0, 9.0));
+    // A line floating inside the big square, which doesn't enclose anything.
+    lines.push(line(point2(1.0, 5.0), point2(3.0, 8.0)));
+    let graph = PlanarGraph::from_lines(&lines);
+    assert!(check_invariants(&graph));
+
+    assert_eq!(areas(&graph), vec![4.0, 16.0, 80.0]);
+    let outer = face_with_area(&graph, 80.0);
+    assert_eq!(graph.face(outer).inner_boundaries().len(), 3);
+    assert_eq!(graph.polygon_with_holes(outer).unwrap().holes().len(), 2);
+    // The squares, and the unbounded face.
+    assert_eq!(graph.adjacent_faces(outer).len(), 3);
+}
+
+#[test]
+fn dangling_edges_are_left_out_of_polygons() {
+    let mut lines = square(0.0, 10.0);
+    // A spike into the square from one corner, out of it from another, and a
+    // little tree on the middle of one of its sides.
+    lines.push(line(point2(10.0, 10.0), point2(7.0, 7.0)));
+    lines.push(line(point2(0.0, 0.0), point2(-5.0, -5.0)));
+    lines.push(line(point2(5.0, 0.0), point2(5.0, 3.0)));
+    lines.push(line(point2(5.0, 3.0), point2(4.0, 4.0)));
+    lines.push(line(point2(5.0, 3.0), point2(6.0, 4.0)));
+    let graph = PlanarGraph::from_lines(&lines);
+    assert!(check_invariants(&graph));
+
+    let faces: Vec<_> = graph.bounded_faces().collect();
+    assert_eq!(faces.len(), 1);
+    let polygon = graph.polygon(faces[0]).unwrap();
+    assert_eq!(polygon.area(), 100.0);
+    // The corners, and the vertex where the tree meets the side.
+    assert_eq!(polygon.len(), 5);
+
+    // The spikes are on both sides of the face they stick into.
+    let outgoing = |p| {
+        let v = graph
+            .vertices()
+            .find(|&v| graph.vertex(v).point() == p)
+            .unwrap();
+        graph.outgoing(v).collect::<Vec<_>>()
+    };
+    for e in outgoing(point2(7.0, 7.0)) {
+        let twin = graph.half_edge(e).twin();
+        assert_eq!(graph.half_edge(e).face(), faces[0]);
+        assert_eq!(graph.half_edge(twin).face(), faces[0]);
+    }
+    for e in outgoing(point2(-5.0, -5.0)) {
+        assert_eq!(graph.half_edge(e).face(), graph.unbounded_face());
+    }
+}
+
+#[test]
+fn trees_have_no_bounded_faces() {
+    // A "T", which doesn't enclose anything.
+    let lines: Vec<Line<i64, UnknownUnit>> = vec![
+        line(point2(0, 0), point2(10, 0)),
+        line(point2(5, 0), point2(5, -10)),
+    ];
+    let graph = PlanarGraph::from_lines(&lines);
+    assert!(check_invariants(&graph));
+    assert_eq!(graph.vertices().count(), 4);
+    assert_eq!(graph.half_edges().count(), 6);
+    assert_eq!(graph.bounded_faces().count(), 0);
+    assert_eq!(
+        graph.face(graph.unbounded_face()).inner_boundaries().len(),
+        1
+    );
+}
+
+#[test]
+fn duplicate_and_zero_length_lines() {
+    let mut lines = square(0.0, 10.0);
+    lines.extend(square(0.0, 10.0));
+    // The same sides again, backwards, and overlapping halves of them.
+    lines.extend(square(0.0, 10.0).iter().map(|l| line(l.b, l.a)));
+    lines.push(line(point2(0.0, 0.0), point2(5.0, 0.0)));
+    // Points, on a corner, on a side, and inside.
+    lines.push(line(point2(0.0, 0.0), point2(0.0, 0.0)));
+    lines.push(line(point2(10.0, 5.0), point2(10.0, 5.0)));
+    lines.push(line(point2(3.0, 3.0), point2(3.0, 3.0)));
+    let graph = PlanarGraph::from_lines(&lines);
+    assert!(check_invariants(&graph));
+
+    assert_eq!(areas(&graph), vec![100.0]);
+    assert_eq!(graph.faces().count(), 2);
+    assert!(graph
+        .vertices()
+        .all(|v| graph.vertex(v).point() != point2(3.0, 3.0)));
+}
+
+#[test]
+fn from_paths_flattens_arcs() {
+    // A circle, cut in half.
+    let mut paths: Vec<Path<f64, UnknownUnit>> =
+        Circle::new(point2(0.0, 0.0), 10.0).to_paths().collect();
+    paths.push(Path::with_commands(vec![
+        LineCommand::MoveTo(point2(-20.0, 0.0)),
+        LineCommand::LineTo(point2(20.0, 0.0)),
+    ]));
+    let graph = PlanarGraph::from_paths(&paths);
+    assert!(check_invariants(&graph));
+
+    let areas = areas(&graph);
+    assert_eq!(areas.len(), 2);
+    for area in areas {
+        assert!((area - 50.0 * PI).abs() < 0.01 * 50.0 * PI);
+    }
+
+    // Every vertex is on the circle or the line.
+    assert!(graph.vertices().all(|v| {
+        let p = graph.vertex(v).point();
+        p.y == 0.0 || (p.to_vector().length() - 10.0).abs() < 0.1
+    }));
+}
+
+#[test]
+fn from_paths_flattens_curves() {
+    // A parabolic "D", and the same with a cubic Bézier curve, relative and
+    // rotated elliptical arcs, and smooth curves, side by side.
+    let d: Path<f64, UnknownUnit> = Path::with_commands(vec![
+        LineCommand::MoveTo(point2(0.0, 0.0)),
+        LineCommand::QuadraticBezierTo {
+            control: point2(10.0, 5.0),
+            end: point2(0.0, 10.0),
+        },
+        LineCommand::Close,
+    ]);
+    let cubic: Path<f64, UnknownUnit> = Path::with_commands(vec![
+        LineCommand::MoveTo(point2(20.0, 0.0)),
+        LineCommand::CubicBezierBy {
+            control_1: vec2(10.0, 0.0),
+            control_2: vec2(10.0, 10.0),
+            end: vec2(0.0, 10.0),
+        },
+        LineCommand::SmoothCubicBezierBy {
+            control: vec2(-10.0, -10.0),
+            end: vec2(0.0, -10.0),
+        },
+    ]);
+    let ellipse: Path<f64, UnknownUnit> = Path::with_commands(vec![
+        LineCommand::MoveTo(point2(40.0, 0.0)),
+        LineCommand::ArcTo {
+            x_radius: 5.0,
+            y_radius: 10.0,
+            x_axis_rotation: Angle::radians(0.0),
+            large_arc_flag: false,
+            sweep_flag: true,
+            end: point2(40.0, 20.0),
+        },
+        LineCommand::ArcBy {
+            x_radius: 5.0,
+            y_radius: 10.0,
+            x_axis_rotation: Angle::radians(0.0),
+            large_arc_flag: false,
+            sweep_flag: true,
+            end: vec2(0.0, -20.0),
+        },
+    ]);
+    let graph = PlanarGraph::from_paths(&[d, cubic, ellipse]);
+    assert!(check_invariants(&graph));
+
+    let areas = areas(&graph);
+    assert_eq!(areas.len(), 3);
+    let expected = [100.0 / 3.0, 120.0, 50.0 * PI];
+    let mut expected = expected.to_vec();
+    expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    for (area, expected) in areas.iter().zip(expected) {
+        assert!(
+            (area - expected).abs() < 0.01 * expected,
+            "{} != {}",
+            area,
+            expected
+        );
+    }
+}